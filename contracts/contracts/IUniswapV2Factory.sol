@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.7.0;
+
+/// @dev Subset of the Uniswap V2 factory interface used by the server to find token pairs.
+interface IUniswapV2Factory {
+    /// @dev Returns the address of the pair for tokens `tokenA` and `tokenB`, or zero address if it does not exist.
+    function getPair(address tokenA, address tokenB) external view returns (address pair);
+}
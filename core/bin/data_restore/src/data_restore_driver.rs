@@ -13,8 +13,13 @@ use zksync_types::{AccountId, AccountMap, AccountUpdate, BlockNumber};
 use crate::contract::{ZkSyncContractVersion, ZkSyncDeployedContract};
 use crate::storage_interactor::StorageInteractor;
 use crate::{
-    contract::get_genesis_account, eth_tx_helpers::get_ethereum_transaction,
-    events_state::EventsState, rollup_ops::RollupOpsBlock, tree_state::TreeState,
+    contract::get_genesis_account,
+    eth_tx_helpers::{
+        get_block_number_from_ethereum_transaction, get_ethereum_transaction, get_logs,
+    },
+    events_state::EventsState,
+    rollup_ops::RollupOpsBlock,
+    tree_state::TreeState,
 };
 use ethabi::Address;
 use std::convert::TryFrom;
@@ -125,9 +130,17 @@ where
         }
     }
 
+    /// Returns the upgrade events of the zkSync contract, starting from the given Ethereum block.
+    ///
+    /// # Arguments
+    ///
+    /// * `upgrade_gatekeeper_contract_address` - Upgrade gatekeeper contract address
+    /// * `from_block` - Ethereum block to start the search from, the contracts deployment block
+    ///
     pub async fn get_gatekeeper_logs(
         &self,
         upgrade_gatekeeper_contract_address: Address,
+        from_block: u64,
     ) -> anyhow::Result<Vec<Log>> {
         let gatekeeper_abi = upgrade_gatekeeper();
         let upgrade_contract_event = gatekeeper_abi
@@ -137,26 +150,34 @@ where
 
         let filter = FilterBuilder::default()
             .address(vec![upgrade_gatekeeper_contract_address])
-            .from_block(Web3BlockNumber::Earliest)
-            .to_block(Web3BlockNumber::Latest)
-            .topics(Some(vec![upgrade_contract_event]), None, None, None)
-            .build();
-
-        let result = self
-            .web3
-            .eth()
-            .logs(filter)
+            .topics(Some(vec![upgrade_contract_event]), None, None, None);
+        let last_block = EventsState::get_last_block_number(&self.web3).await?;
+
+        let result = get_logs(&self.web3, filter, from_block, last_block)
             .await
             .map_err(|e| anyhow::format_err!("No new logs: {}", e))?;
         Ok(result)
     }
+
+    /// Finds the versions of the zkSync contract and the Ethereum blocks they were used in.
+    ///
+    /// # Arguments
+    ///
+    /// * `upgrade_gatekeeper_contract_addr` - Upgrade gatekeeper contract address
+    /// * `zksync_contract_addr` - Rollup contract address
+    /// * `genesis_tx_hash` - Contracts deployment tx hash, upgrades are searched from its block
+    ///
     pub async fn init_contracts(
         &mut self,
         upgrade_gatekeeper_contract_addr: Address,
         zksync_contract_addr: Address,
+        genesis_tx_hash: H256,
     ) -> anyhow::Result<()> {
+        let genesis_transaction = get_ethereum_transaction(&self.web3, &genesis_tx_hash).await?;
+        let genesis_block_number =
+            get_block_number_from_ethereum_transaction(&genesis_transaction)?;
         let logs = self
-            .get_gatekeeper_logs(upgrade_gatekeeper_contract_addr)
+            .get_gatekeeper_logs(upgrade_gatekeeper_contract_addr, genesis_block_number)
             .await?;
 
         let mut last_updated_block = Web3BlockNumber::Earliest;
@@ -1,8 +1,10 @@
 // External uses
 use anyhow::{ensure, format_err};
 use web3::types::H256;
-use web3::types::{Transaction, TransactionId};
+use web3::types::{FilterBuilder, Log, Transaction, TransactionId};
 use web3::{Transport, Web3};
+// Workspace uses
+use zksync_eth_client::get_logs_chunked;
 
 pub const FUNC_NAME_HASH_LENGTH: usize = 4;
 
@@ -59,3 +61,31 @@ pub async fn get_ethereum_transaction<T: Transport>(
         .ok_or_else(|| format_err!("No tx with this hash"))?;
     Ok(web3_transaction)
 }
+
+/// Returns the logs matching the filter from the blocks `from_block..=to_block`.
+/// Large block ranges are requested in chunks.
+///
+/// # Arguments
+///
+/// * `web3` - Web3 provider url
+/// * `filter` - Logs filter without the block range
+/// * `from_block` - Start ethereum block number
+/// * `to_block` - End ethereum block number
+///
+pub async fn get_logs<T: Transport>(
+    web3: &Web3<T>,
+    filter: FilterBuilder,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<Log>, anyhow::Error> {
+    get_logs_chunked(
+        |filter| {
+            let eth = web3.eth();
+            async move { eth.logs(filter).await.map_err(anyhow::Error::from) }
+        },
+        filter,
+        from_block,
+        to_block,
+    )
+    .await
+}
@@ -7,7 +7,7 @@ use web3::types::{BlockNumber as Web3BlockNumber, FilterBuilder, Log, H256, U256
 use web3::{Transport, Web3};
 // Workspace deps
 use crate::contract::ZkSyncDeployedContract;
-use crate::eth_tx_helpers::get_block_number_from_ethereum_transaction;
+use crate::eth_tx_helpers::{get_block_number_from_ethereum_transaction, get_logs};
 use crate::events::{BlockEvent, EventType};
 use zksync_types::{Address, BlockNumber, TokenId};
 
@@ -174,8 +174,8 @@ impl EventsState {
         let token_logs = EventsState::get_token_added_logs(
             web3,
             governance_contract,
-            Web3BlockNumber::Number(from_block_number_u64.into()),
-            Web3BlockNumber::Number(to_block_number_u64.into()),
+            from_block_number_u64,
+            to_block_number_u64,
         )
         .await?;
         let mut logs = vec![];
@@ -186,30 +186,30 @@ impl EventsState {
             }
             let from_block_number = match zksync_contract.from {
                 Web3BlockNumber::Latest => panic!("Impossible in from block"),
-                Web3BlockNumber::Earliest => Web3BlockNumber::Number(from_block_number_u64.into()),
+                Web3BlockNumber::Earliest => from_block_number_u64,
                 Web3BlockNumber::Pending => unreachable!(),
                 Web3BlockNumber::Number(n) => {
                     if from_block_number_u64 > n.as_u64() {
                         continue;
                     }
-                    Web3BlockNumber::Number(from_block_number_u64.into())
+                    from_block_number_u64
                 }
             };
 
             let to_block_number = match zksync_contract.to {
                 Web3BlockNumber::Latest => {
                     from_block_number_u64 = to_block_number_u64;
-                    Web3BlockNumber::Number(to_block_number_u64.into())
+                    to_block_number_u64
                 }
                 Web3BlockNumber::Earliest => panic!("Impossible in to block"),
                 Web3BlockNumber::Pending => unreachable!(),
                 Web3BlockNumber::Number(n) => {
                     if to_block_number_u64 < n.as_u64() {
                         from_block_number_u64 = n.as_u64();
-                        Web3BlockNumber::Number(to_block_number_u64.into())
+                        to_block_number_u64
                     } else {
                         from_block_number_u64 = n.as_u64();
-                        Web3BlockNumber::Number(n)
+                        n.as_u64()
                     }
                 }
             };
@@ -239,8 +239,8 @@ impl EventsState {
     async fn get_token_added_logs<T: Transport>(
         web3: &Web3<T>,
         contract: &(ethabi::Contract, Contract<T>),
-        from: Web3BlockNumber,
-        to: Web3BlockNumber,
+        from: u64,
+        to: u64,
     ) -> Result<Vec<NewTokenEvent>, anyhow::Error> {
         let new_token_event_topic = contract
             .0
@@ -249,13 +249,9 @@ impl EventsState {
             .signature();
         let filter = FilterBuilder::default()
             .address(vec![contract.1.address()])
-            .from_block(from)
-            .to_block(to)
-            .topics(Some(vec![new_token_event_topic]), None, None, None)
-            .build();
+            .topics(Some(vec![new_token_event_topic]), None, None, None);
 
-        web3.eth()
-            .logs(filter)
+        get_logs(web3, filter, from, to)
             .await?
             .into_iter()
             .map(|event| {
@@ -277,8 +273,8 @@ impl EventsState {
     async fn get_block_logs<T: Transport>(
         web3: &Web3<T>,
         contract: &ZkSyncDeployedContract<T>,
-        from_block_number: Web3BlockNumber,
-        to_block_number: Web3BlockNumber,
+        from_block_number: u64,
+        to_block_number: u64,
     ) -> Result<Vec<Log>, anyhow::Error> {
         let block_verified_topic = contract
             .abi
@@ -303,14 +299,9 @@ impl EventsState {
 
         let filter = FilterBuilder::default()
             .address(vec![contract.web3_contract.address()])
-            .from_block(from_block_number)
-            .to_block(to_block_number)
-            .topics(Some(topics_vec), None, None, None)
-            .build();
-
-        let result = web3
-            .eth()
-            .logs(filter)
+            .topics(Some(topics_vec), None, None, None);
+
+        let result = get_logs(web3, filter, from_block_number, to_block_number)
             .await
             .map_err(|e| format_err!("No new logs: {}", e))?;
 
@@ -114,7 +114,11 @@ async fn main() {
     );
 
     driver
-        .init_contracts(config.upgrade_gatekeeper_addr, config.contract_addr)
+        .init_contracts(
+            config.upgrade_gatekeeper_addr,
+            config.contract_addr,
+            config.genesis_tx_hash,
+        )
         .await
         .expect("Wrong driver initialization");
 
@@ -10,13 +10,16 @@
 //! and `SUPPORTED_BLOCK_CHUNKS_SIZES_SETUP_POWERS` that are read from env in config files.
 //! Before generating parameters universal setup keys should be downloaded using `zksync plonk-setup` command.
 
+mod op_circuit_size;
 mod recursive_keys;
 mod sample_proofs;
 mod verifier_contract_generator;
 mod zksync_key;
 
+use std::path::PathBuf;
 use structopt::StructOpt;
 
+use crate::op_circuit_size::report_op_circuit_size;
 use crate::recursive_keys::{
     count_gates_recursive_verification_keys, make_recursive_verification_keys,
 };
@@ -36,6 +39,12 @@ enum Command {
     Contract,
     /// Counts available sizes (chunks and aggregated proof size) for available setups
     CircuitSize,
+    /// Reports constraints and gates count of the blocks containing a single operation of each type as JSON
+    OpCircuitSize {
+        /// Path to save the report to, the report is printed if not set
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(StructOpt)]
@@ -65,5 +74,9 @@ fn main() {
             calculate_and_print_max_zksync_main_circuit_size();
             count_gates_recursive_verification_keys();
         }
+        Command::OpCircuitSize { output } => {
+            report_op_circuit_size(output.as_deref())
+                .expect("Failed to report circuit size per operation");
+        }
     }
 }
@@ -0,0 +1,291 @@
+//! Report of the main circuit size for the blocks containing a single operation of each type,
+//! used to track the circuit cost changes of the operations between the commits.
+
+// Built-in deps
+use std::fs::File;
+use std::path::Path;
+// External deps
+use num::BigUint;
+use serde_json::json;
+// Workspace deps
+use zksync_circuit::witness::WitnessBuilder;
+use zksync_crypto::bellman::plonk::transpile_with_gates_count;
+use zksync_crypto::bellman::Circuit;
+use zksync_crypto::circuit::CircuitAccountTree;
+use zksync_crypto::franklin_crypto::circuit::test::TestConstraintSystem;
+use zksync_crypto::params::account_tree_depth;
+use zksync_crypto::Engine;
+use zksync_test_account::ZkSyncAccount;
+use zksync_types::operations::{
+    ChangePubKeyOp, CloseOp, DepositOp, ForcedExitOp, FullExitOp, NoopOp, TransferOp,
+    TransferToNewOp, WithdrawOp,
+};
+use zksync_types::{
+    Account, AccountId, Address, BlockNumber, Deposit, FullExit, TokenId, ZkSyncOp,
+};
+
+const FEE_ACCOUNT_ID: AccountId = AccountId(0);
+const TOKEN: TokenId = TokenId(0);
+const BALANCE: u64 = 1_000_000;
+
+/// Accounts the operations are performed with.
+struct TestAccounts {
+    /// Account with the balance and the public key set.
+    sender: (AccountId, ZkSyncAccount),
+    /// Account with the balance and without the public key set.
+    recipient: (AccountId, ZkSyncAccount),
+    /// Account without the balance, which is not in the tree.
+    new_account: (AccountId, ZkSyncAccount),
+    /// Account without the balance and with the public key set.
+    empty_account: (AccountId, ZkSyncAccount),
+}
+
+impl TestAccounts {
+    fn new() -> Self {
+        let account = |id| {
+            let account = ZkSyncAccount::rand();
+            account.set_account_id(Some(AccountId(id)));
+            (AccountId(id), account)
+        };
+
+        Self {
+            sender: account(1),
+            recipient: account(2),
+            new_account: account(3),
+            empty_account: account(4),
+        }
+    }
+
+    /// Creates the account tree with all the accounts except the new one.
+    fn account_tree(&self) -> CircuitAccountTree {
+        let mut tree = CircuitAccountTree::new(account_tree_depth());
+        tree.insert(
+            *FEE_ACCOUNT_ID,
+            Account::default_with_address(&Address::default()).into(),
+        );
+
+        let (id, sender) = &self.sender;
+        let mut account = Account::default_with_address(&sender.address);
+        account.add_balance(TOKEN, &BigUint::from(BALANCE));
+        account.pub_key_hash = sender.pubkey_hash;
+        tree.insert(**id, account.into());
+
+        let (id, recipient) = &self.recipient;
+        let mut account = Account::default_with_address(&recipient.address);
+        account.add_balance(TOKEN, &BigUint::from(BALANCE));
+        tree.insert(**id, account.into());
+
+        let (id, empty_account) = &self.empty_account;
+        let mut account = Account::default_with_address(&empty_account.address);
+        account.pub_key_hash = empty_account.pubkey_hash;
+        tree.insert(**id, account.into());
+
+        tree
+    }
+
+    /// Creates the operations of each type, named as they are in the report.
+    fn operations(&self) -> Vec<(&'static str, ZkSyncOp)> {
+        let (sender_id, sender) = &self.sender;
+        let (recipient_id, recipient) = &self.recipient;
+        let (new_account_id, new_account) = &self.new_account;
+        let (empty_account_id, empty_account) = &self.empty_account;
+        let amount = BigUint::from(BALANCE / 2);
+        let fee = BigUint::from(BALANCE / 100);
+
+        vec![
+            (
+                "deposit",
+                ZkSyncOp::Deposit(Box::new(DepositOp {
+                    priority_op: Deposit {
+                        from: sender.address,
+                        token: TOKEN,
+                        amount: amount.clone(),
+                        to: sender.address,
+                    },
+                    account_id: *sender_id,
+                })),
+            ),
+            (
+                "transfer",
+                ZkSyncOp::Transfer(Box::new(TransferOp {
+                    tx: sender
+                        .sign_transfer(
+                            TOKEN,
+                            "",
+                            amount.clone(),
+                            fee.clone(),
+                            &recipient.address,
+                            None,
+                            false,
+                            Default::default(),
+                        )
+                        .0,
+                    from: *sender_id,
+                    to: *recipient_id,
+                })),
+            ),
+            (
+                "transfer_to_new",
+                ZkSyncOp::TransferToNew(Box::new(TransferToNewOp {
+                    tx: sender
+                        .sign_transfer(
+                            TOKEN,
+                            "",
+                            amount.clone(),
+                            fee.clone(),
+                            &new_account.address,
+                            None,
+                            false,
+                            Default::default(),
+                        )
+                        .0,
+                    from: *sender_id,
+                    to: *new_account_id,
+                })),
+            ),
+            (
+                "withdraw",
+                ZkSyncOp::Withdraw(Box::new(WithdrawOp {
+                    tx: sender
+                        .sign_withdraw(
+                            TOKEN,
+                            "",
+                            amount,
+                            fee.clone(),
+                            &sender.address,
+                            None,
+                            false,
+                            Default::default(),
+                        )
+                        .0,
+                    account_id: *sender_id,
+                })),
+            ),
+            (
+                "change_pubkey",
+                ZkSyncOp::ChangePubKeyOffchain(Box::new(ChangePubKeyOp {
+                    tx: recipient.sign_change_pubkey_tx(
+                        None,
+                        false,
+                        TOKEN,
+                        fee.clone(),
+                        false,
+                        Default::default(),
+                    ),
+                    account_id: *recipient_id,
+                })),
+            ),
+            (
+                "forced_exit",
+                ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
+                    tx: sender.sign_forced_exit(
+                        TOKEN,
+                        fee,
+                        &recipient.address,
+                        None,
+                        false,
+                        Default::default(),
+                    ),
+                    target_account_id: *recipient_id,
+                    withdraw_amount: Some(BigUint::from(BALANCE).into()),
+                })),
+            ),
+            (
+                "full_exit",
+                ZkSyncOp::FullExit(Box::new(FullExitOp {
+                    priority_op: FullExit {
+                        account_id: *sender_id,
+                        eth_address: sender.address,
+                        token: TOKEN,
+                    },
+                    withdraw_amount: Some(BigUint::from(BALANCE).into()),
+                })),
+            ),
+            (
+                "close",
+                ZkSyncOp::Close(Box::new(CloseOp {
+                    tx: empty_account.sign_close(None, false),
+                    account_id: *empty_account_id,
+                })),
+            ),
+            ("noop", ZkSyncOp::Noop(NoopOp {})),
+        ]
+    }
+}
+
+/// Synthesizes the block containing only the given operation, and reports the amount of
+/// constraints and gates of the circuit in total and per block chunk.
+fn op_circuit_size(
+    name: &str,
+    op: &ZkSyncOp,
+    account_tree: &mut CircuitAccountTree,
+) -> anyhow::Result<serde_json::Value> {
+    let chunks = op.chunks();
+    let circuit = {
+        let mut witness_builder =
+            WitnessBuilder::new(account_tree, FEE_ACCOUNT_ID, BlockNumber(1), 0);
+        let fees: Vec<_> = witness_builder.add_zksync_op(op)?.into_iter().collect();
+        witness_builder.extend_pubdata_with_noops(chunks);
+        witness_builder.collect_fees(&fees);
+        witness_builder.calculate_pubdata_commitment();
+        witness_builder.into_circuit_instance()
+    };
+
+    let mut cs = TestConstraintSystem::<Engine>::new();
+    circuit
+        .clone()
+        .synthesize(&mut cs)
+        .map_err(|e| anyhow::format_err!("Failed to synthesize the circuit: {}", e))?;
+    let constraints = cs.num_constraints();
+    let unsatisfied = cs.which_is_unsatisfied().map(String::from);
+    if let Some(constraint) = &unsatisfied {
+        vlog::warn!(
+            "Circuit of the {} block is not satisfied: {}",
+            name,
+            constraint
+        );
+    }
+
+    let (gates, _) = transpile_with_gates_count(circuit)
+        .map_err(|e| anyhow::format_err!("Failed to transpile the circuit: {}", e))?;
+    vlog::info!(
+        "{}: chunks: {}, constraints: {}, gates: {}",
+        name,
+        chunks,
+        constraints,
+        gates
+    );
+
+    Ok(json!({
+        "operation": name,
+        "chunks": chunks,
+        "constraints": constraints,
+        "gates": gates,
+        "constraints_per_chunk": constraints / chunks,
+        "gates_per_chunk": gates / chunks,
+        "unsatisfied_constraint": unsatisfied,
+    }))
+}
+
+/// Reports the main circuit size for the blocks containing a single operation of each type
+/// as JSON, which is saved to the `output` file if provided, or printed otherwise.
+pub fn report_op_circuit_size(output: Option<&Path>) -> anyhow::Result<()> {
+    vlog::info!("Counting zkSync circuit size per operation type");
+    let accounts = TestAccounts::new();
+
+    let mut report = Vec::new();
+    for (name, op) in accounts.operations() {
+        let mut account_tree = accounts.account_tree();
+        report.push(op_circuit_size(name, &op, &mut account_tree)?);
+    }
+    let report = serde_json::Value::Array(report);
+
+    match output {
+        Some(path) => {
+            serde_json::to_writer_pretty(File::create(path)?, &report)?;
+            vlog::info!("Report saved to {}", path.display());
+        }
+        None => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(())
+}
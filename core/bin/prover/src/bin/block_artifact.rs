@@ -0,0 +1,183 @@
+//! Exports the witness of the block along with the proof stored for it into a self-contained file,
+//! and proves or verifies such a file offline, without the database or the prover server.
+//! Witness of the block should be present in the db to export it.
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use structopt::StructOpt;
+use zksync_prover::plonk_step_by_step_prover::{
+    PlonkStepByStepProver, PlonkStepByStepProverConfig,
+};
+use zksync_prover::{ProverConfig, ProverImpl};
+use zksync_prover_utils::api::JobResultData;
+use zksync_prover_utils::artifacts::BlockProofArtifact;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "zkSync block artifact tool",
+    author = "Matter Labs",
+    rename_all = "snake_case"
+)]
+enum Opt {
+    /// Exports the witness and the stored proof of the block from the db.
+    Export {
+        /// Number of the block to export
+        #[structopt(long)]
+        block: u32,
+        /// Path to the output file
+        #[structopt(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Verifies the proof stored in the artifact.
+    Verify {
+        /// Path to the artifact file
+        #[structopt(parse(from_os_str))]
+        artifact: PathBuf,
+    },
+    /// Proves the block from the artifact witness and verifies the resulting proof.
+    Prove {
+        /// Path to the artifact file
+        #[structopt(parse(from_os_str))]
+        artifact: PathBuf,
+        /// Path to save the artifact with the resulting proof to
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+}
+
+async fn export(block_number: BlockNumber, output: &Path) {
+    let connection_pool = ConnectionPool::new(Some(1));
+    let mut storage = connection_pool
+        .access_storage()
+        .await
+        .expect("Storage access failed");
+
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await
+        .expect("DB access fail")
+        .expect("Block not found in the db");
+    let witness = storage
+        .prover_schema()
+        .get_witness(block_number)
+        .await
+        .expect("DB access fail")
+        .expect("Witness for the block not found in the db");
+    let proof = storage
+        .prover_schema()
+        .load_proof(block_number)
+        .await
+        .expect("DB access fail");
+
+    let artifact = BlockProofArtifact::new(
+        block_number,
+        block.block_chunks_size,
+        serde_json::from_value(witness).expect("Failed to parse witness from db"),
+        proof,
+    );
+    artifact
+        .save(output)
+        .expect("Failed to save block artifact");
+
+    vlog::info!(
+        "Exported block {} (size {}, proof stored: {}) to {}",
+        block_number,
+        artifact.block_size,
+        artifact.proof.is_some(),
+        output.display()
+    );
+}
+
+fn load_artifact(path: &Path) -> BlockProofArtifact {
+    let artifact = BlockProofArtifact::load(path).expect("Failed to load block artifact");
+    vlog::info!(
+        "Loaded artifact of the block {}, block size: {}",
+        artifact.block_number,
+        artifact.block_size
+    );
+    artifact
+}
+
+/// Verifies the proof stored in the artifact. Returns `true` if the proof is present and valid.
+fn verify(artifact: &BlockProofArtifact) -> bool {
+    match artifact.verify_proof().expect("Failed to verify the proof") {
+        Some(true) => {
+            vlog::info!("Proof is valid");
+            true
+        }
+        Some(false) => {
+            vlog::error!("Proof is invalid");
+            false
+        }
+        None => {
+            vlog::error!("There is no proof in the artifact");
+            false
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    vlog::init();
+
+    match Opt::from_args() {
+        Opt::Export { block, output } => {
+            export(BlockNumber(block), &output).await;
+        }
+        Opt::Verify { artifact } => {
+            let artifact = load_artifact(&artifact);
+            if !verify(&artifact) {
+                std::process::exit(1);
+            }
+        }
+        Opt::Prove { artifact, output } => {
+            let mut artifact = load_artifact(&artifact);
+            if artifact.proof.is_some() {
+                vlog::info!("Verifying the stored proof");
+                verify(&artifact);
+            }
+
+            let prover =
+                PlonkStepByStepProver::create_from_config(PlonkStepByStepProverConfig::from_env());
+            let timer = Instant::now();
+            let proof = match prover
+                .create_proof(artifact.job_data())
+                .expect("Failed to prove the block")
+            {
+                JobResultData::BlockProof(proof) => proof,
+                JobResultData::AggregatedBlockProof(_) => {
+                    panic!("Single block proof is expected from the prover")
+                }
+            };
+            vlog::info!("Block proven in {}s", timer.elapsed().as_secs());
+
+            let stored_proof_matches = artifact
+                .proof
+                .as_ref()
+                .map(|stored_proof| stored_proof.0.input_values == proof.0.input_values);
+            artifact.proof = Some(proof);
+            vlog::info!("Verifying the resulting proof");
+            let valid = verify(&artifact);
+            if stored_proof_matches == Some(false) {
+                vlog::warn!("Public inputs of the resulting proof don't match the stored proof");
+            }
+
+            if let Some(output) = output {
+                artifact
+                    .save(&output)
+                    .expect("Failed to save block artifact");
+                vlog::info!(
+                    "Saved artifact with the resulting proof to {}",
+                    output.display()
+                );
+            }
+            if !valid {
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -80,8 +80,11 @@ pub trait ProverImpl {
     /// Creates prover from config and API client.
     fn create_from_config(config: Self::Config) -> Self;
     fn get_request_aux_data(&self) -> ProverInputRequestAuxData {
-        Default::default()
         // TODO: Add the ability to define different config (ZKS-283).
+        ProverInputRequestAuxData {
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            ..Default::default()
+        }
     }
     /// Resource heavy operation
     fn create_proof(&self, data: JobRequestData) -> anyhow::Result<JobResultData>;
@@ -177,6 +180,7 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
             data: job_data,
             first_block,
             last_block,
+            deadline,
         } = prover_input_response;
         let job_data = if let Some(job_data) = job_data {
             job_data
@@ -190,6 +194,9 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
             first_block,
             last_block
         );
+        if let Some(deadline) = deadline {
+            vlog::info!("proof for job id {} is expected by {}", job_id, deadline);
+        }
 
         let heartbeat_future_handle = heartbeat_future_handle(
             client.clone(),
@@ -1,11 +1,13 @@
 // Built-in deps
 use std::sync::Mutex;
 // Workspace deps
-use zksync_config::ChainConfig;
+use zksync_config::{ChainConfig, ProverConfig as EnvProverConfig};
 use zksync_crypto::proof::{AggregatedProof, PrecomputedSampleProofs, SingleProof};
 use zksync_crypto::Engine;
 use zksync_prover_utils::aggregated_proofs::{gen_aggregate_proof, prepare_proof_data};
-use zksync_prover_utils::api::{JobRequestData, JobResultData};
+use zksync_prover_utils::api::{
+    JobRequestData, JobResultData, ProverCapabilities, ProverInputRequestAuxData,
+};
 use zksync_prover_utils::{PlonkVerificationKey, SetupForStepByStepProver};
 use zksync_utils::parse_env;
 // Local deps
@@ -30,6 +32,7 @@ pub struct PlonkStepByStepProverConfig {
     pub block_sizes: Vec<usize>,
     pub download_setup_from_network: bool,
     pub aggregated_proof_sizes_with_setup_pow: Vec<(usize, u32)>,
+    pub available_ram: Option<u64>,
 }
 
 impl ProverConfig for PlonkStepByStepProverConfig {
@@ -45,6 +48,7 @@ impl ProverConfig for PlonkStepByStepProverConfig {
             all_block_sizes: env_config.circuit.supported_block_chunks_sizes,
             block_sizes: env_config.state_keeper.block_chunk_sizes,
             aggregated_proof_sizes_with_setup_pow,
+            available_ram: EnvProverConfig::from_env().prover.available_ram,
         }
     }
 }
@@ -135,6 +139,25 @@ impl PlonkStepByStepProver {
 impl ProverImpl for PlonkStepByStepProver {
     type Config = PlonkStepByStepProverConfig;
 
+    fn get_request_aux_data(&self) -> ProverInputRequestAuxData {
+        let capabilities = ProverCapabilities {
+            block_sizes: self.config.block_sizes.clone(),
+            aggregated_proof_sizes: self
+                .config
+                .aggregated_proof_sizes_with_setup_pow
+                .iter()
+                .map(|(size, _)| *size)
+                .collect(),
+            available_ram: self.config.available_ram,
+        };
+
+        ProverInputRequestAuxData {
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            capabilities: Some(capabilities),
+            ..Default::default()
+        }
+    }
+
     fn create_proof(&self, data: JobRequestData) -> Result<JobResultData, anyhow::Error> {
         let proof = match data {
             JobRequestData::AggregatedBlockProof(proofs_to_aggregate) => {
@@ -23,8 +23,9 @@ use zksync_prover::plonk_step_by_step_prover::{
 };
 use zksync_prover::{ProverImpl, ShutdownRequest};
 use zksync_prover_utils::api::{
-    JobRequestData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
+    JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
 };
+use zksync_prover_utils::artifacts::BlockProofArtifact;
 use zksync_types::{
     block::smallest_block_size_for_chunks, operations::DepositOp, Account, AccountId, Address,
     BlockNumber, Deposit, TokenId,
@@ -47,6 +48,7 @@ impl Default for MockProverConfigs {
             aggregated_proof_sizes_with_setup_pow: vec![(1, 22), (4, 23), (8, 24), (18, 25)],
             block_sizes: vec![10, 32],
             download_setup_from_network: false,
+            available_ram: None,
         };
         let dummy_config = DummyProverConfig {
             block_sizes: vec![10, 32],
@@ -56,10 +58,15 @@ impl Default for MockProverConfigs {
                 heartbeat_interval: 1000,
                 cycle_wait: 500,
                 request_timeout: 1,
+                available_ram: None,
             },
             core: zksync_config::configs::prover::Core {
                 gone_timeout: 2,
                 idle_provers: 1,
+                max_provers: 20,
+                max_job_attempts: 3,
+                ram_per_constraint: 2048,
+                verify_proofs: true,
             },
             witness_generator: zksync_config::configs::prover::WitnessGenerator {
                 prepare_data_interval: 5000,
@@ -249,6 +256,51 @@ async fn test_publishing_proof() {
     };
 }
 
+/// Checks that the block artifact is saved and loaded back intact, and the proof stored in it
+/// is verified against the public input of the block.
+#[test]
+fn test_block_artifact_round_trip() {
+    let (witness, block_size) = match test_data_for_prover() {
+        JobRequestData::BlockProof(witness, block_size) => (witness, block_size),
+        JobRequestData::AggregatedBlockProof(_) => unreachable!(),
+    };
+    let path =
+        std::env::temp_dir().join(format!("zksync_block_artifact_{}.json", std::process::id()));
+
+    // Artifact without a proof.
+    let artifact = BlockProofArtifact::new(BlockNumber(1), block_size, witness, None);
+    artifact.save(&path).unwrap();
+    let mut artifact = BlockProofArtifact::load(&path).unwrap();
+    assert_eq!(artifact.block_number, BlockNumber(1));
+    assert_eq!(artifact.block_size, block_size);
+    assert_eq!(
+        artifact.public_data_commitment,
+        artifact.witness.public_data_commitment
+    );
+    assert_eq!(artifact.verify_proof().unwrap(), None);
+
+    // Artifact with the proof of the block.
+    let prover =
+        PlonkStepByStepProver::create_from_config(MockProverConfigs::default().plonk_config);
+    let proof = match prover.create_proof(artifact.job_data()).unwrap() {
+        JobResultData::BlockProof(proof) => proof,
+        JobResultData::AggregatedBlockProof(_) => panic!("single block proof is expected"),
+    };
+    artifact.proof = Some(proof);
+    artifact.save(&path).unwrap();
+    let mut artifact = BlockProofArtifact::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(artifact.verify_proof().unwrap(), Some(true));
+
+    // Proof doesn't match the public input of the block.
+    artifact.proof.as_mut().unwrap().0.input_values = vec![Fr::from_str("1").unwrap()];
+    assert_eq!(artifact.verify_proof().unwrap(), Some(false));
+
+    // Public input doesn't match the witness.
+    artifact.public_data_commitment = Fr::from_str("1").unwrap();
+    assert!(artifact.verify_proof().is_err());
+}
+
 #[derive(Debug, Clone, Default)]
 struct MockApiClient {
     /// All published proofs are saved by `job_id`.
@@ -269,6 +321,7 @@ impl zksync_prover::ApiClient for MockApiClient {
             first_block: BlockNumber(1),
             last_block: BlockNumber(1),
             data: Some(test_data_for_prover()),
+            deadline: None,
         };
 
         Ok(response)
@@ -16,6 +16,7 @@ fn generate_transactions(number: usize) -> IncomingTxBatchForFee {
         tx_types,
         addresses,
         token_like: TokenLike::Symbol("wBTC".to_string()),
+        tx_tokens: Vec::new(),
     }
 }
 
@@ -4,23 +4,31 @@ use std::thread;
 
 // External uses
 use actix_web::dev::ServiceRequest;
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{web, App, HttpMessage, HttpRequest, HttpResponse, HttpServer};
 use actix_web_httpauth::extractors::{
     bearer::{BearerAuth, Config},
     AuthenticationError,
 };
 use actix_web_httpauth::middleware::HttpAuthentication;
-use futures::channel::mpsc;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
 use jsonwebtoken::errors::Error as JwtError;
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 
 // Local uses
+use crate::fee_ticker::TickerRequest;
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens, Address, TokenId};
+use zksync_types::{
+    ethereum::{ETHSenderAdminActionType, EthOpId},
+    tokens::{self, FeeTokenSettings},
+    Address, TokenId, TokenLike, U256,
+};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PayloadAuthToken {
     /// Subject (whom auth token refers to).
     sub: String,
@@ -32,6 +40,7 @@ struct PayloadAuthToken {
 struct AppState {
     secret_auth: String,
     connection_pool: ConnectionPool,
+    ticker_request_sender: mpsc::Sender<TickerRequest>,
 }
 
 impl AppState {
@@ -43,6 +52,20 @@ impl AppState {
     }
 }
 
+/// Returns the subject of the auth token used to perform the request,
+/// it is recorded as the author of the changes.
+fn request_subject(req: &HttpRequest) -> String {
+    req.extensions()
+        .get::<PayloadAuthToken>()
+        .map(|payload| payload.sub.clone())
+        .unwrap_or_default()
+}
+
+fn storage_error(e: anyhow::Error) -> actix_web::Error {
+    vlog::warn!("failed to process request in the storage: {}", e);
+    actix_web::error::ErrorInternalServerError("storage layer error")
+}
+
 /// Token that contains information to add to the server
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 struct AddTokenRequest {
@@ -69,10 +92,11 @@ impl<'a> AuthTokenValidator<'a> {
     }
 
     /// Validate JsonWebToken
-    fn validate_auth_token(&self, token: &str) -> Result<(), JwtError> {
-        decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
+    fn validate_auth_token(&self, token: &str) -> Result<PayloadAuthToken, JwtError> {
+        let token_data =
+            decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
 
-        Ok(())
+        Ok(token_data.claims)
     }
 
     async fn validator(
@@ -82,8 +106,10 @@ impl<'a> AuthTokenValidator<'a> {
     ) -> actix_web::Result<ServiceRequest> {
         let config = req.app_data::<Config>().cloned().unwrap_or_default();
 
-        self.validate_auth_token(credentials.token())
+        let payload = self
+            .validate_auth_token(credentials.token())
             .map_err(|_| AuthenticationError::from(config))?;
+        req.extensions_mut().insert(payload);
 
         Ok(req)
     }
@@ -126,6 +152,264 @@ async fn add_token(
     Ok(HttpResponse::Ok().json(token))
 }
 
+async fn resolve_token(
+    storage: &mut zksync_storage::StorageProcessor<'_>,
+    token_like: &str,
+) -> actix_web::Result<tokens::Token> {
+    storage
+        .tokens_schema()
+        .get_token(TokenLike::parse(token_like))
+        .await
+        .map_err(storage_error)?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("token not found"))
+}
+
+/// Makes the fee ticker reload the changed fee token settings.
+async fn invalidate_fee_token_settings(data: &AppState) {
+    data.ticker_request_sender
+        .clone()
+        .send(TickerRequest::InvalidateFeeTokenSettings)
+        .await
+        .unwrap_or_else(|e| {
+            vlog::warn!(
+                "failed to invalidate fee token settings in the fee ticker: {}",
+                e
+            )
+        });
+}
+
+async fn fee_token_overrides(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let overrides = storage
+        .tokens_schema()
+        .load_fee_token_overrides()
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(overrides))
+}
+
+async fn set_fee_token_override(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    web::Path(token_like): web::Path<String>,
+    settings: web::Json<FeeTokenSettings>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let token = resolve_token(&mut storage, &token_like).await?;
+
+    let subject = request_subject(&req);
+    let fee_token_override = storage
+        .tokens_schema()
+        .store_fee_token_override(token.id, settings.into_inner(), &subject)
+        .await
+        .map_err(storage_error)?;
+    invalidate_fee_token_settings(&data).await;
+
+    vlog::info!(
+        "Fee settings of the token {} were changed by {}: {:?}",
+        token.symbol,
+        subject,
+        fee_token_override.settings
+    );
+    Ok(HttpResponse::Ok().json(fee_token_override))
+}
+
+async fn remove_fee_token_override(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    web::Path(token_like): web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let token = resolve_token(&mut storage, &token_like).await?;
+
+    let subject = request_subject(&req);
+    let removed = storage
+        .tokens_schema()
+        .remove_fee_token_override(token.id, &subject)
+        .await
+        .map_err(storage_error)?;
+
+    if removed {
+        invalidate_fee_token_settings(&data).await;
+        vlog::info!(
+            "Fee settings of the token {} were reset by {}",
+            token.symbol,
+            subject
+        );
+    }
+    Ok(HttpResponse::Ok().json(removed))
+}
+
+async fn fee_token_override_history(
+    data: web::Data<AppState>,
+    web::Path(token_like): web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let token = resolve_token(&mut storage, &token_like).await?;
+
+    let history = storage
+        .tokens_schema()
+        .load_fee_token_override_history(token.id)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(history))
+}
+
+/// Applies the changes made in the fixed token prices file.
+async fn reload_ticker_prices(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+) -> actix_web::Result<HttpResponse> {
+    let (response, receiver) = oneshot::channel();
+    data.ticker_request_sender
+        .clone()
+        .send(TickerRequest::ReloadPrices { response })
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to send request to the fee ticker: {}", e);
+            actix_web::error::ErrorInternalServerError("fee ticker error")
+        })?;
+
+    receiver
+        .await
+        .map_err(|_| actix_web::error::ErrorInternalServerError("fee ticker error"))?
+        .map_err(|e| {
+            vlog::warn!("failed to reload ticker prices: {}", e);
+            actix_web::error::ErrorBadRequest(e.to_string())
+        })?;
+
+    vlog::info!("Ticker prices were reloaded by {}", request_subject(&req));
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Amount of the latest eth_sender admin actions returned by the API.
+const ETH_SENDER_ACTIONS_LIMIT: u32 = 100;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BumpGasPriceRequest {
+    /// Gas price of the replacement transaction in gwei.
+    /// If not set, it's chosen by the eth_sender as for the stuck transaction.
+    gas_price_gwei: Option<u64>,
+}
+
+async fn eth_sender_state(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let state = storage
+        .ethereum_schema()
+        .load_sender_state()
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(state))
+}
+
+async fn eth_sender_actions(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let actions = storage
+        .ethereum_schema()
+        .load_sender_admin_actions(ETH_SENDER_ACTIONS_LIMIT)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(actions))
+}
+
+/// Records the eth_sender admin action, which is then performed by the eth_sender.
+async fn add_eth_sender_action(
+    req: &HttpRequest,
+    data: &AppState,
+    action: ETHSenderAdminActionType,
+    eth_op_id: Option<EthOpId>,
+    gas_price: Option<U256>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    if let Some(eth_op_id) = eth_op_id {
+        let confirmed = storage
+            .ethereum_schema()
+            .is_eth_op_confirmed(eth_op_id)
+            .await
+            .map_err(storage_error)?
+            .ok_or_else(|| actix_web::error::ErrorNotFound("operation not found"))?;
+        if confirmed {
+            return Err(actix_web::error::ErrorBadRequest(
+                "operation is already confirmed",
+            ));
+        }
+    }
+
+    let subject = request_subject(req);
+    let admin_action = storage
+        .ethereum_schema()
+        .add_sender_admin_action(action, eth_op_id, gas_price, &subject)
+        .await
+        .map_err(storage_error)?;
+
+    vlog::info!(
+        "eth_sender action {} was requested by {}: {:?}",
+        action,
+        subject,
+        admin_action
+    );
+    Ok(HttpResponse::Ok().json(admin_action))
+}
+
+async fn pause_eth_sender(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+) -> actix_web::Result<HttpResponse> {
+    add_eth_sender_action(&req, &data, ETHSenderAdminActionType::Pause, None, None).await
+}
+
+async fn resume_eth_sender(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+) -> actix_web::Result<HttpResponse> {
+    add_eth_sender_action(&req, &data, ETHSenderAdminActionType::Resume, None, None).await
+}
+
+async fn bump_eth_operation_gas_price(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    web::Path(eth_op_id): web::Path<EthOpId>,
+    request: Option<web::Json<BumpGasPriceRequest>>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.map(web::Json::into_inner).unwrap_or_default();
+    let gas_price = request
+        .gas_price_gwei
+        .map(|gwei| U256::from(gwei) * U256::exp10(9));
+
+    add_eth_sender_action(
+        &req,
+        &data,
+        ETHSenderAdminActionType::BumpGasPrice,
+        Some(eth_op_id),
+        gas_price,
+    )
+    .await
+}
+
+async fn resend_eth_operation(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    web::Path(eth_op_id): web::Path<EthOpId>,
+) -> actix_web::Result<HttpResponse> {
+    add_eth_sender_action(
+        &req,
+        &data,
+        ETHSenderAdminActionType::Resend,
+        Some(eth_op_id),
+        None,
+    )
+    .await
+}
+
 async fn run_server(app_state: AppState, bind_to: SocketAddr) {
     HttpServer::new(move || {
         let auth = HttpAuthentication::bearer(move |req, credentials| async {
@@ -143,6 +427,35 @@ async fn run_server(app_state: AppState, bind_to: SocketAddr) {
             .wrap(auth)
             .app_data(web::Data::new(app_state.clone()))
             .route("/tokens", web::post().to(add_token))
+            .route("/tokens/fee_overrides", web::get().to(fee_token_overrides))
+            .route(
+                "/tokens/{id}/fee_override",
+                web::put().to(set_fee_token_override),
+            )
+            .route(
+                "/tokens/{id}/fee_override",
+                web::delete().to(remove_fee_token_override),
+            )
+            .route(
+                "/tokens/{id}/fee_override/history",
+                web::get().to(fee_token_override_history),
+            )
+            .route(
+                "/ticker/prices/reload",
+                web::post().to(reload_ticker_prices),
+            )
+            .route("/eth_sender/state", web::get().to(eth_sender_state))
+            .route("/eth_sender/actions", web::get().to(eth_sender_actions))
+            .route("/eth_sender/pause", web::post().to(pause_eth_sender))
+            .route("/eth_sender/resume", web::post().to(resume_eth_sender))
+            .route(
+                "/eth_sender/operations/{id}/bump_gas_price",
+                web::post().to(bump_eth_operation_gas_price),
+            )
+            .route(
+                "/eth_sender/operations/{id}/resend",
+                web::post().to(resend_eth_operation),
+            )
     })
     .workers(1)
     .bind(&bind_to)
@@ -156,6 +469,7 @@ pub fn start_admin_server(
     bind_to: SocketAddr,
     secret_auth: String,
     connection_pool: zksync_storage::ConnectionPool,
+    ticker_request_sender: mpsc::Sender<TickerRequest>,
     panic_notify: mpsc::Sender<bool>,
 ) {
     thread::Builder::new()
@@ -166,6 +480,7 @@ pub fn start_admin_server(
                 let app_state = AppState {
                     connection_pool,
                     secret_auth,
+                    ticker_request_sender,
                 };
 
                 run_server(app_state, bind_to).await;
@@ -64,6 +64,7 @@ pub fn start_api_server(
         config.api.admin.bind_addr(),
         config.api.admin.secret_auth.clone(),
         connection_pool.clone(),
+        ticker_request_sender.clone(),
         panic_notify.clone(),
     );
 
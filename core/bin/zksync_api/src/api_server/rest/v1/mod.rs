@@ -9,7 +9,7 @@ use actix_web::{
 use Error as ApiError;
 // Workspace uses
 pub use zksync_api_client::rest::v1::{
-    Client, ClientError, Pagination, PaginationQuery, MAX_LIMIT,
+    Client, ClientError, HistoryQuery, Pagination, PaginationQuery, MAX_LIMIT,
 };
 use zksync_config::ZkSyncConfig;
 
@@ -32,6 +32,17 @@ mod transactions;
 
 type JsonResult<T> = std::result::Result<web::Json<T>, Error>;
 
+/// Checks that the history request does not ask for too many entries.
+fn check_history_limit(limit: u32) -> Result<(), Error> {
+    if limit > MAX_LIMIT {
+        return Err(Error::bad_request(format!(
+            "Limit should be lower than {}",
+            MAX_LIMIT
+        )));
+    }
+    Ok(())
+}
+
 pub(crate) fn api_scope(tx_sender: TxSender, zk_config: &ZkSyncConfig) -> Scope {
     web::scope("/api/v1")
         .service(accounts::api_scope(
@@ -367,7 +367,9 @@ impl TestServerConfig {
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
+                    None,
                 )
                 .await?;
             storage
@@ -390,6 +392,7 @@ impl TestServerConfig {
                         job_data.clone(),
                         0,
                         ProverJobType::SingleProof,
+                        Some(BLOCK_SIZE_CHUNKS),
                     )
                     .await?;
                 ProverSchema(&mut storage)
@@ -399,17 +402,18 @@ impl TestServerConfig {
                         job_data,
                         1,
                         ProverJobType::AggregatedProof,
+                        None,
                     )
                     .await?;
 
                 // Get job id.
                 let stored_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue(&Default::default())
                     .await?
                     .unwrap()
                     .job_id;
                 let stored_aggregated_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue(&Default::default())
                     .await?
                     .unwrap()
                     .job_id;
@@ -453,7 +457,9 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
+                        None,
                     )
                     .await?;
                 let eth_tx_hash = dummy_ethereum_tx_hash(id);
@@ -494,7 +500,9 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
+                        None,
                     )
                     .await?;
                 storage
@@ -16,7 +16,10 @@ use futures::{
 // Workspace uses
 use zksync_api_client::rest::v1::{TokenPriceKind, TokenPriceQuery};
 use zksync_storage::{ConnectionPool, QueryResult};
-use zksync_types::{Token, TokenLike};
+use zksync_types::{
+    tokens::{FeeTokenOverride, FeeTokenOverrideChange},
+    Token, TokenLike, TokenPrice,
+};
 
 use crate::{
     fee_ticker::{TickerRequest, TokenPriceRequestType},
@@ -24,7 +27,7 @@ use crate::{
 };
 
 // Local uses
-use super::{ApiError, JsonResult};
+use super::{check_history_limit, ApiError, HistoryQuery, JsonResult};
 
 /// Shared data between `api/v1/tokens` endpoints.
 #[derive(Clone)]
@@ -65,6 +68,58 @@ impl ApiTokensData {
         self.tokens.get_token(&mut storage, token_like).await
     }
 
+    async fn fee_token_override(
+        &self,
+        token_like: TokenLike,
+    ) -> QueryResult<Option<FeeTokenOverride>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let token = match self.tokens.get_token(&mut storage, token_like).await? {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+
+        storage
+            .tokens_schema()
+            .get_fee_token_override(token.id)
+            .await
+    }
+
+    async fn fee_token_override_history(
+        &self,
+        token_like: TokenLike,
+    ) -> QueryResult<Vec<FeeTokenOverrideChange>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let token = match self.tokens.get_token(&mut storage, token_like).await? {
+            Some(token) => token,
+            None => return Ok(Vec::new()),
+        };
+
+        storage
+            .tokens_schema()
+            .load_fee_token_override_history(token.id)
+            .await
+    }
+
+    async fn token_price_history(
+        &self,
+        token_like: TokenLike,
+        query: HistoryQuery,
+    ) -> QueryResult<Vec<TokenPrice>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let token = match self.tokens.get_token(&mut storage, token_like).await? {
+            Some(token) => token,
+            None => return Ok(Vec::new()),
+        };
+
+        storage
+            .tokens_schema()
+            .load_ticker_price_history(token.id, query.from, query.to, query.limit)
+            .await
+    }
+
     async fn token_price_usd(&self, token: TokenLike) -> QueryResult<Option<BigDecimal>> {
         let (price_sender, price_receiver) = oneshot::channel();
         self.fee_ticker
@@ -132,6 +187,47 @@ async fn token_price(
     Ok(Json(price))
 }
 
+async fn token_price_history(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+    web::Query(query): web::Query<HistoryQuery>,
+) -> JsonResult<Vec<TokenPrice>> {
+    check_history_limit(query.limit)?;
+    let token_like = TokenLike::parse(&token_like);
+
+    let history = data
+        .token_price_history(token_like, query)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(history))
+}
+
+async fn fee_token_override(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+) -> JsonResult<Option<FeeTokenOverride>> {
+    let token_like = TokenLike::parse(&token_like);
+
+    let fee_token_override = data
+        .fee_token_override(token_like)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(fee_token_override))
+}
+
+async fn fee_token_override_history(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+) -> JsonResult<Vec<FeeTokenOverrideChange>> {
+    let token_like = TokenLike::parse(&token_like);
+
+    let history = data
+        .fee_token_override_history(token_like)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(history))
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     tokens_db: TokenDBCache,
@@ -144,15 +240,28 @@ pub fn api_scope(
         .route("", web::get().to(tokens))
         .route("{id}", web::get().to(token_by_id))
         .route("{id}/price", web::get().to(token_price))
+        .route("{id}/price/history", web::get().to(token_price_history))
+        .route("{id}/fee_override", web::get().to(fee_token_override))
+        .route(
+            "{id}/fee_override/history",
+            web::get().to(fee_token_override_history),
+        )
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use zksync_types::{Address, TokenId};
+    use num::{rational::Ratio, BigUint};
+    use zksync_types::{
+        tokens::{FeeTokenEligibility, FeeTokenSettings},
+        Address, TokenId,
+    };
 
-    use super::{super::test_utils::TestServerConfig, *};
+    use super::{
+        super::{test_utils::TestServerConfig, MAX_LIMIT},
+        *,
+    };
 
     fn dummy_fee_ticker(prices: &[(TokenLike, BigDecimal)]) -> mpsc::Sender<TickerRequest> {
         let (sender, mut receiver) = mpsc::channel(10);
@@ -281,6 +390,102 @@ mod tests {
         );
         assert_eq!(client.token_by_id(&TokenLike::parse("XM")).await?, None);
 
+        // Fee token overrides requests
+        let settings = FeeTokenSettings {
+            eligibility: Some(FeeTokenEligibility::Denied),
+            ..Default::default()
+        };
+        let expected_override = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .store_fee_token_override(TokenId(1), settings.clone(), "admin")
+                .await?
+        };
+        assert_eq!(
+            client
+                .fee_token_override(&TokenLike::Id(TokenId(1)))
+                .await?,
+            Some(expected_override)
+        );
+        assert_eq!(
+            client
+                .fee_token_override(&TokenLike::Id(TokenId(2)))
+                .await?,
+            None
+        );
+        let history = client
+            .fee_token_override_history(&TokenLike::Id(TokenId(1)))
+            .await?;
+        // The latest change goes first.
+        assert_eq!(history[0].settings, Some(settings.clone()));
+        assert_eq!(history[0].changed_by, "admin");
+
+        // Fee token settings are cached until they're invalidated.
+        {
+            let tokens_cache = TokenDBCache::new();
+            let mut storage = cfg.pool.access_storage().await?;
+            assert_eq!(
+                tokens_cache
+                    .get_fee_token_settings(&mut storage, TokenId(1))
+                    .await?,
+                settings
+            );
+
+            storage
+                .tokens_schema()
+                .remove_fee_token_override(TokenId(1), "admin")
+                .await?;
+            assert_eq!(
+                tokens_cache
+                    .get_fee_token_settings(&mut storage, TokenId(1))
+                    .await?,
+                settings
+            );
+
+            tokens_cache.invalidate_fee_token_settings().await;
+            assert_eq!(
+                tokens_cache
+                    .get_fee_token_settings(&mut storage, TokenId(1))
+                    .await?,
+                FeeTokenSettings::default()
+            );
+        }
+
+        // Price history requests
+        let price = TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(42u32)),
+            last_updated: chrono::Utc::now(),
+        };
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .update_historical_ticker_price(TokenId(1), price.clone())
+                .await?;
+        }
+        let query = HistoryQuery {
+            from: Some(price.last_updated - chrono::Duration::seconds(1)),
+            to: None,
+            limit: 1,
+        };
+        let history = client
+            .token_price_history(&TokenLike::Id(TokenId(1)), query)
+            .await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].usd_price, price.usd_price);
+        // Too big limit is rejected.
+        client
+            .token_price_history(
+                &TokenLike::Id(TokenId(1)),
+                HistoryQuery {
+                    limit: MAX_LIMIT + 1,
+                    ..query
+                },
+            )
+            .await
+            .unwrap_err();
+
         server.stop().await;
         Ok(())
     }
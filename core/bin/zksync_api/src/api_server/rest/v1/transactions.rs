@@ -10,15 +10,17 @@ use actix_web::{
 
 // Workspace uses
 pub use zksync_api_client::rest::v1::{
-    FastProcessingQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee,
-    Receipt, TxData,
+    FastProcessingQuery, FeeHistoryQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee,
+    IncomingTxForFee, Receipt, TxData,
 };
 use zksync_storage::{
     chain::operations_ext::records::TxReceiptResponse, QueryResult, StorageProcessor,
 };
-use zksync_types::{tx::TxHash, BatchFee, BlockNumber, Fee, SignedZkSyncTx};
+use zksync_types::{
+    tx::TxHash, BatchFee, BlockNumber, Fee, FeeHistoryEntry, SignedZkSyncTx, TokenLike,
+};
 // Local uses
-use super::{Error as ApiError, JsonResult, Pagination, PaginationQuery};
+use super::{check_history_limit, Error as ApiError, JsonResult, Pagination, PaginationQuery};
 use crate::api_server::rpc_server::types::TxWithSignature;
 use crate::api_server::tx_sender::{SubmitError, TxSender};
 
@@ -91,6 +93,26 @@ impl ApiTransactionsData {
             .await
     }
 
+    async fn fee_history(&self, query: FeeHistoryQuery) -> QueryResult<Vec<FeeHistoryEntry>> {
+        let mut storage = self.tx_sender.pool.access_storage().await?;
+
+        let token_like = TokenLike::parse(&query.token);
+        let token = match self
+            .tx_sender
+            .tokens
+            .get_token(&mut storage, token_like)
+            .await?
+        {
+            Some(token) => token,
+            None => return Ok(Vec::new()),
+        };
+
+        storage
+            .tokens_schema()
+            .load_fee_history(token.id, query.from, query.to, query.limit)
+            .await
+    }
+
     async fn tx_status(&self, tx_hash: TxHash) -> QueryResult<Option<Receipt>> {
         let mut storage = self.tx_sender.pool.access_storage().await?;
 
@@ -293,13 +315,23 @@ async fn get_txs_batch_fee_in_wei(
         .collect();
     let fee = data
         .tx_sender
-        .get_txs_batch_fee_in_wei(txs, body.token_like)
+        .get_txs_batch_fee_in_wei(txs, body.token_like, body.tx_tokens)
         .await
         .map_err(ApiError::from)?;
 
     Ok(Json(fee))
 }
 
+async fn fee_history(
+    data: web::Data<ApiTransactionsData>,
+    web::Query(query): web::Query<FeeHistoryQuery>,
+) -> JsonResult<Vec<FeeHistoryEntry>> {
+    check_history_limit(query.limit)?;
+
+    let history = data.fee_history(query).await.map_err(ApiError::internal)?;
+    Ok(Json(history))
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiTransactionsData::new(tx_sender);
 
@@ -315,6 +347,7 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .route("submit", web::post().to(submit_tx))
         .route("submit/batch", web::post().to(submit_tx_batch))
         .route("fee/batch", web::post().to(get_txs_batch_fee_in_wei))
+        .route("fee/history", web::get().to(fee_history))
         .route("fee", web::post().to(get_txs_fee_in_wei))
 }
 
@@ -406,10 +439,16 @@ mod tests {
                     } => {
                         let fee = BatchFee {
                             total_fee: BigUint::from(transactions.len()),
+                            transactions: Vec::new(),
+                            options: Vec::new(),
                         };
 
                         response.send(Ok(fee)).expect("Unable to send response");
                     }
+                    TickerRequest::ReloadPrices { response } => {
+                        response.send(Ok(())).unwrap_or_default();
+                    }
+                    TickerRequest::InvalidateFeeTokenSettings => {}
                 }
             }
         });
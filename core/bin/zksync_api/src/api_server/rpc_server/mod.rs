@@ -270,12 +270,14 @@ impl RpcApp {
         mut ticker_request_sender: mpsc::Sender<TickerRequest>,
         transactions: Vec<(TxFeeTypes, Address)>,
         token: TokenLike,
+        fee_tokens: Vec<TokenLike>,
     ) -> Result<BatchFee> {
         let req = oneshot::channel();
         ticker_request_sender
             .send(TickerRequest::GetBatchTxFee {
                 transactions,
                 token: token.clone(),
+                fee_tokens,
                 response: req.0,
             })
             .await
@@ -217,6 +217,7 @@ impl RpcApp {
         tx_types: Vec<TxFeeTypes>,
         addresses: Vec<Address>,
         token: TokenLike,
+        tx_tokens: Option<Vec<TokenLike>>,
     ) -> Result<BatchFee> {
         let start = Instant::now();
         if tx_types.len() != addresses.len() {
@@ -226,6 +227,14 @@ impl RpcApp {
                 data: None,
             });
         }
+        let tx_tokens = tx_tokens.unwrap_or_default();
+        if !tx_tokens.is_empty() && tx_types.len() != tx_tokens.len() {
+            return Err(Error {
+                code: RpcErrorCodes::IncorrectTx.into(),
+                message: "Number of tx_types must be equal to the number of tx_tokens".to_string(),
+                data: None,
+            });
+        }
 
         let ticker = self.tx_sender.ticker_requests.clone();
         let token_allowed = Self::token_allowed_for_fees(ticker.clone(), token.clone()).await?;
@@ -235,7 +244,8 @@ impl RpcApp {
 
         let transactions: Vec<(TxFeeTypes, Address)> =
             (tx_types.iter().cloned().zip(addresses.iter().cloned())).collect();
-        let total_fee = Self::ticker_batch_fee_request(ticker, transactions, token.clone()).await?;
+        let total_fee =
+            Self::ticker_batch_fee_request(ticker, transactions, token.clone(), tx_tokens).await?;
 
         metrics::histogram!("api.rpc.get_txs_batch_fee_in_wei", start.elapsed());
         Ok(total_fee)
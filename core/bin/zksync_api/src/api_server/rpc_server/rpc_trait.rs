@@ -60,12 +60,15 @@ pub trait Rpc {
     ) -> FutureResp<Fee>;
 
     // _addresses argument is left for the backward compatibility.
+    // If tx_tokens are provided, the response contains the options to pay the fee
+    // by any of the batch transactions.
     #[rpc(name = "get_txs_batch_fee_in_wei", returns = "BatchFee")]
     fn get_txs_batch_fee_in_wei(
         &self,
         tx_types: Vec<TxFeeTypes>,
         _addresses: Vec<Address>,
         token_like: TokenLike,
+        tx_tokens: Option<Vec<TokenLike>>,
     ) -> FutureResp<BatchFee>;
 
     #[rpc(name = "get_token_price", returns = "BigDecimal")]
@@ -177,12 +180,16 @@ impl Rpc for RpcApp {
         tx_types: Vec<TxFeeTypes>,
         addresses: Vec<Address>,
         token_like: TokenLike,
+        tx_tokens: Option<Vec<TokenLike>>,
     ) -> FutureResp<BatchFee> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_get_txs_batch_fee_in_wei(tx_types, addresses, token_like))
+                .spawn(
+                    self_
+                        ._impl_get_txs_batch_fee_in_wei(tx_types, addresses, token_like, tx_tokens),
+                )
                 .await
                 .unwrap()
         };
@@ -364,6 +364,7 @@ impl TxSender {
             self.ticker_requests.clone(),
             transaction_types,
             eth_token.clone(),
+            Vec::new(),
         )
         .await?;
 
@@ -485,12 +486,28 @@ impl TxSender {
         Self::ticker_request(self.ticker_requests.clone(), tx_type, address, token).await
     }
 
+    /// Returns the fee for the batch in the requested token along with the fees of the separate
+    /// transactions. If the tokens of the batch transactions are provided, the result also contains
+    /// the options to pay the batch fee by any of its members, the cheapest one first.
     pub async fn get_txs_batch_fee_in_wei(
         &self,
         transactions: Vec<(TxFeeTypes, Address)>,
         token: TokenLike,
+        fee_tokens: Vec<TokenLike>,
     ) -> Result<BatchFee, SubmitError> {
-        Self::ticker_batch_fee_request(self.ticker_requests.clone(), transactions, token).await
+        if !fee_tokens.is_empty() && fee_tokens.len() != transactions.len() {
+            return Err(SubmitError::invalid_params(
+                "Number of tokens must be equal to the number of transactions",
+            ));
+        }
+
+        Self::ticker_batch_fee_request(
+            self.ticker_requests.clone(),
+            transactions,
+            token,
+            fee_tokens,
+        )
+        .await
     }
 
     /// For forced exits, we must check that target account exists for more
@@ -578,12 +595,14 @@ impl TxSender {
         mut ticker_request_sender: mpsc::Sender<TickerRequest>,
         transactions: Vec<(TxFeeTypes, Address)>,
         token: TokenLike,
+        fee_tokens: Vec<TokenLike>,
     ) -> Result<BatchFee, SubmitError> {
         let req = oneshot::channel();
         ticker_request_sender
             .send(TickerRequest::GetBatchTxFee {
                 transactions,
                 token: token.clone(),
+                fee_tokens,
                 response: req.0,
             })
             .await
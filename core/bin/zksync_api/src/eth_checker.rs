@@ -124,7 +124,7 @@ mod tests {
             transport,
             zksync_contract(),
             Default::default(),
-            PrivateKeySigner::new(Default::default()),
+            PrivateKeySigner::new(Default::default()).into(),
             Default::default(),
             0,
             1.0,
@@ -40,12 +40,12 @@ where
         validator: FeeTokenValidator<WATCHER>,
         requests: Receiver<TickerRequest>,
         db_pool: ConnectionPool,
+        token_db_cache: TokenDBCache,
         number_of_tickers: u8,
     ) -> Self {
         let mut tickers = vec![];
         let mut channels = vec![];
 
-        let token_db_cache = TokenDBCache::new();
         let price_cache = Arc::new(Mutex::new(HashMap::new()));
         let gas_price_cache = Arc::new(Mutex::new(None));
 
@@ -0,0 +1,167 @@
+//! Fee history sampler periodically asks the fee ticker for the fees of the
+//! common operations in every fee token and stores them in the database,
+//! so it's possible to find out later why a certain fee was charged.
+//!
+//! Token prices are stored in the history by the ticker itself once received.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use chrono::Utc;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use tokio::task::JoinHandle;
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    tokens::{ChangePubKeyFeeType, ChangePubKeyFeeTypeArg},
+    Address, Fee, Token, TokenLike, TxFeeTypes,
+};
+// Local deps
+use crate::fee_ticker::TickerRequest;
+
+/// Samples the fees computed by the fee ticker and removes the outdated history.
+pub struct FeeHistorySampler {
+    pool: ConnectionPool,
+    ticker_requests: mpsc::Sender<TickerRequest>,
+    sampling_interval: Duration,
+    retention: chrono::Duration,
+    /// Address of the existing account, used as a recipient for the transfer fee.
+    existing_account: Address,
+}
+
+impl FeeHistorySampler {
+    pub fn new(
+        pool: ConnectionPool,
+        ticker_requests: mpsc::Sender<TickerRequest>,
+        config: &ZkSyncConfig,
+    ) -> Self {
+        Self {
+            pool,
+            ticker_requests,
+            sampling_interval: config.ticker.history_sampling_interval(),
+            retention: config.ticker.history_retention(),
+            existing_account: config.chain.state_keeper.fee_account_addr,
+        }
+    }
+
+    /// Transactions which fees are sampled. Zero address is expected to not have an account,
+    /// so the transfer to it is charged as a transfer to the new account.
+    fn sampled_transactions(&self) -> Vec<(TxFeeTypes, Address)> {
+        vec![
+            (TxFeeTypes::Transfer, self.existing_account),
+            (TxFeeTypes::Transfer, Address::zero()),
+            (TxFeeTypes::Withdraw, self.existing_account),
+            (TxFeeTypes::FastWithdraw, self.existing_account),
+            (
+                TxFeeTypes::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
+                    ChangePubKeyFeeType::ECDSA,
+                )),
+                self.existing_account,
+            ),
+        ]
+    }
+
+    async fn token_allowed(&mut self, token: &Token) -> anyhow::Result<bool> {
+        let (response, receiver) = oneshot::channel();
+        self.ticker_requests
+            .send(TickerRequest::IsTokenAllowed {
+                token: TokenLike::Id(token.id),
+                response,
+            })
+            .await?;
+
+        receiver.await?
+    }
+
+    async fn fee(
+        &mut self,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: &Token,
+    ) -> anyhow::Result<Fee> {
+        let (response, receiver) = oneshot::channel();
+        self.ticker_requests
+            .send(TickerRequest::GetTxFee {
+                tx_type,
+                address,
+                token: TokenLike::Id(token.id),
+                response,
+            })
+            .await?;
+
+        receiver.await?
+    }
+
+    async fn sample_token_fees(&mut self, token: &Token) -> anyhow::Result<()> {
+        if !self.token_allowed(token).await? {
+            return Ok(());
+        }
+
+        let sampled_at = Utc::now();
+        let mut fees = Vec::new();
+        for (tx_type, address) in self.sampled_transactions() {
+            fees.push(self.fee(tx_type, address, token).await?);
+        }
+
+        self.pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .store_fee_history(token.id, &fees, sampled_at)
+            .await
+    }
+
+    async fn sample_fees(&mut self) -> anyhow::Result<()> {
+        let tokens = self
+            .pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .load_tokens()
+            .await?;
+
+        for token in tokens.values() {
+            // Failure to compute fee in one token should not prevent sampling the others.
+            if let Err(err) = self.sample_token_fees(token).await {
+                vlog::warn!("Failed to sample fees in token {}: {}", token.symbol, err);
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove_outdated_history(&mut self) -> anyhow::Result<()> {
+        self.pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .remove_outdated_ticker_history(Utc::now() - self.retention)
+            .await
+    }
+
+    pub async fn run(mut self) {
+        let mut timer = tokio::time::interval(self.sampling_interval);
+        loop {
+            timer.tick().await;
+
+            if let Err(err) = self.sample_fees().await {
+                vlog::warn!("Failed to sample fee history: {}", err);
+            }
+            if let Err(err) = self.remove_outdated_history().await {
+                vlog::warn!("Failed to remove outdated ticker history: {}", err);
+            }
+        }
+    }
+}
+
+pub fn run_fee_history_sampler(
+    db_pool: ConnectionPool,
+    ticker_requests: mpsc::Sender<TickerRequest>,
+    config: &ZkSyncConfig,
+) -> JoinHandle<()> {
+    let sampler = FeeHistorySampler::new(db_pool, ticker_requests, config);
+    tokio::spawn(sampler.run())
+}
@@ -23,11 +23,15 @@ use tokio::task::JoinHandle;
 use tokio::time::Instant;
 
 // Workspace deps
-use zksync_config::{configs::ticker::TokenPriceSource, ZkSyncConfig};
+use zksync_config::{
+    configs::ticker::{TokenLiquiditySource, TokenPriceSource},
+    ZkSyncConfig,
+};
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 use zksync_types::{
-    Address, BatchFee, ChangePubKeyOp, Fee, OutputFeeType, Token, TokenId, TokenLike, TransferOp,
-    TransferToNewOp, TxFeeTypes, WithdrawOp,
+    Address, BatchFee, BatchFeeOption, ChangePubKeyOp, Fee, OutputFeeType, Token, TokenId,
+    TokenLike, TransferOp, TransferToNewOp, TxFeeTypes, WithdrawOp,
 };
 use zksync_utils::ratio_to_big_decimal;
 
@@ -37,11 +41,14 @@ use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::MarketUpdater;
 use crate::fee_ticker::{
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, fixed::FixedPriceAPI,
+        FeeTickerAPI, TickerApi, CONNECTION_TIMEOUT,
     },
     validator::{
-        watcher::{TokenWatcher, UniswapTokenWatcher},
+        watcher::{
+            FileTokenWatcher, TokenWatcher, TokenWatcherWrapper, UniswapTokenWatcher,
+            UniswapV2PairsTokenWatcher,
+        },
         FeeTokenValidator,
     },
 };
@@ -49,6 +56,7 @@ use crate::utils::token_db_cache::TokenDBCache;
 use zksync_types::tokens::{ChangePubKeyFeeType, ChangePubKeyFeeTypeArg};
 
 mod constants;
+pub mod history_sampler;
 mod ticker_api;
 mod ticker_info;
 pub mod validator;
@@ -207,6 +215,9 @@ pub enum TickerRequest {
     GetBatchTxFee {
         transactions: Vec<(TxFeeTypes, Address)>,
         token: TokenLike,
+        /// Tokens of the batch transactions. If not empty, the response
+        /// will contain the options to pay the batch fee in these tokens.
+        fee_tokens: Vec<TokenLike>,
         response: oneshot::Sender<Result<BatchFee, anyhow::Error>>,
     },
     GetTokenPrice {
@@ -218,6 +229,12 @@ pub enum TickerRequest {
         token: TokenLike,
         response: oneshot::Sender<Result<bool, anyhow::Error>>,
     },
+    /// Reloads the token prices provided by the operator.
+    ReloadPrices {
+        response: oneshot::Sender<Result<(), anyhow::Error>>,
+    },
+    /// Drops the cached fee token settings after they were changed by the operator.
+    InvalidateFeeTokenSettings,
 }
 
 struct FeeTicker<API, INFO, WATCHER> {
@@ -241,8 +258,23 @@ pub fn run_ticker_task(
         not_subsidized_tokens: HashSet::from_iter(config.ticker.not_subsidized_tokens.clone()),
     };
 
-    let cache = (db_pool.clone(), TokenDBCache::new());
-    let watcher = UniswapTokenWatcher::new(config.ticker.uniswap_url.clone());
+    // Token cache is shared between the validator and the tickers, so the fee token settings
+    // invalidated via the admin API are reloaded by all of them.
+    let token_db_cache = TokenDBCache::new();
+    let cache = (db_pool.clone(), token_db_cache.clone());
+    let (price_source, price_source_location) = config.ticker.price_source();
+    // Fixed prices file also provides the token market volumes,
+    // so in this mode the ticker doesn't send any requests to the Internet.
+    let fixed_price_api = match price_source {
+        TokenPriceSource::Fixed => Some(
+            FixedPriceAPI::new(price_source_location).expect("failed to load fixed token prices"),
+        ),
+        TokenPriceSource::CoinGecko | TokenPriceSource::CoinMarketCap => None,
+    };
+    let watcher = fixed_price_api
+        .clone()
+        .map(TokenWatcherWrapper::from)
+        .unwrap_or_else(|| token_watcher(config));
     let validator = FeeTokenValidator::new(
         cache.clone(),
         chrono::Duration::seconds(config.ticker.available_liquidity_seconds as i64),
@@ -253,18 +285,17 @@ pub fn run_ticker_task(
 
     let updater = MarketUpdater::new(cache, watcher);
     tokio::spawn(updater.keep_updated(config.ticker.token_market_update_time));
-    let client = reqwest::ClientBuilder::new()
-        .timeout(CONNECTION_TIMEOUT)
-        .connect_timeout(CONNECTION_TIMEOUT)
-        .build()
-        .expect("Failed to build reqwest::Client");
-    let (price_source, base_url) = config.ticker.price_source();
     match price_source {
         TokenPriceSource::CoinMarketCap => {
-            let token_price_api =
-                CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
+            let token_price_api = CoinMarketCapAPI::new(
+                http_client(),
+                price_source_location
+                    .parse()
+                    .expect("Correct CoinMarketCap url"),
+            );
 
-            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
+            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api)
+                .with_token_db_cache(token_db_cache);
             let ticker_info = TickerInfo::new(db_pool);
             let fee_ticker = FeeTicker::new(
                 ticker_api,
@@ -278,9 +309,32 @@ pub fn run_ticker_task(
         }
 
         TokenPriceSource::CoinGecko => {
+            let token_price_api = CoinGeckoAPI::new(
+                http_client(),
+                price_source_location
+                    .parse()
+                    .expect("Correct CoinGecko url"),
+            )
+            .expect("failed to init CoinGecko client");
+            let ticker_info = TickerInfo::new(db_pool.clone());
+
+            let mut ticker_balancer = TickerBalancer::new(
+                token_price_api,
+                ticker_info,
+                ticker_config,
+                validator,
+                tricker_requests,
+                db_pool,
+                token_db_cache,
+                config.ticker.number_of_ticker_actors,
+            );
+            ticker_balancer.spawn_tickers();
+            tokio::spawn(ticker_balancer.run())
+        }
+
+        TokenPriceSource::Fixed => {
             let token_price_api =
-                CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
-                    .expect("failed to init CoinGecko client");
+                fixed_price_api.expect("Fixed price API is created for the fixed price source");
             let ticker_info = TickerInfo::new(db_pool.clone());
 
             let mut ticker_balancer = TickerBalancer::new(
@@ -290,6 +344,7 @@ pub fn run_ticker_task(
                 validator,
                 tricker_requests,
                 db_pool,
+                token_db_cache,
                 config.ticker.number_of_ticker_actors,
             );
             ticker_balancer.spawn_tickers();
@@ -298,6 +353,33 @@ pub fn run_ticker_task(
     }
 }
 
+fn http_client() -> reqwest::Client {
+    reqwest::ClientBuilder::new()
+        .timeout(CONNECTION_TIMEOUT)
+        .connect_timeout(CONNECTION_TIMEOUT)
+        .build()
+        .expect("Failed to build reqwest::Client")
+}
+
+/// Creates the token liquidity watcher chosen in the ticker configuration.
+fn token_watcher(config: &ZkSyncConfig) -> TokenWatcherWrapper {
+    match config.ticker.token_liquidity_source {
+        TokenLiquiditySource::UniswapSubgraph => {
+            UniswapTokenWatcher::new(config.ticker.uniswap_url.clone()).into()
+        }
+        TokenLiquiditySource::UniswapV2Contracts => UniswapV2PairsTokenWatcher::new(
+            EthereumGateway::from_config(config),
+            config.ticker.uniswap_v2_factory_address,
+            config.ticker.liquidity_quote_token,
+            config.ticker.liquidity_quote_token_decimals,
+        )
+        .into(),
+        TokenLiquiditySource::File => {
+            FileTokenWatcher::new(&config.ticker.liquidity_file_path).into()
+        }
+    }
+}
+
 impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<API, INFO, WATCHER> {
     fn new(
         api: API,
@@ -355,12 +437,23 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
                 TickerRequest::GetBatchTxFee {
                     transactions,
                     token,
+                    fee_tokens,
                     response,
                 } => {
-                    let fee = self.get_batch_from_ticker_in_wei(token, transactions).await;
+                    let fee = self
+                        .get_batch_with_options_from_ticker_in_wei(token, transactions, fee_tokens)
+                        .await;
                     metrics::histogram!("ticker.get_tx_fee", start.elapsed());
                     response.send(fee).unwrap_or_default()
                 }
+                TickerRequest::ReloadPrices { response } => {
+                    let result = self.api.reload_prices().await;
+                    metrics::histogram!("ticker.reload_prices", start.elapsed());
+                    response.send(result).unwrap_or_default()
+                }
+                TickerRequest::InvalidateFeeTokenSettings => {
+                    self.validator.invalidate_fee_token_settings().await;
+                }
             }
         }
     }
@@ -385,7 +478,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
     }
 
     /// Returns `true` if the token is subsidized.
-    fn is_token_subsidized(&self, token: &Token) -> bool {
+    async fn is_token_subsidized(&self, token: &Token) -> anyhow::Result<bool> {
         // We have disabled the subsidies up until the contract upgrade (when the prices will indeed become that
         // low), but however we want to leave ourselves the possibility to easily enable them if required.
         // Thus:
@@ -394,10 +487,20 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
             .map(|val| val == "true")
             .unwrap_or(false);
         if !subsidies_enabled {
-            return false;
+            return Ok(false);
         }
 
-        !self.config.not_subsidized_tokens.contains(&token.address)
+        // Setting configured by the operator takes precedence over the `not_subsidized_tokens` list.
+        if let Some(subsidized) = self
+            .validator
+            .fee_token_settings(token.id)
+            .await?
+            .subsidized
+        {
+            return Ok(subsidized);
+        }
+
+        Ok(!self.config.not_subsidized_tokens.contains(&token.address))
     }
 
     async fn get_fee_from_ticker_in_wei(
@@ -411,7 +514,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
 
         let gas_price_wei = self.api.get_gas_price_wei().await?;
         let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
-        let is_token_subsidized = self.is_token_subsidized(&token);
+        let is_token_subsidized = self.is_token_subsidized(&token).await?;
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
@@ -442,17 +545,30 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
 
         let gas_price_wei = self.api.get_gas_price_wei().await?;
         let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
-        let is_token_subsidized = self.is_token_subsidized(&token);
+        let is_token_subsidized = self.is_token_subsidized(&token).await?;
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
         let mut total_gas_tx_amount = BigUint::zero();
         let mut total_op_chunks = BigUint::zero();
+        let mut transactions = Vec::with_capacity(txs.len());
 
         for (tx_type, recipient) in txs {
-            let (_, gas_tx_amount, op_chunks) = self
+            let (fee_type, gas_tx_amount, op_chunks) = self
                 .gas_tx_amount(is_token_subsidized, tx_type, recipient)
                 .await;
+
+            let zkp_fee = (zkp_cost_chunk.clone() * op_chunks.clone()) * token_usd_risk.clone();
+            let gas_fee = (wei_price_usd.clone() * gas_tx_amount.clone() * scale_gas_price.clone())
+                * token_usd_risk.clone();
+            transactions.push(Fee::new(
+                fee_type,
+                zkp_fee,
+                gas_fee,
+                gas_tx_amount.clone(),
+                gas_price_wei.clone(),
+            ));
+
             total_gas_tx_amount += gas_tx_amount;
             total_op_chunks += op_chunks;
         }
@@ -460,11 +576,81 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         let total_zkp_fee = (zkp_cost_chunk * total_op_chunks) * token_usd_risk.clone();
         let total_gas_fee =
             (wei_price_usd * total_gas_tx_amount * scale_gas_price) * token_usd_risk;
-        let total_fee = BatchFee::new(&total_zkp_fee, &total_gas_fee);
+        let total_fee = BatchFee::new(&total_zkp_fee, &total_gas_fee, transactions);
 
         Ok(total_fee)
     }
 
+    /// Calculates the batch fee in the requested token and, if the tokens of the batch
+    /// transactions are provided, the options to pay this fee by any of the batch members.
+    async fn get_batch_with_options_from_ticker_in_wei(
+        &mut self,
+        token: TokenLike,
+        txs: Vec<(TxFeeTypes, Address)>,
+        fee_tokens: Vec<TokenLike>,
+    ) -> anyhow::Result<BatchFee> {
+        let mut batch_fee = self
+            .get_batch_from_ticker_in_wei(token, txs.clone())
+            .await?;
+        batch_fee.options = self.batch_fee_options(txs, fee_tokens).await?;
+
+        Ok(batch_fee)
+    }
+
+    /// Returns the options to pay the batch fee in the tokens of its transactions,
+    /// sorted by the fee value in USD. Unknown tokens and the tokens which are not suitable
+    /// for paying fees are skipped.
+    async fn batch_fee_options(
+        &mut self,
+        txs: Vec<(TxFeeTypes, Address)>,
+        fee_tokens: Vec<TokenLike>,
+    ) -> anyhow::Result<Vec<BatchFeeOption>> {
+        // Group the batch members by their tokens, any of them can pay the fee for the whole batch.
+        let mut payers: Vec<(Token, Vec<usize>)> = Vec::new();
+        for (tx_index, token) in fee_tokens.into_iter().enumerate() {
+            // Unknown tokens can't be used to pay fees, so the batch members with them are skipped.
+            let token = match self.validator.resolve_token(token).await? {
+                Some(token) => token,
+                None => continue,
+            };
+            match payers.iter_mut().find(|(known, _)| known.id == token.id) {
+                Some((_, indices)) => indices.push(tx_index),
+                None => payers.push((token, vec![tx_index])),
+            }
+        }
+
+        let mut options = Vec::with_capacity(payers.len());
+        for (token, payers) in payers {
+            if !self
+                .validator
+                .token_allowed(TokenLike::Id(token.id))
+                .await?
+            {
+                continue;
+            }
+
+            let fee = self
+                .get_batch_from_ticker_in_wei(TokenLike::Id(token.id), txs.clone())
+                .await?;
+            let token_price_usd = self
+                .api
+                .get_last_quote(TokenLike::Id(token.id))
+                .await?
+                .usd_price
+                / BigUint::from(10u32).pow(u32::from(token.decimals));
+
+            options.push(BatchFeeOption {
+                token: token.id,
+                payers,
+                total_fee_usd: token_price_usd * fee.total_fee.clone(),
+                total_fee: fee.total_fee,
+            });
+        }
+        options.sort_by(|lhs, rhs| lhs.total_fee_usd.cmp(&rhs.total_fee_usd));
+
+        Ok(options)
+    }
+
     async fn wei_price_usd(&mut self) -> anyhow::Result<Ratio<BigUint>> {
         Ok(self
             .api
@@ -8,7 +8,10 @@ use futures::{channel::mpsc, executor::block_on};
 use std::str::FromStr;
 use std::thread::sleep;
 use tokio::time::Duration;
-use zksync_types::{Address, Token, TokenId, TokenPrice};
+use zksync_types::{
+    tokens::{FeeTokenEligibility, FeeTokenSettings},
+    Address, Token, TokenId, TokenPrice,
+};
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal, UnsignedRatioSerializeAsDecimal};
 
 use crate::fee_ticker::{
@@ -152,6 +155,10 @@ impl FeeTickerAPI for MockApiProvider {
         }
         unreachable!("incorrect token input")
     }
+
+    async fn reload_prices(&self) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
 }
 
 struct MockTickerInfo;
@@ -334,6 +341,102 @@ fn test_ticker_formula() {
     }
 }
 
+/// Checks that the batch fee contains the per-transaction breakdown and that the options
+/// to pay it in the tokens of the batch members are sorted by their USD value.
+#[test]
+fn test_batch_fee_options() {
+    let tokens = vec![TestToken::eth(), TestToken::cheap(), TestToken::expensive()]
+        .into_iter()
+        .map(|token| {
+            let token = Token::new(token.id, Address::default(), "", token.precision);
+            (TokenLike::Id(token.id), token)
+        })
+        .collect();
+    let fee_token_settings = vec![
+        (TokenId(0), FeeTokenEligibility::Allowed),
+        (TestToken::cheap().id, FeeTokenEligibility::Allowed),
+        (TestToken::expensive().id, FeeTokenEligibility::Denied),
+    ]
+    .into_iter()
+    .map(|(token_id, eligibility)| {
+        let settings = FeeTokenSettings {
+            eligibility: Some(eligibility),
+            ..Default::default()
+        };
+        (token_id, settings)
+    })
+    .collect();
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_fee_token_settings(fee_token_settings),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+        FakeTokenWatcher,
+    );
+
+    let config = get_test_ticker_config();
+    let mut ticker = FeeTicker::new(
+        MockApiProvider,
+        MockTickerInfo,
+        mpsc::channel(1).1,
+        config,
+        validator,
+    );
+
+    let txs = vec![
+        (TxFeeTypes::Transfer, Address::default()),
+        (TxFeeTypes::Withdraw, Address::default()),
+        (TxFeeTypes::Transfer, Address::default()),
+        (TxFeeTypes::FastWithdraw, Address::default()),
+        (TxFeeTypes::Transfer, Address::default()),
+    ];
+    let fee_tokens = vec![
+        TestToken::cheap().id.into(),
+        TestToken::expensive().id.into(),
+        TokenId(0).into(),
+        TestToken::cheap().id.into(),
+        // Unknown token.
+        TokenId(1000).into(),
+    ];
+
+    let batch_fee = block_on(ticker.get_batch_with_options_from_ticker_in_wei(
+        TokenId(0).into(),
+        txs.clone(),
+        fee_tokens,
+    ))
+    .expect("failed to get batch fee");
+
+    // Breakdown contains fees of the separate transactions.
+    assert_eq!(batch_fee.transactions.len(), txs.len());
+    for ((tx_type, address), tx_fee) in txs.iter().zip(&batch_fee.transactions) {
+        let expected_fee =
+            block_on(ticker.get_fee_from_ticker_in_wei(*tx_type, TokenId(0).into(), *address))
+                .expect("failed to get fee in token");
+        assert_eq!(tx_fee.total_fee, expected_fee.total_fee);
+    }
+
+    // Denied and unknown tokens are not suggested, the rest are sorted by their USD value.
+    let options = batch_fee
+        .options
+        .iter()
+        .map(|option| (option.token, option.payers.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        options,
+        vec![(TokenId(0), vec![2]), (TestToken::cheap().id, vec![0, 3]),]
+    );
+    assert_eq!(batch_fee.cheapest_option(), batch_fee.options.first());
+    assert!(batch_fee.options[0].total_fee_usd <= batch_fee.options[1].total_fee_usd);
+    for option in &batch_fee.options {
+        let expected_fee =
+            block_on(ticker.get_batch_from_ticker_in_wei(option.token.into(), txs.clone()))
+                .expect("failed to get batch fee");
+        assert_eq!(option.total_fee, expected_fee.total_fee);
+    }
+}
+
 #[actix_rt::test]
 #[ignore]
 // It's ignore because we can't initialize coingecko in current way with block
@@ -443,3 +546,73 @@ async fn test_error_api() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn test_fixed_price_api() {
+    use crate::fee_ticker::ticker_api::fixed::FixedPriceAPI;
+
+    let path =
+        std::env::temp_dir().join(format!("zksync_fixed_prices_{}.json", std::process::id()));
+    let write_prices = |gas_price: &str, eth_price: &str| {
+        let prices = serde_json::json!({
+            "gasPriceWei": gas_price,
+            "tokens": {
+                "ETH": { "usdPrice": eth_price, "marketVolume": "1000000" },
+                "DAI": { "usdPrice": "1" },
+            },
+        });
+        std::fs::write(&path, prices.to_string()).unwrap();
+    };
+    let eth = Token::new(TokenId(0), Address::zero(), "ETH", 18);
+    let dai = Token::new(TokenId(1), Address::repeat_byte(1), "DAI", 18);
+
+    write_prices("1000000000", "1500.5");
+    let mut api = FixedPriceAPI::new(&path).unwrap();
+
+    let eth_price = api.get_price("ETH").await.unwrap();
+    assert_eq!(
+        eth_price.usd_price,
+        big_decimal_to_ratio(&BigDecimal::from_str("1500.5").unwrap()).unwrap()
+    );
+    api.get_price("UNKNOWN").await.unwrap_err();
+    assert_eq!(
+        api.fixed_gas_price_wei().await.unwrap(),
+        Some(BigUint::from(1_000_000_000u64))
+    );
+    assert_eq!(
+        api.get_token_market_volume(&eth).await.unwrap(),
+        BigDecimal::from(1_000_000)
+    );
+    // Token without the market volume has no liquidity.
+    assert_eq!(
+        api.get_token_market_volume(&dai).await.unwrap(),
+        BigDecimal::zero()
+    );
+
+    // Changes in the file are applied only after reload.
+    write_prices("2000000000", "2000");
+    assert_eq!(
+        api.get_price("ETH").await.unwrap().usd_price,
+        eth_price.usd_price
+    );
+
+    let shared_api = api.clone();
+    shared_api.reload().await.unwrap();
+    assert_eq!(
+        api.get_price("ETH").await.unwrap().usd_price,
+        Ratio::from_integer(BigUint::from(2000u32))
+    );
+    assert_eq!(
+        api.fixed_gas_price_wei().await.unwrap(),
+        Some(BigUint::from(2_000_000_000u64))
+    );
+
+    // Invalid file is not applied.
+    std::fs::write(&path, "not a json").unwrap();
+    api.reload().await.unwrap_err();
+    assert_eq!(
+        api.fixed_gas_price_wei().await.unwrap(),
+        Some(BigUint::from(2_000_000_000u64))
+    );
+    std::fs::remove_file(&path).unwrap();
+}
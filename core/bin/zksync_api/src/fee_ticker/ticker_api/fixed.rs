@@ -0,0 +1,133 @@
+//! Price source for the networks without access to the price APIs (e.g. devnets or
+//! private deployments). Token prices, their market volumes and the gas price are
+//! read from the local JSON file:
+//!
+//! ```json
+//! {
+//!     "gasPriceWei": "1000000000",
+//!     "tokens": {
+//!         "ETH": { "usdPrice": "1500.5", "marketVolume": "1000000" },
+//!         "DAI": { "usdPrice": "1" }
+//!     }
+//! }
+//! ```
+//!
+//! Tokens are identified by their symbols. Token without the market volume is considered
+//! to have no liquidity, so it can be used to pay fees only if allowed explicitly.
+
+// Built-in deps
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+// External deps
+use anyhow::format_err;
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use num::BigUint;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+// Workspace deps
+use zksync_types::{Token, TokenPrice};
+use zksync_utils::{big_decimal_to_ratio, BigUintSerdeAsRadix10Str};
+// Local deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::validator::watcher::TokenWatcher;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixedTokenPrice {
+    pub usd_price: BigDecimal,
+    #[serde(default)]
+    pub market_volume: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixedPrices {
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_price_wei: BigUint,
+    /// Prices keyed by the token symbol.
+    pub tokens: HashMap<String, FixedTokenPrice>,
+}
+
+impl FixedPrices {
+    fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format_err!("Unable to read fixed prices file {:?}: {}", path, e))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    async fn read_async(path: &Path) -> anyhow::Result<Self> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| format_err!("Unable to read fixed prices file {:?}: {}", path, e))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+/// Price source backed by the fixed prices file. File is read once on creation
+/// and then only on `reload`, so the invalid changes in the file don't affect
+/// the running server until they are explicitly applied.
+///
+/// Clones share the loaded prices, so reloading one of them updates all of them.
+#[derive(Debug, Clone)]
+pub struct FixedPriceAPI {
+    path: PathBuf,
+    prices: Arc<RwLock<FixedPrices>>,
+}
+
+impl FixedPriceAPI {
+    pub fn new(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let prices = FixedPrices::read(&path)?;
+        Ok(Self {
+            path,
+            prices: Arc::new(RwLock::new(prices)),
+        })
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for FixedPriceAPI {
+    async fn get_price(&self, token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        let prices = self.prices.read().await;
+        let token_price = prices
+            .tokens
+            .get(token_symbol)
+            .ok_or_else(|| format_err!("Token '{}' has no fixed price", token_symbol))?;
+
+        Ok(TokenPrice {
+            usd_price: big_decimal_to_ratio(&token_price.usd_price)?,
+            last_updated: Utc::now(),
+        })
+    }
+
+    async fn fixed_gas_price_wei(&self) -> anyhow::Result<Option<BigUint>> {
+        Ok(Some(self.prices.read().await.gas_price_wei.clone()))
+    }
+
+    async fn reload(&self) -> anyhow::Result<()> {
+        let prices = FixedPrices::read_async(&self.path).await?;
+        *self.prices.write().await = prices;
+
+        vlog::info!("Fixed token prices were reloaded from {:?}", self.path);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenWatcher for FixedPriceAPI {
+    async fn get_token_market_volume(&mut self, token: &Token) -> anyhow::Result<BigDecimal> {
+        Ok(self
+            .prices
+            .read()
+            .await
+            .tokens
+            .get(&token.symbol)
+            .and_then(|price| price.market_volume.clone())
+            .unwrap_or_else(BigDecimal::zero))
+    }
+}
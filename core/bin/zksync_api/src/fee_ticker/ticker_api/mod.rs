@@ -13,6 +13,7 @@ use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
 
 pub mod coingecko;
 pub mod coinmarkercap;
+pub mod fixed;
 
 const API_PRICE_EXPIRATION_TIME_SECS: i64 = 300; // 5 mins
 const HISTORICAL_PRICE_EXPIRATION_TIME: Duration = Duration::from_secs(60);
@@ -25,6 +26,16 @@ pub const CONNECTION_TIMEOUT: Duration = Duration::from_millis(700);
 #[async_trait]
 pub trait TokenPriceAPI {
     async fn get_price(&self, token_symbol: &str) -> Result<TokenPrice, anyhow::Error>;
+
+    /// Returns the gas price if it's set by the price source rather than taken from the network.
+    async fn fixed_gas_price_wei(&self) -> Result<Option<BigUint>, anyhow::Error> {
+        Ok(None)
+    }
+
+    /// Reloads the prices if they are provided by the operator.
+    async fn reload(&self) -> Result<(), anyhow::Error> {
+        anyhow::bail!("Token price source doesn't support reloading")
+    }
 }
 
 /// Api responsible for querying for TokenPrices
@@ -37,6 +48,9 @@ pub trait FeeTickerAPI {
     async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error>;
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error>;
+
+    /// Reloads the prices provided by the operator and drops the cached ones.
+    async fn reload_prices(&self) -> Result<(), anyhow::Error>;
 }
 
 #[derive(Debug, Clone)]
@@ -196,6 +210,29 @@ impl<T: TokenPriceAPI> TickerApi<T> {
         metrics::histogram!("ticker.get_historical_ticker_price", start.elapsed());
         result
     }
+
+    async fn get_fixed_ticker_price(
+        &self,
+        token_id: TokenId,
+    ) -> Result<Option<TokenPrice>, anyhow::Error> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        let fixed_usd_price = self
+            .token_db_cache
+            .get_fee_token_settings(&mut storage, token_id)
+            .await
+            .map_err(|e| format_err!("Can't load fee token settings from storage: {}", e))?
+            .fixed_usd_price;
+
+        Ok(fixed_usd_price.map(|usd_price| TokenPrice {
+            usd_price,
+            last_updated: Utc::now(),
+        }))
+    }
 }
 
 #[async_trait]
@@ -218,6 +255,12 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
             });
         }
 
+        // Fixed price configured by the operator (e.g. for stablecoins) is used as is.
+        if let Some(fixed_price) = self.get_fixed_ticker_price(token.id).await? {
+            metrics::histogram!("ticker.get_last_quote", start.elapsed());
+            return Ok(fixed_price);
+        }
+
         if let Some(cached_value) = self.get_stored_value(token.id).await {
             metrics::histogram!("ticker.get_last_quote", start.elapsed());
             return Ok(cached_value);
@@ -253,6 +296,11 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
     /// Get current gas price in ETH
     async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error> {
         let start = Instant::now();
+        if let Some(gas_price) = self.token_price_api.fixed_gas_price_wei().await? {
+            metrics::histogram!("ticker.get_gas_price_wei", start.elapsed());
+            return Ok(gas_price);
+        }
+
         let mut cached_value = self.gas_price_cache.lock().await;
 
         if let Some((cached_gas_price, cache_time)) = cached_value.take() {
@@ -291,4 +339,12 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
         metrics::histogram!("ticker.get_token", start.elapsed());
         result
     }
+
+    async fn reload_prices(&self) -> Result<(), anyhow::Error> {
+        self.token_price_api.reload().await?;
+        // Cache is shared between the tickers, so none of them will use the outdated prices.
+        self.price_cache.lock().await.clear();
+        *self.gas_price_cache.lock().await = None;
+        Ok(())
+    }
 }
@@ -3,7 +3,10 @@ use std::{collections::HashMap, sync::Arc};
 use tokio::sync::Mutex;
 
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike};
+use zksync_types::{
+    tokens::{FeeTokenSettings, TokenMarketVolume},
+    Token, TokenId, TokenLike,
+};
 
 use crate::utils::token_db_cache::TokenDBCache;
 
@@ -23,6 +26,7 @@ pub(crate) struct TokenInDBCache {
 pub(crate) struct TokenInMemoryCache {
     tokens: Arc<Mutex<HashMap<TokenLike, Token>>>,
     market: Arc<Mutex<HashMap<TokenId, TokenMarketVolume>>>,
+    fee_token_settings: Arc<Mutex<HashMap<TokenId, FeeTokenSettings>>>,
 }
 
 impl TokenInDBCache {
@@ -50,6 +54,16 @@ impl TokenInMemoryCache {
             ..self
         }
     }
+
+    pub fn with_fee_token_settings(
+        self,
+        fee_token_settings: HashMap<TokenId, FeeTokenSettings>,
+    ) -> Self {
+        Self {
+            fee_token_settings: Arc::new(Mutex::new(fee_token_settings)),
+            ..self
+        }
+    }
 }
 
 impl From<TokenInMemoryCache> for TokenCacheWrapper {
@@ -113,6 +127,39 @@ impl TokenCacheWrapper {
             }
         }
     }
+
+    /// Returns fee settings configured for the token by the operator.
+    /// Default settings are returned if there is no override for the token.
+    pub async fn get_fee_token_settings(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<FeeTokenSettings> {
+        match self {
+            Self::DB(cache) => {
+                cache
+                    .inner
+                    .get_fee_token_settings(&mut cache.pool.access_storage().await?, token_id)
+                    .await
+            }
+            Self::Memory(cache) => Ok(cache
+                .fee_token_settings
+                .lock()
+                .await
+                .get(&token_id)
+                .cloned()
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Drops the cached fee token settings after they were changed by the operator.
+    pub async fn invalidate_fee_token_settings(&self) {
+        match self {
+            Self::DB(cache) => cache.inner.invalidate_fee_token_settings().await,
+            // Settings of the in-memory cache are the source of truth themselves.
+            Self::Memory(_) => {}
+        }
+    }
+
     pub async fn get_all_tokens(&self) -> anyhow::Result<Vec<Token>> {
         match self {
             Self::DB(cache) => {
@@ -15,8 +15,8 @@ use chrono::Utc;
 
 // Workspace uses
 use zksync_types::{
-    tokens::{Token, TokenLike, TokenMarketVolume},
-    Address,
+    tokens::{FeeTokenEligibility, FeeTokenSettings, Token, TokenLike, TokenMarketVolume},
+    Address, TokenId,
 };
 
 // Local uses
@@ -134,6 +134,12 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
     pub(crate) async fn token_allowed(&mut self, token: TokenLike) -> anyhow::Result<bool> {
         let token = self.resolve_token(token).await?;
         if let Some(token) = token {
+            // Decision made by the operator takes precedence over any other checks.
+            match self.fee_token_settings(token.id).await?.eligibility {
+                Some(FeeTokenEligibility::Allowed) => return Ok(true),
+                Some(FeeTokenEligibility::Denied) => return Ok(false),
+                None => {}
+            }
             if self.unconditionally_valid.contains(&token.address) {
                 return Ok(true);
             }
@@ -144,7 +150,21 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
         }
     }
 
-    async fn resolve_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>> {
+    /// Returns fee settings configured for the token via the admin API.
+    pub(crate) async fn fee_token_settings(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<FeeTokenSettings> {
+        self.tokens_cache.get_fee_token_settings(token_id).await
+    }
+
+    /// Drops the cached fee token settings after they were changed via the admin API.
+    pub(crate) async fn invalidate_fee_token_settings(&self) {
+        self.tokens_cache.invalidate_fee_token_settings().await
+    }
+
+    /// Returns the token by its id, symbol or address, `None` if the token is unknown.
+    pub(crate) async fn resolve_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>> {
         self.tokens_cache.get_token(token).await
     }
 
@@ -209,7 +229,6 @@ mod tests {
     use std::str::FromStr;
     use std::sync::Arc;
     use tokio::sync::Mutex;
-    use zksync_types::TokenId;
 
     #[derive(Clone)]
     struct InMemoryTokenWatcher {
@@ -343,4 +362,75 @@ mod tests {
         assert!(validator.tokens.get(&dai_token_address).unwrap().allowed);
         assert!(!validator.tokens.get(&phnx_token_address).unwrap().allowed);
     }
+
+    #[tokio::test]
+    async fn check_token_overrides() {
+        let dai_token_address =
+            Address::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap();
+        let dai_token = Token::new(TokenId(1), dai_token_address, "DAI", 18);
+        let phnx_token_address =
+            Address::from_str("38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7").unwrap();
+        let phnx_token = Token::new(TokenId(2), phnx_token_address, "PHNX", 18);
+
+        let mut market = HashMap::new();
+        for token in &[&dai_token, &phnx_token] {
+            market.insert(
+                token.id,
+                TokenMarketVolume {
+                    market_volume: Ratio::new(BigUint::from(200u32), BigUint::from(1u32)),
+                    last_updated: Utc::now(),
+                },
+            );
+        }
+
+        let mut tokens = HashMap::new();
+        tokens.insert(TokenLike::Address(dai_token_address), dai_token.clone());
+        tokens.insert(TokenLike::Address(phnx_token_address), phnx_token.clone());
+
+        // DAI is liquid enough, but denied by the operator.
+        // PHNX is liquid enough too, but we also trust the operator to allow it.
+        let mut fee_token_settings = HashMap::new();
+        fee_token_settings.insert(
+            dai_token.id,
+            FeeTokenSettings {
+                eligibility: Some(FeeTokenEligibility::Denied),
+                ..Default::default()
+            },
+        );
+        fee_token_settings.insert(
+            phnx_token.id,
+            FeeTokenSettings {
+                eligibility: Some(FeeTokenEligibility::Allowed),
+                ..Default::default()
+            },
+        );
+
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_market(market)
+            .with_fee_token_settings(fee_token_settings);
+        let watcher = InMemoryTokenWatcher {
+            amounts: Default::default(),
+        };
+        let mut validator = FeeTokenValidator::new(
+            cache,
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            HashSet::new(),
+            watcher,
+        );
+
+        let dai_allowed = validator
+            .token_allowed(TokenLike::Address(dai_token_address))
+            .await
+            .unwrap();
+        let phnx_allowed = validator
+            .token_allowed(TokenLike::Address(phnx_token_address))
+            .await
+            .unwrap();
+        assert_eq!(dai_allowed, false);
+        assert_eq!(phnx_allowed, true);
+        // Overridden tokens aren't checked by the market volume.
+        assert!(validator.tokens.is_empty());
+    }
 }
@@ -1,13 +1,18 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
 use bigdecimal::{BigDecimal, Zero};
+use num::BigUint;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
-use zksync_types::{Address, Token};
+use web3::contract::Options;
+use zksync_contracts::{uniswap_v2_factory_contract, uniswap_v2_pair_contract};
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{Address, Token, U256};
 
-use crate::fee_ticker::ticker_api::REQUEST_TIMEOUT;
+use crate::fee_ticker::ticker_api::{fixed::FixedPriceAPI, REQUEST_TIMEOUT};
 
 #[async_trait::async_trait]
 pub trait TokenWatcher {
@@ -106,3 +111,264 @@ impl TokenWatcher for UniswapTokenWatcher {
         anyhow::bail!("Token amount api is not available right now.")
     }
 }
+
+/// Watcher for Uniswap V2-style exchanges, which reads the pair reserves directly from
+/// the Ethereum node and doesn't require a subgraph.
+///
+/// Liquidity of the token is estimated as the doubled reserve of the quote token
+/// in the pair with it, so the quote token is expected to be a USD stablecoin.
+#[derive(Clone)]
+pub struct UniswapV2PairsTokenWatcher {
+    client: EthereumGateway,
+    factory_address: Address,
+    factory_contract: ethabi::Contract,
+    pair_contract: ethabi::Contract,
+    quote_token: Address,
+    quote_token_decimals: u8,
+    cache: Arc<Mutex<HashMap<Address, BigDecimal>>>,
+}
+
+impl UniswapV2PairsTokenWatcher {
+    pub fn new(
+        client: EthereumGateway,
+        factory_address: Address,
+        quote_token: Address,
+        quote_token_decimals: u8,
+    ) -> Self {
+        Self {
+            client,
+            factory_address,
+            factory_contract: uniswap_v2_factory_contract(),
+            pair_contract: uniswap_v2_pair_contract(),
+            quote_token,
+            quote_token_decimals,
+            cache: Default::default(),
+        }
+    }
+
+    async fn get_market_volume(&self, address: Address) -> anyhow::Result<BigDecimal> {
+        let start = Instant::now();
+
+        let pair_address: Address = self
+            .client
+            .call_contract_function(
+                "getPair",
+                (address, self.quote_token),
+                None,
+                Options::default(),
+                None,
+                self.factory_address,
+                self.factory_contract.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::format_err!("Failed to query getPair: {}", e))?;
+        if pair_address.is_zero() {
+            // There is no market for the token.
+            return Ok(BigDecimal::zero());
+        }
+
+        let token0: Address = self
+            .client
+            .call_contract_function(
+                "token0",
+                (),
+                None,
+                Options::default(),
+                None,
+                pair_address,
+                self.pair_contract.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::format_err!("Failed to query token0: {}", e))?;
+        let (reserve0, reserve1, _): (U256, U256, U256) = self
+            .client
+            .call_contract_function(
+                "getReserves",
+                (),
+                None,
+                Options::default(),
+                None,
+                pair_address,
+                self.pair_contract.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::format_err!("Failed to query getReserves: {}", e))?;
+
+        metrics::histogram!(
+            "ticker.uniswap_v2_pairs_watcher.get_market_volume",
+            start.elapsed()
+        );
+
+        let quote_reserve = if token0 == self.quote_token {
+            reserve0
+        } else {
+            reserve1
+        };
+        Ok(pair_liquidity_volume(
+            quote_reserve,
+            self.quote_token_decimals,
+        ))
+    }
+}
+
+/// Returns the liquidity of the pair in the quote token units,
+/// given the reserve of the quote token in this pair.
+fn pair_liquidity_volume(quote_reserve: U256, quote_token_decimals: u8) -> BigDecimal {
+    // Both sides of the pair hold assets of the same value.
+    let mut quote_reserve_bytes = [0u8; 32];
+    quote_reserve.to_big_endian(&mut quote_reserve_bytes);
+    let liquidity = BigUint::from_bytes_be(&quote_reserve_bytes) * 2u32;
+    BigDecimal::new(liquidity.into(), i64::from(quote_token_decimals))
+}
+
+#[async_trait::async_trait]
+impl TokenWatcher for UniswapV2PairsTokenWatcher {
+    async fn get_token_market_volume(&mut self, token: &Token) -> anyhow::Result<BigDecimal> {
+        match self.get_market_volume(token.address).await {
+            Ok(amount) => {
+                self.cache
+                    .lock()
+                    .await
+                    .insert(token.address, amount.clone());
+                return Ok(amount);
+            }
+            Err(err) => {
+                vlog::error!("Error in Uniswap V2 pair contracts: {:?}", err);
+            }
+        }
+
+        if let Some(amount) = self.cache.lock().await.get(&token.address).cloned() {
+            return Ok(amount);
+        };
+        anyhow::bail!("Token amount api is not available right now.")
+    }
+}
+
+/// Watcher which takes token volumes from the local JSON file, mapping token
+/// addresses to their volumes in USD. Suitable for the private deployments.
+///
+/// File is read on every request, so it can be edited without restarting the server.
+/// Tokens missing in the file are considered to have no liquidity.
+#[derive(Debug, Clone)]
+pub struct FileTokenWatcher {
+    path: PathBuf,
+}
+
+impl FileTokenWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    async fn load_volumes(&self) -> anyhow::Result<HashMap<Address, BigDecimal>> {
+        let contents = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            anyhow::format_err!("Unable to read token volumes file {:?}: {}", self.path, e)
+        })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenWatcher for FileTokenWatcher {
+    async fn get_token_market_volume(&mut self, token: &Token) -> anyhow::Result<BigDecimal> {
+        let volumes = self.load_volumes().await?;
+        Ok(volumes
+            .get(&token.address)
+            .cloned()
+            .unwrap_or_else(BigDecimal::zero))
+    }
+}
+
+/// Token watcher chosen in the ticker configuration.
+#[derive(Clone)]
+pub enum TokenWatcherWrapper {
+    UniswapSubgraph(UniswapTokenWatcher),
+    UniswapV2Pairs(UniswapV2PairsTokenWatcher),
+    File(FileTokenWatcher),
+    /// Volumes listed in the fixed prices file.
+    Fixed(FixedPriceAPI),
+}
+
+impl From<UniswapTokenWatcher> for TokenWatcherWrapper {
+    fn from(watcher: UniswapTokenWatcher) -> Self {
+        Self::UniswapSubgraph(watcher)
+    }
+}
+
+impl From<UniswapV2PairsTokenWatcher> for TokenWatcherWrapper {
+    fn from(watcher: UniswapV2PairsTokenWatcher) -> Self {
+        Self::UniswapV2Pairs(watcher)
+    }
+}
+
+impl From<FileTokenWatcher> for TokenWatcherWrapper {
+    fn from(watcher: FileTokenWatcher) -> Self {
+        Self::File(watcher)
+    }
+}
+
+impl From<FixedPriceAPI> for TokenWatcherWrapper {
+    fn from(watcher: FixedPriceAPI) -> Self {
+        Self::Fixed(watcher)
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenWatcher for TokenWatcherWrapper {
+    async fn get_token_market_volume(&mut self, token: &Token) -> anyhow::Result<BigDecimal> {
+        match self {
+            Self::UniswapSubgraph(watcher) => watcher.get_token_market_volume(token).await,
+            Self::UniswapV2Pairs(watcher) => watcher.get_token_market_volume(token).await,
+            Self::File(watcher) => watcher.get_token_market_volume(token).await,
+            Self::Fixed(watcher) => watcher.get_token_market_volume(token).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::TokenId;
+
+    #[test]
+    fn uniswap_v2_pair_liquidity() {
+        // 1500.5 USDC with 6 decimals.
+        let volume = pair_liquidity_volume(U256::from(1_500_500_000u64), 6);
+        assert_eq!(volume, BigDecimal::from(3001));
+
+        let volume = pair_liquidity_volume(U256::zero(), 18);
+        assert_eq!(volume, BigDecimal::zero());
+    }
+
+    #[tokio::test]
+    async fn file_token_watcher() {
+        let listed_token = Token::new(TokenId(1), Address::repeat_byte(1), "LST", 18);
+        let unlisted_token = Token::new(TokenId(2), Address::repeat_byte(2), "UNL", 18);
+
+        let path =
+            std::env::temp_dir().join(format!("zksync_token_volumes_{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            serde_json::json!({ format!("{:#x}", listed_token.address): "150.5" }).to_string(),
+        )
+        .unwrap();
+
+        let mut watcher = FileTokenWatcher::new(&path);
+        let listed_volume = watcher
+            .get_token_market_volume(&listed_token)
+            .await
+            .unwrap();
+        let unlisted_volume = watcher
+            .get_token_market_volume(&unlisted_token)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(listed_volume, "150.5".parse::<BigDecimal>().unwrap());
+        assert_eq!(unlisted_volume, BigDecimal::zero());
+        // Missing file is an error.
+        watcher
+            .get_token_market_volume(&listed_token)
+            .await
+            .unwrap_err();
+    }
+}
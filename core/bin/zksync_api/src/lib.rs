@@ -1,6 +1,9 @@
 #![recursion_limit = "256"]
 
-use crate::{api_server::start_api_server, fee_ticker::run_ticker_task};
+use crate::{
+    api_server::start_api_server,
+    fee_ticker::{history_sampler::run_fee_history_sampler, run_ticker_task},
+};
 use futures::channel::mpsc;
 use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
@@ -23,6 +26,12 @@ pub fn run_api(
     let (ticker_request_sender, ticker_request_receiver) = mpsc::channel(channel_size);
 
     let ticker_task = run_ticker_task(connection_pool.clone(), ticker_request_receiver, config);
+    // Fee history is not critical for the API, so the sampler task is detached.
+    run_fee_history_sampler(
+        connection_pool.clone(),
+        ticker_request_sender.clone(),
+        config,
+    );
 
     start_api_server(connection_pool, panic_notify, ticker_request_sender, config);
 
@@ -1,15 +1,25 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use tokio::sync::RwLock;
 
 use zksync_storage::StorageProcessor;
-use zksync_types::tokens::TokenMarketVolume;
+use zksync_types::tokens::{FeeTokenSettings, TokenMarketVolume};
 use zksync_types::{Token, TokenId, TokenLike};
 
+/// Fee token settings are reloaded after this time even if they weren't invalidated,
+/// so the changes made by the other server instances are eventually applied.
+const FEE_TOKEN_SETTINGS_EXPIRATION_TIME: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone, Default)]
 pub struct TokenDBCache {
     // TODO: handle stale entries, edge case when we rename token after adding it (ZKS-97)
     cache: Arc<RwLock<HashMap<TokenLike, Token>>>,
+    /// Fee settings overrides of all the tokens and the time they were loaded at.
+    fee_token_settings: Arc<RwLock<Option<(HashMap<TokenId, FeeTokenSettings>, Instant)>>>,
 }
 
 impl TokenDBCache {
@@ -109,4 +119,38 @@ impl TokenDBCache {
             .update_token_market_volume(token, market)
             .await?)
     }
+
+    /// Returns fee settings configured for the token by the operator.
+    /// Default settings are returned if there is no override for the token.
+    ///
+    /// Overrides of all the tokens are loaded at once and cached until they are invalidated
+    /// with `invalidate_fee_token_settings` or expired.
+    pub async fn get_fee_token_settings(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token: TokenId,
+    ) -> anyhow::Result<FeeTokenSettings> {
+        if let Some((fee_token_settings, loaded_at)) = &*self.fee_token_settings.read().await {
+            if loaded_at.elapsed() < FEE_TOKEN_SETTINGS_EXPIRATION_TIME {
+                return Ok(fee_token_settings.get(&token).cloned().unwrap_or_default());
+            }
+        }
+
+        let fee_token_settings: HashMap<_, _> = storage
+            .tokens_schema()
+            .load_fee_token_overrides()
+            .await?
+            .into_iter()
+            .map(|fee_token_override| (fee_token_override.token_id, fee_token_override.settings))
+            .collect();
+        let settings = fee_token_settings.get(&token).cloned().unwrap_or_default();
+        *self.fee_token_settings.write().await = Some((fee_token_settings, Instant::now()));
+
+        Ok(settings)
+    }
+
+    /// Drops the cached fee token settings, so they're loaded from the database on the next request.
+    pub async fn invalidate_fee_token_settings(&self) {
+        *self.fee_token_settings.write().await = None;
+    }
 }
@@ -19,7 +19,7 @@ fn main() {
     let (eth_req_sender, eth_req_receiver) = mpsc::channel(256);
 
     let eth_client = EthHttpClient::new(client, config.contracts.contract_addr);
-    let watcher = EthWatch::new(eth_client, 0);
+    let watcher = EthWatch::new(eth_client, None, 0, config.eth_watch.backfill_step);
 
     main_runtime.spawn(watcher.run(eth_req_receiver));
     main_runtime.block_on(async move {
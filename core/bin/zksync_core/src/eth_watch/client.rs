@@ -9,8 +9,10 @@ use web3::{
 };
 
 use zksync_contracts::zksync_contract;
-use zksync_eth_client::ethereum_gateway::EthereumGateway;
-use zksync_types::{Address, Nonce, PriorityOp, H160, U256};
+use zksync_eth_client::{
+    ethereum_gateway::EthereumGateway, ChunkedLogsRequest, LogsRequestOptions,
+};
+use zksync_types::{Address, Nonce, PriorityOp, H160, H256, U256};
 
 struct ContractTopics {
     new_priority_request: Hash,
@@ -35,6 +37,7 @@ pub trait EthClient {
         to: BlockNumber,
     ) -> anyhow::Result<Vec<PriorityOp>>;
     async fn block_number(&self) -> anyhow::Result<u64>;
+    async fn block_hash(&self, block_number: u64) -> anyhow::Result<Option<H256>>;
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>>;
     async fn get_auth_fact_reset_time(&self, address: Address, nonce: Nonce)
         -> anyhow::Result<u64>;
@@ -56,6 +59,16 @@ impl EthHttpClient {
         }
     }
 
+    async fn resolve_block_number(&self, block: BlockNumber) -> anyhow::Result<u64> {
+        match block {
+            BlockNumber::Number(number) => Ok(number.as_u64()),
+            BlockNumber::Earliest => Ok(0),
+            BlockNumber::Latest | BlockNumber::Pending => {
+                Ok(self.client.block_number().await?.as_u64())
+            }
+        }
+    }
+
     async fn get_events<T>(
         &self,
         from: BlockNumber,
@@ -66,15 +79,20 @@ impl EthHttpClient {
         T: TryFrom<Log>,
         T::Error: Debug,
     {
+        let from = self.resolve_block_number(from).await?;
+        let to = self.resolve_block_number(to).await?;
         let filter = FilterBuilder::default()
             .address(vec![self.zksync_contract_addr])
-            .from_block(from)
-            .to_block(to)
-            .topics(Some(topics), None, None, None)
-            .build();
+            .topics(Some(topics), None, None, None);
 
-        self.client
-            .logs(filter)
+        // Failed requests are not retried here, since the watcher handles the errors itself
+        // and enters the backoff mode when the rate limit is reached.
+        let options = LogsRequestOptions {
+            max_retries: 0,
+            ..Default::default()
+        };
+        ChunkedLogsRequest::new(|filter| self.client.logs(filter), filter, from, to, options)
+            .collect()
             .await?
             .into_iter()
             .filter_map(|event| {
@@ -110,6 +128,10 @@ impl EthClient for EthHttpClient {
         Ok(self.client.block_number().await?.as_u64())
     }
 
+    async fn block_hash(&self, block_number: u64) -> anyhow::Result<Option<H256>> {
+        self.client.block_hash(block_number.into()).await
+    }
+
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>> {
         self.client
             .call_main_contract_function(
@@ -1,8 +1,8 @@
 // Built-in deps
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 // External uses
 // Workspace deps
-use zksync_types::{PriorityOp, SerialId};
+use zksync_types::{PriorityOp, SerialId, H256};
 // Local deps
 use super::received_ops::ReceivedPriorityOp;
 
@@ -29,6 +29,9 @@ pub struct ETHState {
     /// Queue of priority operations that passed the confirmation
     /// threshold and are waiting to be executed.
     priority_queue: HashMap<u64, ReceivedPriorityOp>,
+    /// Hashes of the recently processed Ethereum blocks, used to detect
+    /// the chain reorganizations.
+    block_hashes: BTreeMap<u64, H256>,
 }
 
 impl ETHState {
@@ -36,11 +39,13 @@ impl ETHState {
         last_ethereum_block: u64,
         unconfirmed_queue: Vec<PriorityOp>,
         priority_queue: HashMap<SerialId, ReceivedPriorityOp>,
+        block_hashes: BTreeMap<u64, H256>,
     ) -> Self {
         Self {
             last_ethereum_block,
             unconfirmed_queue,
             priority_queue,
+            block_hashes,
         }
     }
 
@@ -55,4 +60,8 @@ impl ETHState {
     pub fn unconfirmed_queue(&self) -> &[PriorityOp] {
         &self.unconfirmed_queue
     }
+
+    pub fn block_hashes(&self) -> &BTreeMap<u64, H256> {
+        &self.block_hashes
+    }
 }
@@ -4,10 +4,17 @@
 //!
 //! Poll interval is configured using the `ETH_POLL_INTERVAL` constant.
 //! Number of confirmations is configured using the `CONFIRMATIONS_FOR_ETH_EVENT` environment variable.
+//!
+//! Hashes of the recently processed blocks are remembered, so chain reorganizations deeper than the number
+//! of confirmations are detected: priority operations from the orphaned blocks are dropped and received again
+//! from the new chain. If the reverted operations were already executed by zkSync, the server is halted.
+//!
+//! The watcher state is saved to the database, so after restart only the blocks processed since the last
+//! save are requested. Blocks are processed in steps of at most `ETH_WATCH_BACKFILL_STEP` blocks.
 
 // Built-in deps
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     time::{Duration, Instant},
 };
 
@@ -17,12 +24,13 @@ use futures::{
     SinkExt, StreamExt,
 };
 
+use thiserror::Error;
 use tokio::{task::JoinHandle, time};
-use web3::types::{Address, BlockNumber};
+use web3::types::{Address, BlockNumber, H256};
 
 // Workspace deps
 use zksync_crypto::params::PRIORITY_EXPIRATION;
-use zksync_types::{Nonce, PriorityOp, PubKeyHash, ZkSyncPriorityOp};
+use zksync_types::{Nonce, PriorityOp, PubKeyHash, SerialId, ZkSyncPriorityOp};
 
 // Local deps
 use self::{
@@ -33,8 +41,9 @@ use self::{
 
 pub use client::EthHttpClient;
 use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
 
-use zksync_eth_client::ethereum_gateway::EthereumGateway;
+use zksync_eth_client::{ethereum_gateway::EthereumGateway, is_rate_limit_error};
 
 mod client;
 mod eth_state;
@@ -47,6 +56,15 @@ mod tests;
 /// before repeating the request.
 const RATE_LIMIT_DELAY: Duration = Duration::from_secs(30);
 
+/// Amount of the latest Ethereum blocks which hashes are remembered to detect the chain reorganizations.
+const REORG_DETECTION_DEPTH: u64 = 256;
+
+/// Error returned when the chain reorganization reverted the priority operations
+/// which were already executed by zkSync. It can't be fixed automatically.
+#[derive(Debug, Error)]
+#[error("Priority operations {0:?} executed by zkSync were reverted by the Ethereum chain reorganization")]
+pub struct ExecutedOpsRevertedError(Vec<SerialId>);
+
 /// Ethereum Watcher operating mode.
 ///
 /// Normally Ethereum watcher will always poll the Ethereum node upon request,
@@ -92,25 +110,104 @@ pub enum EthWatchRequest {
 
 pub struct EthWatch<W: EthClient> {
     client: W,
+    /// Connection pool used to save the watcher state. Without it, the state is
+    /// restored from Ethereum on every start.
+    db_pool: Option<ConnectionPool>,
     eth_state: ETHState,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
+    /// Maximum amount of blocks processed at once.
+    backfill_step: u64,
     mode: WatcherMode,
+    /// Serial ID of the first priority operation not executed yet, as reported by the state keeper requests.
+    next_unprocessed_priority_op: SerialId,
 }
 
 impl<W: EthClient> EthWatch<W> {
-    pub fn new(client: W, number_of_confirmations_for_event: u64) -> Self {
+    pub fn new(
+        client: W,
+        db_pool: Option<ConnectionPool>,
+        number_of_confirmations_for_event: u64,
+        backfill_step: u64,
+    ) -> Self {
+        assert!(backfill_step > 0, "Backfill step must be positive");
+
         Self {
             client,
+            db_pool,
             eth_state: ETHState::default(),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
+            backfill_step,
+            next_unprocessed_priority_op: 0,
         }
     }
 
-    /// Atomically replaces the stored Ethereum state.
-    fn set_new_state(&mut self, new_state: ETHState) {
+    /// Atomically replaces the stored Ethereum state, saving it to the database if possible.
+    async fn set_new_state(&mut self, new_state: ETHState) -> anyhow::Result<()> {
+        if let Some(db_pool) = &self.db_pool {
+            // Only the operations that were not saved before are stored.
+            let old_priority_queue = self.eth_state.priority_queue();
+            let new_priority_ops: Vec<_> = new_state
+                .priority_queue()
+                .values()
+                .filter(|op| match old_priority_queue.get(&op.as_ref().serial_id) {
+                    Some(old_op) => {
+                        old_op.as_ref().eth_hash != op.as_ref().eth_hash
+                            || old_op.as_ref().eth_block != op.as_ref().eth_block
+                    }
+                    None => true,
+                })
+                .map(|op| (op.as_ref().clone(), op.received_at()))
+                .collect();
+            let retained_serial_ids: Vec<_> = new_state.priority_queue().keys().copied().collect();
+
+            let mut storage = db_pool.access_storage().await?;
+            storage
+                .eth_watch_schema()
+                .save_state(
+                    new_state.last_ethereum_block(),
+                    new_state.block_hashes(),
+                    new_state.unconfirmed_queue(),
+                    &new_priority_ops,
+                    &retained_serial_ids,
+                )
+                .await?;
+        }
+
         self.eth_state = new_state;
+        Ok(())
+    }
+
+    /// Loads the saved Ethereum state, if any.
+    async fn load_state(&self) -> anyhow::Result<Option<ETHState>> {
+        let db_pool = match &self.db_pool {
+            Some(db_pool) => db_pool,
+            None => return Ok(None),
+        };
+
+        let mut storage = db_pool.access_storage().await?;
+        let state = match storage.eth_watch_schema().load_state().await? {
+            Some(state) => state,
+            None => return Ok(None),
+        };
+        let priority_queue = storage
+            .eth_watch_schema()
+            .load_priority_ops()
+            .await?
+            .into_iter()
+            .map(|op| {
+                let (op, received_at) = op.into_priority_op();
+                (op.serial_id, ReceivedPriorityOp::new(op, received_at))
+            })
+            .collect();
+
+        Ok(Some(ETHState::new(
+            state.last_ethereum_block(),
+            state.unconfirmed_ops(),
+            priority_queue,
+            state.block_hashes(),
+        )))
     }
 
     async fn get_unconfirmed_ops(
@@ -122,10 +219,12 @@ impl<W: EthClient> EthWatch<W> {
         // `+ 1` is added because if we subtract number of confirmations, we'll obtain the last block
         // which has operations that must be processed. So, for the unconfirmed operations, we must
         // start from the block next to it.
+        // Blocks after the current one are not requested, since they will be processed later
+        // (this matters when the blocks are processed in several steps).
         let block_from_number =
             current_ethereum_block.saturating_sub(self.number_of_confirmations_for_event) + 1;
         let block_from = BlockNumber::Number(block_from_number.into());
-        let block_to = BlockNumber::Latest;
+        let block_to = BlockNumber::Number(current_ethereum_block.into());
 
         self.client
             .get_priority_op_events(block_from, block_to)
@@ -153,19 +252,70 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue.insert(serial_id, op);
         }
 
-        let new_state = ETHState::new(last_ethereum_block, unconfirmed_queue, priority_queue);
-        self.set_new_state(new_state);
+        let block_hashes = self
+            .update_block_hashes(self.eth_state.block_hashes().clone(), last_ethereum_block)
+            .await?;
+
+        let new_state = ETHState::new(
+            last_ethereum_block,
+            unconfirmed_queue,
+            priority_queue,
+            block_hashes,
+        );
+        self.set_new_state(new_state).await
+    }
+
+    /// Processes the blocks up to `last_ethereum_block` in steps of at most `backfill_step` blocks,
+    /// so the requested block ranges are bounded. Since the state is saved after every step,
+    /// the progress is not lost if the watcher is restarted.
+    async fn backfill(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
+        while self.eth_state.last_ethereum_block() < last_ethereum_block {
+            let next_block = std::cmp::min(
+                self.eth_state.last_ethereum_block() + self.backfill_step,
+                last_ethereum_block,
+            );
+            self.process_new_blocks(next_block).await?;
+        }
         Ok(())
     }
 
-    async fn restore_state_from_eth(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
-        let (unconfirmed_queue, priority_queue) = self
-            .update_eth_state(last_ethereum_block, PRIORITY_EXPIRATION)
-            .await?;
+    /// Restores the saved state and processes the blocks received since it was saved. If there is
+    /// no saved state or it's too old, the state is restored from Ethereum.
+    async fn restore_state(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
+        match self.load_state().await? {
+            Some(state)
+                if last_ethereum_block.saturating_sub(state.last_ethereum_block())
+                    <= PRIORITY_EXPIRATION =>
+            {
+                vlog::info!(
+                    "Restored ETH watcher state at block #{}, current block is #{}",
+                    state.last_ethereum_block(),
+                    last_ethereum_block
+                );
+                self.eth_state = state;
+
+                if let Some(fork_block) = self.find_reorg_fork_block().await? {
+                    self.rollback_to_block(fork_block).await?;
+                }
+                self.backfill(last_ethereum_block).await?;
+                vlog::debug!("ETH state: {:#?}", self.eth_state);
+                Ok(())
+            }
+            _ => self.restore_state_from_eth(last_ethereum_block).await,
+        }
+    }
 
-        let new_state = ETHState::new(last_ethereum_block, unconfirmed_queue, priority_queue);
+    async fn restore_state_from_eth(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
+        // Priority operations older than `PRIORITY_EXPIRATION` blocks are expired,
+        // so there is no need to look for them.
+        self.eth_state = ETHState::new(
+            last_ethereum_block.saturating_sub(PRIORITY_EXPIRATION),
+            Vec::new(),
+            HashMap::new(),
+            BTreeMap::new(),
+        );
+        self.backfill(last_ethereum_block).await?;
 
-        self.set_new_state(new_state);
         vlog::debug!("ETH state: {:#?}", self.eth_state);
         Ok(())
     }
@@ -195,6 +345,101 @@ impl<W: EthClient> EthWatch<W> {
         Ok((unconfirmed_queue, priority_queue))
     }
 
+    /// Adds the hash of the new processed block to the remembered ones,
+    /// forgetting the blocks older than `REORG_DETECTION_DEPTH`.
+    async fn update_block_hashes(
+        &self,
+        mut block_hashes: BTreeMap<u64, H256>,
+        last_ethereum_block: u64,
+    ) -> anyhow::Result<BTreeMap<u64, H256>> {
+        if let Some(hash) = self.client.block_hash(last_ethereum_block).await? {
+            block_hashes.insert(last_ethereum_block, hash);
+        }
+
+        let oldest_block = last_ethereum_block.saturating_sub(REORG_DETECTION_DEPTH);
+        Ok(block_hashes.split_off(&oldest_block))
+    }
+
+    /// Compares the remembered block hashes with the ones of the current chain.
+    /// Returns the number of the last block which is still in the chain if the
+    /// chain was reorganized, and `None` otherwise.
+    async fn find_reorg_fork_block(&self) -> anyhow::Result<Option<u64>> {
+        let block_hashes = self.eth_state.block_hashes();
+
+        for (idx, (&block, hash)) in block_hashes.iter().rev().enumerate() {
+            if self.client.block_hash(block).await?.as_ref() == Some(hash) {
+                // If the latest processed block is still in the chain, there was no reorg.
+                return Ok(if idx == 0 { None } else { Some(block) });
+            }
+        }
+
+        // None of the remembered blocks is in the chain anymore.
+        Ok(block_hashes.keys().next().map(|oldest_block| {
+            vlog::error!(
+                "Ethereum chain reorganization is deeper than {} blocks, \
+                 assuming the chain forked before block #{}",
+                REORG_DETECTION_DEPTH,
+                oldest_block
+            );
+            oldest_block.saturating_sub(1)
+        }))
+    }
+
+    /// Rolls back the state to the block `fork_block`, dropping the priority operations
+    /// from the orphaned blocks. They will be received again as the new blocks are processed.
+    async fn rollback_to_block(&mut self, fork_block: u64) -> anyhow::Result<()> {
+        let reorg_depth = self
+            .eth_state
+            .last_ethereum_block()
+            .saturating_sub(fork_block);
+
+        let (priority_queue, reverted_ops): (HashMap<_, _>, HashMap<_, _>) = self
+            .eth_state
+            .priority_queue()
+            .clone()
+            .into_iter()
+            .partition(|(_, op)| op.as_ref().eth_block <= fork_block);
+        let unconfirmed_queue = self
+            .eth_state
+            .unconfirmed_queue()
+            .iter()
+            .filter(|op| op.eth_block <= fork_block)
+            .cloned()
+            .collect();
+        let mut block_hashes = self.eth_state.block_hashes().clone();
+        block_hashes.split_off(&(fork_block + 1));
+
+        vlog::warn!(
+            "Ethereum chain reorganization detected: {} blocks after block #{} were reverted, \
+             {} confirmed priority operations are dropped",
+            reorg_depth,
+            fork_block,
+            reverted_ops.len()
+        );
+        metrics::counter!("eth_watcher.chain_reorg", 1);
+        metrics::gauge!("eth_watcher.chain_reorg_depth", reorg_depth as f64);
+
+        self.set_new_state(ETHState::new(
+            fork_block,
+            unconfirmed_queue,
+            priority_queue,
+            block_hashes,
+        ))
+        .await?;
+
+        let mut executed_ops: Vec<_> = reverted_ops
+            .keys()
+            .copied()
+            .filter(|&serial_id| serial_id < self.next_unprocessed_priority_op)
+            .collect();
+        if executed_ops.is_empty() {
+            Ok(())
+        } else {
+            executed_ops.sort_unstable();
+            Err(ExecutedOpsRevertedError(executed_ops).into())
+        }
+    }
+
     fn get_priority_requests(&self, first_serial_id: u64, max_chunks: usize) -> Vec<PriorityOp> {
         let mut result = Vec::new();
 
@@ -270,17 +515,20 @@ impl<W: EthClient> EthWatch<W> {
         let start = Instant::now();
         let last_block_number = self.client.block_number().await?;
 
+        if let Some(fork_block) = self.find_reorg_fork_block().await? {
+            self.rollback_to_block(fork_block).await?;
+        }
+
         if last_block_number > self.eth_state.last_ethereum_block() {
-            self.process_new_blocks(last_block_number).await?;
+            self.backfill(last_block_number).await?;
         }
 
         metrics::histogram!("eth_watcher.poll_eth_node", start.elapsed());
         Ok(())
     }
 
-    // TODO try to move it to eth client
     fn is_backoff_requested(&self, error: &anyhow::Error) -> bool {
-        error.to_string().contains("429 Too Many Requests")
+        is_rate_limit_error(error)
     }
 
     fn enter_backoff_mode(&mut self) {
@@ -334,7 +582,7 @@ impl<W: EthClient> EthWatch<W> {
         // can interact with the node again. We're not expecting the rate limiting to be applied
         // immediately after that, thus any error on this stage is considered critical and
         // irrecoverable.
-        self.restore_state_from_eth(block)
+        self.restore_state(block)
             .await
             .expect("Unable to restore ETHWatcher state");
 
@@ -349,6 +597,13 @@ impl<W: EthClient> EthWatch<W> {
                     let poll_result = self.poll_eth_node().await;
 
                     if let Err(error) = poll_result {
+                        if let Some(error) = error.downcast_ref::<ExecutedOpsRevertedError>() {
+                            // The state of zkSync doesn't match the Ethereum anymore, so we must stop
+                            // the server before the state keeper produces any more blocks.
+                            vlog::error!("{}", error);
+                            panic!("{}. Manual intervention is required", error);
+                        }
+
                         if self.is_backoff_requested(&error) {
                             vlog::warn!(
                                 "Rate limit was reached, as reported by Ethereum node. \
@@ -367,6 +622,8 @@ impl<W: EthClient> EthWatch<W> {
                     max_chunks,
                     resp,
                 } => {
+                    self.next_unprocessed_priority_op =
+                        self.next_unprocessed_priority_op.max(op_start_id);
                     resp.send(self.get_priority_requests(op_start_id, max_chunks))
                         .unwrap_or_default();
                 }
@@ -404,13 +661,16 @@ pub fn start_eth_watch(
     config_options: &ZkSyncConfig,
     eth_req_sender: mpsc::Sender<EthWatchRequest>,
     eth_req_receiver: mpsc::Receiver<EthWatchRequest>,
+    db_pool: ConnectionPool,
 ) -> JoinHandle<()> {
     let client = EthereumGateway::from_config(&config_options);
     let eth_client = EthHttpClient::new(client, config_options.contracts.contract_addr);
 
     let eth_watch = EthWatch::new(
         eth_client,
+        Some(db_pool),
         config_options.eth_watch.confirmations_for_eth_event,
+        config_options.eth_watch.backfill_step,
     );
 
     tokio::spawn(eth_watch.run(eth_req_receiver));
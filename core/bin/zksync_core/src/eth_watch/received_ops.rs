@@ -1,8 +1,7 @@
 // Built-in deps
-use std::{
-    collections::HashMap,
-    time::{Duration, Instant},
-};
+use std::{collections::HashMap, time::Duration};
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace deps
 use zksync_types::PriorityOp;
 
@@ -25,21 +24,33 @@ pub const PRIORITY_OP_EXPIRATION: Duration =
 #[derive(Debug, Clone)]
 pub struct ReceivedPriorityOp {
     op: PriorityOp,
-    received_at: Instant,
+    received_at: DateTime<Utc>,
 }
 
 impl ReceivedPriorityOp {
+    /// Creates the operation received at the given moment, e.g. restored from the database.
+    pub fn new(op: PriorityOp, received_at: DateTime<Utc>) -> Self {
+        Self { op, received_at }
+    }
+
+    pub fn received_at(&self) -> DateTime<Utc> {
+        self.received_at
+    }
+
     pub fn is_outdated(&self) -> bool {
-        self.received_at.elapsed() >= PRIORITY_OP_EXPIRATION
+        // Negative age (e.g. because of the clock adjustment) can't be converted and means that the
+        // operation is not outdated.
+        Utc::now()
+            .signed_duration_since(self.received_at)
+            .to_std()
+            .map(|age| age >= PRIORITY_OP_EXPIRATION)
+            .unwrap_or(false)
     }
 }
 
 impl From<PriorityOp> for ReceivedPriorityOp {
     fn from(op: PriorityOp) -> Self {
-        Self {
-            op,
-            received_at: Instant::now(),
-        }
+        Self::new(op, Utc::now())
     }
 }
 
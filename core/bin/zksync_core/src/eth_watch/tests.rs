@@ -1,17 +1,21 @@
 use std::cmp::max;
 use std::collections::HashMap;
 
-use web3::types::{Address, BlockNumber};
+use web3::types::{Address, BlockNumber, H256};
 
 use zksync_types::{AccountId, Deposit, FullExit, Nonce, PriorityOp, TokenId, ZkSyncPriorityOp};
 
-use crate::eth_watch::{client::EthClient, EthWatch};
+use crate::eth_watch::{client::EthClient, EthWatch, ExecutedOpsRevertedError};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
     last_block_number: u64,
+    /// Blocks after which the chain was reorganized.
+    fork_blocks: Vec<u64>,
+    /// The largest block range requested by the watcher.
+    max_requested_range: u64,
 }
 
 impl FakeEthClientData {
@@ -19,9 +23,32 @@ impl FakeEthClientData {
         Self {
             priority_ops: Default::default(),
             last_block_number: 0,
+            fork_blocks: Vec::new(),
+            max_requested_range: 0,
         }
     }
 
+    /// Hash of the block depends on the amount of reorganizations it went through.
+    fn block_hash(&self, number: u64) -> Option<H256> {
+        if number > self.last_block_number {
+            return None;
+        }
+        let forks = self
+            .fork_blocks
+            .iter()
+            .filter(|&&fork| fork < number)
+            .count() as u64;
+        Some(H256::from_low_u64_be((forks << 32) + number))
+    }
+
+    /// Replaces all the blocks after `fork_block` with the new ones.
+    fn reorg(&mut self, fork_block: u64, last_block_number: u64, ops: &[PriorityOp]) {
+        self.priority_ops.retain(|&block, _| block <= fork_block);
+        self.fork_blocks.push(fork_block);
+        self.last_block_number = last_block_number;
+        self.add_operations(ops);
+    }
+
     fn add_operations(&mut self, ops: &[PriorityOp]) {
         for op in ops {
             self.last_block_number = max(op.eth_block, self.last_block_number);
@@ -49,6 +76,17 @@ impl FakeEthClient {
         self.inner.write().await.add_operations(ops);
     }
 
+    async fn set_last_block_number(&mut self, number: u64) {
+        self.inner.write().await.last_block_number = number;
+    }
+
+    async fn reorg(&mut self, fork_block: u64, last_block_number: u64, ops: &[PriorityOp]) {
+        self.inner
+            .write()
+            .await
+            .reorg(fork_block, last_block_number, ops);
+    }
+
     async fn block_to_number(&self, block: &BlockNumber) -> u64 {
         match block {
             BlockNumber::Latest => self.inner.read().await.last_block_number,
@@ -68,6 +106,11 @@ impl EthClient for FakeEthClient {
     ) -> Result<Vec<PriorityOp>, anyhow::Error> {
         let from = self.block_to_number(&from).await;
         let to = self.block_to_number(&to).await;
+
+        let mut inner = self.inner.write().await;
+        inner.max_requested_range = max(inner.max_requested_range, to.saturating_sub(from) + 1);
+        drop(inner);
+
         let mut operations = vec![];
         for number in from..=to {
             if let Some(ops) = self.inner.read().await.priority_ops.get(&number) {
@@ -81,6 +124,10 @@ impl EthClient for FakeEthClient {
         Ok(self.inner.read().await.last_block_number)
     }
 
+    async fn block_hash(&self, block_number: u64) -> Result<Option<H256>, anyhow::Error> {
+        Ok(self.inner.read().await.block_hash(block_number))
+    }
+
     async fn get_auth_fact(
         &self,
         _address: Address,
@@ -99,7 +146,7 @@ impl EthClient for FakeEthClient {
 }
 
 fn create_watcher<T: EthClient>(client: T) -> EthWatch<T> {
-    EthWatch::new(client, 1)
+    EthWatch::new(client, None, 1, 10)
 }
 
 #[tokio::test]
@@ -364,3 +411,108 @@ async fn test_restore_and_poll_time_lag() {
     priority_queues.get(&0).unwrap();
     priority_queues.get(&1).unwrap();
 }
+
+/// Checks that the blocks are requested in ranges bounded by the backfill step.
+#[tokio::test]
+async fn test_restore_backfill_step() {
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[dummy_deposit(0, 1, 1), dummy_deposit(1, 95, 2)])
+        .await;
+    client.set_last_block_number(100).await;
+
+    let mut watcher = create_watcher(client.clone());
+    watcher.restore_state_from_eth(100).await.unwrap();
+
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 100);
+    assert_eq!(watcher.eth_state.priority_queue().len(), 2);
+    // Backfill step is 10 blocks, and the ranges of confirmed events overlap by one block.
+    assert_eq!(client.inner.read().await.max_requested_range, 11);
+}
+
+fn dummy_deposit(serial_id: u64, eth_block: u64, eth_hash: u8) -> PriorityOp {
+    PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: Default::default(),
+            token: TokenId(0),
+            amount: Default::default(),
+            to: Default::default(),
+        }),
+        deadline_block: 0,
+        eth_hash: [eth_hash; 32].into(),
+        eth_block,
+    }
+}
+
+/// Creates a watcher which has processed blocks 2, 4 and 6 with priority operations
+/// in blocks 2 and 4, and then reorganizes the chain after block 3.
+async fn reorganized_watcher() -> EthWatch<FakeEthClient> {
+    let mut client = FakeEthClient::new();
+    let mut watcher = create_watcher(client.clone());
+
+    client.add_operations(&[dummy_deposit(0, 2, 1)]).await;
+    watcher.poll_eth_node().await.unwrap();
+    client.add_operations(&[dummy_deposit(1, 4, 2)]).await;
+    watcher.poll_eth_node().await.unwrap();
+    client.set_last_block_number(6).await;
+    watcher.poll_eth_node().await.unwrap();
+
+    assert_eq!(watcher.eth_state.priority_queue().len(), 2);
+    assert_eq!(
+        watcher
+            .eth_state
+            .block_hashes()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![2, 4, 6]
+    );
+
+    // Operation with serial ID 1 is moved to the other block by the reorganization.
+    client.reorg(3, 7, &[dummy_deposit(1, 5, 3)]).await;
+    watcher
+}
+
+/// Checks that the priority operations from the orphaned blocks are replaced
+/// with the ones from the new chain.
+#[tokio::test]
+async fn test_chain_reorg() {
+    let mut watcher = reorganized_watcher().await;
+    watcher.poll_eth_node().await.unwrap();
+
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 7);
+    assert_eq!(
+        watcher
+            .eth_state
+            .block_hashes()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![2, 7]
+    );
+
+    let priority_queue = watcher.eth_state.priority_queue();
+    assert_eq!(priority_queue.len(), 2);
+    assert_eq!(priority_queue[&0].as_ref().eth_block, 2);
+    assert_eq!(priority_queue[&1].as_ref().eth_block, 5);
+    assert_eq!(priority_queue[&1].as_ref().eth_hash, H256::from([3; 32]));
+}
+
+/// Checks that the reorganization reverting already executed priority operations is reported.
+#[tokio::test]
+async fn test_chain_reorg_reverts_executed_ops() {
+    let mut watcher = reorganized_watcher().await;
+    // State keeper has already executed both operations.
+    watcher.next_unprocessed_priority_op = 2;
+
+    let error = watcher.poll_eth_node().await.unwrap_err();
+    let error = error
+        .downcast_ref::<ExecutedOpsRevertedError>()
+        .expect("Unexpected error type");
+    assert_eq!(error.0, vec![1]);
+
+    // Operations from the orphaned blocks are dropped anyway.
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 2);
+    assert_eq!(watcher.eth_state.priority_queue().len(), 1);
+}
@@ -0,0 +1,63 @@
+//! Forecasting of the operator accounts balances.
+//!
+//! The spending rate of the operator account is estimated from the costs of the transactions
+//! confirmed within the recent period, assuming that the load stays the same.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use zksync_basic_types::U256;
+
+/// Amount of wei in 1 ETH.
+const WEI_IN_ETH: f64 = 1e18;
+
+/// Estimates how long the `balance` lasts if `spent` wei are spent every `window`.
+/// Returns `None` if nothing was spent, i.e. the balance is not used at all.
+pub fn balance_lasts_for(balance: U256, spent: U256, window: Duration) -> Option<Duration> {
+    if spent.is_zero() {
+        return None;
+    }
+
+    let secs = balance.saturating_mul(window.as_secs().into()) / spent;
+    Some(Duration::from_secs(
+        secs.min(U256::from(u64::max_value())).as_u64(),
+    ))
+}
+
+/// Converts the amount of wei into ETH, used for the metrics reporting only.
+pub fn wei_to_eth(amount: U256) -> f64 {
+    amount.low_u128() as f64 / WEI_IN_ETH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_forecast() {
+        const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+        // Nothing is spent.
+        assert_eq!(balance_lasts_for(100.into(), 0.into(), DAY), None);
+        // Balance is enough for 2.5 days.
+        assert_eq!(
+            balance_lasts_for(250.into(), 100.into(), DAY),
+            Some(DAY * 5 / 2)
+        );
+        // Balance is empty.
+        assert_eq!(
+            balance_lasts_for(0.into(), 100.into(), DAY),
+            Some(Duration::from_secs(0))
+        );
+        // Forecast is capped instead of overflowing.
+        assert_eq!(
+            balance_lasts_for(U256::max_value(), 1.into(), DAY),
+            Some(Duration::from_secs(u64::max_value()))
+        );
+    }
+
+    #[test]
+    fn wei_conversion() {
+        assert_eq!(wei_to_eth(U256::exp10(18) * 3 / 2), 1.5);
+    }
+}
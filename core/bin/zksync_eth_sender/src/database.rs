@@ -2,13 +2,14 @@
 use std::collections::VecDeque;
 use std::str::FromStr;
 // External uses
+use chrono::{DateTime, Utc};
 use num::BigUint;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace uses
 use zksync_storage::{ConnectionPool, StorageProcessor};
-use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
+use zksync_types::ethereum::{ETHOperation, ETHSenderState, EthOpId, InsertedOperationResponse};
 // Local uses
-use super::transactions::ETHStats;
+use super::transactions::{ETHStats, ETHTxCost};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 
 /// Abstract database access trait, optimized for the needs of `ETHSender`.
@@ -47,6 +48,9 @@ pub(super) trait DatabaseInterface {
     ) -> anyhow::Result<()>;
 
     /// Saves a new unconfirmed operation to the database.
+    /// Priority fee is expected to be provided only for EIP-1559 transactions.
+    /// Sender is expected to be provided only for the additional operator accounts.
+    #[allow(clippy::too_many_arguments)]
     async fn save_new_eth_tx(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -54,9 +58,19 @@ pub(super) trait DatabaseInterface {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
+        sender: Option<Address>,
     ) -> anyhow::Result<InsertedOperationResponse>;
 
+    /// Stores the initial nonce of the additional operator account, if it's not stored yet.
+    async fn initialize_operator_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: U256,
+    ) -> anyhow::Result<()>;
+
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
@@ -72,16 +86,28 @@ pub(super) trait DatabaseInterface {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()>;
 
-    /// Marks an operation as completed in the database.
+    /// Marks an operation as completed in the database and stores the cost of the
+    /// confirmed transaction, if known.
     async fn confirm_operation(
         &self,
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        cost: Option<ETHTxCost>,
     ) -> anyhow::Result<()>;
 
+    /// Loads the total fee paid for the transactions sent from the operator account
+    /// (`None` for the main one) and confirmed since the given moment.
+    async fn load_spent_fee(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        sender: Option<Address>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<U256>;
+
     /// Loads the stored Ethereum operations stats.
     async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats>;
 
@@ -99,11 +125,27 @@ pub(super) trait DatabaseInterface {
         average_gas_price: U256,
     ) -> anyhow::Result<()>;
 
+    /// Checks whether the previous operation sent from the same operator account is confirmed.
     async fn is_previous_operation_confirmed(
         &self,
         connection: &mut StorageProcessor<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool>;
+
+    /// Loads the state controlled via the admin API: whether sending is paused,
+    /// and the admin actions waiting to be performed.
+    async fn load_sender_state(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<ETHSenderState>;
+
+    /// Marks the admin action as performed and records its result.
+    async fn complete_admin_action(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        id: i64,
+        result: &str,
+    ) -> anyhow::Result<()>;
 }
 
 /// The actual database wrapper.
@@ -183,7 +225,9 @@ impl DatabaseInterface for Database {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
+        sender: Option<Address>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let result = connection
             .ethereum_schema()
@@ -192,13 +236,29 @@ impl DatabaseInterface for Database {
                 op,
                 deadline_block,
                 BigUint::from_str(&used_gas_price.to_string()).unwrap(),
+                used_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
                 raw_tx,
+                sender,
             )
             .await?;
 
         Ok(result)
     }
 
+    async fn initialize_operator_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: U256,
+    ) -> anyhow::Result<()> {
+        connection
+            .ethereum_schema()
+            .initialize_operator_nonce(address, nonce.as_u64() as i64)
+            .await?;
+
+        Ok(())
+    }
+
     async fn add_hash_entry(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -217,6 +277,7 @@ impl DatabaseInterface for Database {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         Ok(connection
             .ethereum_schema()
@@ -224,6 +285,7 @@ impl DatabaseInterface for Database {
                 eth_op_id,
                 new_deadline_block,
                 BigUint::from_str(&new_gas_value.to_string()).unwrap(),
+                new_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
             )
             .await?)
     }
@@ -233,28 +295,45 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool> {
-        // If the ID of the current operation is 1, then this is the first transaction
-        // and it is not needed for checking confirmation.
-        if op.id == 1 {
-            return Ok(true);
-        }
-
-        // Since the operations are sent to the Ethereum one by one,
-        // we simply consider the operation with ID less by one.
-        let previous_op = op.id - 1;
+        // Since the operations are sent to the Ethereum one by one within each
+        // operator account, we consider the previous operation sent from the same account.
+        // Operations sent from different accounts are ordered by `TxQueue`, which doesn't
+        // send an operation until the operation it depends on is confirmed.
         let confirmed = connection
             .ethereum_schema()
-            .is_aggregated_op_confirmed(previous_op)
+            .is_previous_sender_op_confirmed(op.id, op.sender)
             .await?;
 
         Ok(confirmed)
     }
 
+    async fn load_sender_state(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<ETHSenderState> {
+        let state = connection.ethereum_schema().load_sender_state().await?;
+        Ok(state)
+    }
+
+    async fn complete_admin_action(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        id: i64,
+        result: &str,
+    ) -> anyhow::Result<()> {
+        connection
+            .ethereum_schema()
+            .complete_sender_admin_action(id, result)
+            .await?;
+        Ok(())
+    }
+
     async fn confirm_operation(
         &self,
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        cost: Option<ETHTxCost>,
     ) -> anyhow::Result<()> {
         let mut transaction = connection.start_transaction().await?;
 
@@ -307,11 +386,34 @@ impl DatabaseInterface for Database {
         }
 
         transaction.ethereum_schema().confirm_eth_tx(hash).await?;
+        if let Some(cost) = cost {
+            transaction
+                .ethereum_schema()
+                .save_eth_tx_cost(
+                    hash,
+                    BigUint::from_str(&cost.gas_used.to_string()).unwrap(),
+                    BigUint::from_str(&cost.effective_gas_price.to_string()).unwrap(),
+                )
+                .await?;
+        }
         transaction.commit().await?;
 
         Ok(())
     }
 
+    async fn load_spent_fee(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        sender: Option<Address>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<U256> {
+        let fee = connection
+            .ethereum_schema()
+            .load_spent_fee(sender, since)
+            .await?;
+        Ok(fee)
+    }
+
     async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats> {
         let stats = connection.ethereum_schema().load_stats().await?;
         Ok(stats.into())
@@ -2,7 +2,7 @@
 use std::{collections::VecDeque, marker::PhantomData, time::Instant};
 // External deps
 use zksync_basic_types::U256;
-use zksync_eth_client::EthereumGateway;
+use zksync_eth_client::{Eip1559Fees, EthereumGateway};
 // Local deps
 use crate::database::DatabaseInterface;
 
@@ -93,6 +93,54 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         Ok(price)
     }
 
+    /// Calculates the fees for the EIP-1559 transaction.
+    ///
+    /// New transactions offer the configured priority fee and the max fee of twice the current
+    /// base fee plus the tip, so the transaction remains includable while the base fee grows.
+    /// For the stuck transactions both fees are increased by 15% (as required for the replacement),
+    /// but not lower than the values suggested for the new transaction.
+    ///
+    /// Max fee is cut by the same limit as the legacy gas price.
+    pub async fn get_eip1559_fees(
+        &mut self,
+        ethereum: &EthereumGateway,
+        old_tx_fees: Option<Eip1559Fees>,
+    ) -> anyhow::Result<Eip1559Fees> {
+        let base_fee = ethereum.get_base_fee_per_gas().await?;
+        let priority_fee = parameters::max_priority_fee_per_gas();
+        let suggested_max_fee = base_fee * U256::from(2) + priority_fee;
+
+        let (max_fee, priority_fee) = if let Some(old_fees) = old_tx_fees {
+            // Stuck transaction, scale it up.
+            (
+                self.scale_up(old_fees.max_fee_per_gas, suggested_max_fee),
+                self.scale_up(old_fees.max_priority_fee_per_gas, priority_fee),
+            )
+        } else {
+            (suggested_max_fee, priority_fee)
+        };
+
+        // Now, cut the max fee if it's too big. Priority fee can't exceed the max fee.
+        let max_fee = self.limit_max(max_fee);
+        let priority_fee = std::cmp::min(priority_fee, max_fee);
+
+        if max_fee == self.get_current_max_price() {
+            vlog::warn!(
+                "Maximum possible max fee per gas will be used: <{}>",
+                max_fee
+            );
+        }
+
+        // Report the price that will be actually paid to be gathered by the statistics module.
+        self.statistics
+            .add_sample(std::cmp::min(base_fee + priority_fee, max_fee));
+
+        Ok(Eip1559Fees {
+            max_fee_per_gas: max_fee,
+            max_priority_fee_per_gas: priority_fee,
+        })
+    }
+
     /// Performs an actualization routine for `GasAdjuster`:
     /// This method is intended to be invoked periodically, and it updates the
     /// current max gas price limit according to the configurable update interval.
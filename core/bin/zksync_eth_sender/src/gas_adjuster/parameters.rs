@@ -5,6 +5,7 @@
 //!   gas price suggested by `GasAdjuster`.
 //! - Maximum gas price scale: multiplier to be applied to the average gas price to
 //!   calculate the upper limit for gas price in `GasAdjuster`.
+//! - Max priority fee per gas: tip offered to the block producer in EIP-1559 transactions.
 //!
 //! The module uses a child module `parameters_impl` which contains two implementations
 //! for functions declared in module: one for the actual usage, and one for tests.
@@ -13,6 +14,8 @@
 
 // Built-in deps.
 use std::time::Duration;
+// External deps
+use zksync_basic_types::U256;
 
 /// Obtains the interval for renewing the maximum gas price.
 ///
@@ -41,11 +44,22 @@ pub fn sample_adding_interval() -> Duration {
     parameters_impl::sample_adding_interval()
 }
 
+/// Obtains the priority fee per gas to be used in EIP-1559 transactions.
+///
+/// This value is not cached internally, as it may be changed for the already running
+/// server by an administrator. This may be required if existing settings aren't flexible
+/// enough to match the current network price.
+pub fn max_priority_fee_per_gas() -> U256 {
+    parameters_impl::max_priority_fee_per_gas()
+}
+
 // Actual methods implementation for non-test purposes.
 #[cfg(not(test))]
 mod parameters_impl {
     // Built-in deps.
     use std::time::Duration;
+    // External deps
+    use zksync_basic_types::U256;
     // Workspace deps
     use zksync_config::configs::eth_sender::ETHSenderConfig;
 
@@ -78,6 +92,16 @@ mod parameters_impl {
         let config = ETHSenderConfig::from_env();
         config.gas_price_limit.sample_interval()
     }
+
+    /// Obtains the priority fee per gas to be used in EIP-1559 transactions.
+    ///
+    /// This value is not cached internally, as it may be changed for the already running
+    /// server by an administrator. This may be required if existing settings aren't flexible
+    /// enough to match the current network price.
+    pub fn max_priority_fee_per_gas() -> U256 {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.max_priority_fee_per_gas.into()
+    }
 }
 
 // Hard-coded implementation for tests.
@@ -85,6 +109,8 @@ mod parameters_impl {
 mod parameters_impl {
     // Built-in deps.
     use std::time::Duration;
+    // External deps
+    use zksync_basic_types::U256;
 
     /// `limit_update_interval` version for tests not looking for an environment variable value
     /// but using a zero interval instead.
@@ -103,4 +129,10 @@ mod parameters_impl {
     pub fn sample_adding_interval() -> Duration {
         Duration::from_secs(0)
     }
+
+    /// `max_priority_fee_per_gas` version for tests not looking for an environment variable value
+    /// but using a fixed priority fee (10 wei) instead.
+    pub fn max_priority_fee_per_gas() -> U256 {
+        U256::from(10)
+    }
 }
@@ -8,7 +8,7 @@ use crate::{
     DatabaseInterface, GasAdjuster,
 };
 
-use zksync_eth_client::ethereum_gateway::EthereumGateway;
+use zksync_eth_client::{ethereum_gateway::EthereumGateway, Eip1559Fees};
 
 /// Creates `Ethereum` and `Database` instances for the `GasAdjuster` tests.
 async fn eth_and_db_clients() -> (EthereumGateway, MockDatabase) {
@@ -81,6 +81,76 @@ async fn lower_gas_limit() {
     }
 }
 
+/// Checks the EIP-1559 fees: for new transactions the max fee is twice the base fee plus
+/// the priority fee, and for stuck transactions both fees are increased by 15% or set to
+/// the values suggested for a new transaction, whichever is greater.
+#[tokio::test]
+async fn eip1559_fees() {
+    // Priority fee returned by `parameters::max_priority_fee_per_gas` in tests.
+    const PRIORITY_FEE: u64 = 10;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    let fees = |max_fee: u64, priority_fee: u64| Eip1559Fees {
+        max_fee_per_gas: max_fee.into(),
+        max_priority_fee_per_gas: priority_fee.into(),
+    };
+
+    // Test vector of (base fee, fees of the last tx, expected fees).
+    let test_vector = vec![
+        // New transaction.
+        (80, None, fees(170, PRIORITY_FEE)),
+        (0, None, fees(PRIORITY_FEE, PRIORITY_FEE)),
+        // Base fee is low, increase the old fees by 15%.
+        (10, Some(fees(200, 20)), fees(230, 23)),
+        // Base fee has grown, use the new suggested max fee.
+        (150, Some(fees(200, 20)), fees(310, 23)),
+        // Old priority fee is lower than the configured one.
+        (10, Some(fees(200, 5)), fees(230, PRIORITY_FEE)),
+    ];
+
+    for (base_fee, old_fees, expected_fees) in test_vector {
+        ethereum.get_mut_mock().unwrap().base_fee_per_gas = base_fee.into();
+
+        let actual_fees = gas_adjuster
+            .get_eip1559_fees(&ethereum, old_fees)
+            .await
+            .unwrap();
+        assert_eq!(actual_fees, expected_fees);
+    }
+}
+
+/// Checks that the max fee of EIP-1559 transaction is clamped according to the current limit,
+/// and the priority fee doesn't exceed the max fee.
+#[tokio::test]
+async fn eip1559_fees_upper_limit() {
+    // Initial price limit to set.
+    const PRICE_LIMIT: i64 = 1000;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    db.update_gas_price_limit(PRICE_LIMIT).await.unwrap();
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    ethereum.get_mut_mock().unwrap().base_fee_per_gas = U256::from(PRICE_LIMIT);
+    let fees = gas_adjuster
+        .get_eip1559_fees(&ethereum, None)
+        .await
+        .unwrap();
+    assert_eq!(fees.max_fee_per_gas, PRICE_LIMIT.into());
+
+    let old_fees = Eip1559Fees {
+        max_fee_per_gas: PRICE_LIMIT.into(),
+        max_priority_fee_per_gas: PRICE_LIMIT.into(),
+    };
+    let fees = gas_adjuster
+        .get_eip1559_fees(&ethereum, Some(old_fees))
+        .await
+        .unwrap();
+    assert_eq!(fees.max_fee_per_gas, PRICE_LIMIT.into());
+    assert_eq!(fees.max_priority_fee_per_gas, PRICE_LIMIT.into());
+}
+
 // Checks that after re-creation the price limit is restored from the database.
 #[tokio::test]
 async fn gas_price_limit_restore() {
@@ -4,22 +4,29 @@
 //! every transaction is executed successfully and confirmed.
 
 // Built-in deps
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::time::{Duration, Instant};
 // External uses
 use anyhow::format_err;
+use chrono::Utc;
 use tokio::{task::JoinHandle, time};
 use web3::{
     contract::Options,
-    types::{TransactionReceipt, H256, U256},
+    types::{BlockNumber, TransactionReceipt, H256, U256},
 };
 // Workspace uses
 use zksync_config::{ETHSenderConfig, ZkSyncConfig};
-use zksync_eth_client::{EthereumGateway, SignedCallResult};
+use zksync_eth_client::{
+    ethereum_gateway::FailureInfo, CallOutcome, Eip1559Fees, EthereumGateway, SignedCallResult,
+};
 use zksync_storage::ConnectionPool;
-use zksync_types::ethereum::ETHOperation;
+use zksync_types::{
+    ethereum::{ETHOperation, ETHSenderAdminActionType, EthOpId},
+    Address,
+};
 // Local uses
 use self::{
+    balance_forecast::{balance_lasts_for, wei_to_eth},
     database::{Database, DatabaseInterface},
     gas_adjuster::GasAdjuster,
     transactions::*,
@@ -30,6 +37,7 @@ use zksync_types::{
     gas_counter::GasCounter,
 };
 
+mod balance_forecast;
 mod database;
 mod gas_adjuster;
 mod transactions;
@@ -102,6 +110,39 @@ enum TxCheckMode {
 /// 2. Withdraw operations (only if both commit/verify for the same block operations were sent).
 /// 3. Commit operations.
 ///
+/// # Operator nonce lanes
+///
+/// Besides the main operator account, `ETHSender` can use additional operator accounts, each
+/// one having its own nonce sequence ("lane"). Proof and execute transactions can be assigned
+/// to the separate lanes, so a stuck transaction in one lane doesn't block the others.
+/// Commit transactions are always sent from the main operator account. The order between
+/// operations sent using different lanes is preserved by the `TxQueue`.
+///
+/// # Operator balance forecast
+///
+/// `ETHSender` records the costs of the confirmed transactions and periodically estimates how long
+/// the balances of the operator accounts last at the recent spending rate. An alert is raised
+/// if any balance is expected to run out sooner than the configured threshold.
+///
+/// # Admin controls
+///
+/// Sending can be paused and resumed at runtime via the admin API. While paused, `ETHSender`
+/// doesn't send any transactions (including the replacements of the stuck ones), but keeps
+/// tracking the confirmations of the already sent ones. Admin can also request to replace the
+/// pending transaction of the operation right away (optionally with the given gas price) or
+/// to broadcast the last transaction once again. Requested actions and their results are
+/// recorded in the database.
+///
+/// # Transaction simulation
+///
+/// Before the transaction of the new operation is signed, it's simulated with `eth_call` against
+/// the pending block. If it's expected to revert (e.g. because of the wrong previous block hash or
+/// an invalid proof), the operation is held in the `TxQueue` instead of burning gas on the failing
+/// transactions, and the next operations of the same type wait for it. Held operations are simulated
+/// again on every iteration and sent once they are expected to succeed. Since the revert may be
+/// caused by the preceding transactions not being in the pending block yet, an alert is raised
+/// only if the operation is still expected to revert when there are no unconfirmed transactions.
+///
 /// # Failure policy
 ///
 /// By default, `ETHSender` expects no transactions to fail, and thus upon a failure it will
@@ -115,16 +156,47 @@ struct ETHSender<DB: DatabaseInterface> {
     db: DB,
     /// Ethereum intermediator.
     ethereum: EthereumGateway,
+    /// Additional operator accounts, lane `N` corresponds to the element `N - 1`.
+    lanes: Vec<OperatorLane>,
     /// Queue for ordered transaction processing.
     tx_queue: TxQueue,
     /// Utility for managing the gas price for transactions.
     gas_adjuster: GasAdjuster<DB>,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
+    /// Moment of the last operator balances check.
+    last_balance_check: Option<Instant>,
+    /// Whether sending is paused via the admin API.
+    paused: bool,
+    /// IDs of the held zkSync operations for which the alert is already raised.
+    alerted_held_ops: HashSet<i64>,
+}
+
+/// Additional operator account with its own nonce lane.
+#[derive(Debug, Clone)]
+struct OperatorLane {
+    /// Address of the operator account.
+    address: Address,
+    /// Ethereum intermediator signing the transactions on behalf of the account.
+    ethereum: EthereumGateway,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
-    pub async fn new(options: ETHSenderConfig, db: DB, ethereum: EthereumGateway) -> Self {
+    pub async fn new(
+        options: ETHSenderConfig,
+        db: DB,
+        ethereum: EthereumGateway,
+        lanes: Vec<OperatorLane>,
+    ) -> Self {
+        for &lane in &[options.sender.prove_lane, options.sender.execute_lane] {
+            assert!(
+                lane <= lanes.len(),
+                "Lane {} is used, but only {} additional operator accounts are configured",
+                lane,
+                lanes.len()
+            );
+        }
+
         let mut connection = db
             .acquire_connection()
             .await
@@ -157,11 +229,35 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             .await
             .expect("Failed loading ETH operations stats");
 
+        // Nonces of the additional accounts are taken from the Ethereum node
+        // when the account is used for the first time.
+        for lane in &lanes {
+            let nonce = lane
+                .ethereum
+                .pending_nonce()
+                .await
+                .expect("Can't get the nonce of the additional operator account");
+            db.initialize_operator_nonce(&mut transaction, lane.address, nonce)
+                .await
+                .expect("Can't initialize the nonce of the additional operator account");
+        }
+
         let tx_queue = TxQueueBuilder::new(options.sender.max_txs_in_flight as usize)
             .with_sent_pending_txs(ongoing_ops.len())
             .with_commit_operations_count(stats.last_committed_block)
             .with_verify_operations_count(stats.last_verified_block)
             .with_execute_operations_count(stats.last_executed_block)
+            .with_confirmed_commit_block(Self::last_confirmed_block(
+                &ongoing_ops,
+                AggregatedActionType::CommitBlocks,
+                stats.last_committed_block,
+            ))
+            .with_confirmed_verify_block(Self::last_confirmed_block(
+                &ongoing_ops,
+                AggregatedActionType::PublishProofBlocksOnchain,
+                stats.last_verified_block,
+            ))
+            .with_lanes(options.sender.prove_lane, options.sender.execute_lane)
             .build();
 
         let gas_adjuster = GasAdjuster::new(&db).await;
@@ -174,11 +270,67 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
         Self {
             ethereum,
+            lanes,
             ongoing_ops,
             db,
             tx_queue,
             gas_adjuster,
             options,
+            last_balance_check: None,
+            paused: false,
+            alerted_held_ops: HashSet::new(),
+        }
+    }
+
+    /// Returns the last block for which the operation of the given type is confirmed,
+    /// given the unconfirmed operations and the last block for which the operation was sent.
+    fn last_confirmed_block(
+        ongoing_ops: &VecDeque<ETHOperation>,
+        op_type: AggregatedActionType,
+        last_sent_block: usize,
+    ) -> usize {
+        ongoing_ops
+            .iter()
+            .filter(|op| op.op_type == op_type)
+            .filter_map(|op| op.op.as_ref())
+            .map(|(_, op)| *op.get_block_range().0 as usize - 1)
+            .min()
+            .unwrap_or(last_sent_block)
+    }
+
+    /// Returns the account sending the transactions of the nonce lane,
+    /// `None` for the main operator account.
+    fn lane_sender(&self, lane: usize) -> Option<Address> {
+        match lane {
+            0 => None,
+            _ => Some(self.lanes[lane - 1].address),
+        }
+    }
+
+    /// Returns the Ethereum intermediator for the account sending the transactions of the nonce lane.
+    fn lane_gateway(&self, lane: usize) -> &EthereumGateway {
+        match lane {
+            0 => &self.ethereum,
+            _ => &self.lanes[lane - 1].ethereum,
+        }
+    }
+
+    /// Returns the Ethereum intermediator for the account the operation is sent from.
+    fn operation_gateway(&self, op: &ETHOperation) -> anyhow::Result<&EthereumGateway> {
+        match op.sender {
+            None => Ok(&self.ethereum),
+            Some(sender) => self
+                .lanes
+                .iter()
+                .find(|lane| lane.address == sender)
+                .map(|lane| &lane.ethereum)
+                .ok_or_else(|| {
+                    format_err!(
+                        "ETH Operation <id: {}> is sent from the account {:?}, which is not configured as an additional operator",
+                        op.id,
+                        sender
+                    )
+                }),
         }
     }
 
@@ -196,12 +348,18 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             }
 
             if self.options.sender.is_enabled {
+                // Apply the admin actions first, so the pause takes effect right away.
+                if let Err(e) = self.process_admin_actions().await {
+                    Self::process_error(e).await;
+                }
                 // ...and proceed them.
                 self.proceed_next_operations().await;
                 // Update the gas adjuster to maintain the up-to-date max gas price limit.
                 self.gas_adjuster
                     .keep_updated(&self.ethereum, &self.db)
                     .await;
+                // Warn in advance if the operator is running out of funds.
+                self.check_operator_balances().await;
             }
         }
     }
@@ -243,7 +401,24 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         // Queue for storing all the operations that were not finished at this iteration.
         let mut new_ongoing_ops = VecDeque::new();
 
-        while let Some(tx) = self.tx_queue.pop_front() {
+        if !self.paused {
+            self.release_held_operations().await;
+        }
+
+        // New transactions are not sent while sending is paused.
+        while let Some(tx) = self.pop_tx_to_send() {
+            match self.simulate_operation(&tx).await {
+                Ok(None) => {}
+                Ok(Some(failure)) => {
+                    self.hold_operation(tx, failure);
+                    continue;
+                }
+                Err(e) => {
+                    // Simulation is only a precaution, so the operation is sent anyway.
+                    vlog::warn!("Failed to simulate the operation, sending it as is: {}", e);
+                }
+            }
+
             if let Err(e) = self.initialize_operation(tx.clone()).await {
                 Self::process_error(e).await;
                 // Return the unperformed operation to the queue, since failing the
@@ -274,7 +449,13 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             match commitment {
                 OperationCommitment::Committed => {
                     // Free a slot for the next tx in the queue.
-                    self.tx_queue.report_commitment();
+                    let last_block = current_op
+                        .op
+                        .as_ref()
+                        .map(|(_, op)| op.get_block_range().1)
+                        .unwrap_or_default();
+                    self.tx_queue
+                        .report_commitment(current_op.op_type, last_block);
                 }
                 OperationCommitment::Pending => {
                     // Poll this operation on the next iteration.
@@ -293,6 +474,202 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         metrics::histogram!("eth_sender.proceed_next_operations", start.elapsed());
     }
 
+    /// Returns the next transaction to send, if sending is not paused.
+    fn pop_tx_to_send(&mut self) -> Option<TxData> {
+        if self.paused {
+            None
+        } else {
+            self.tx_queue.pop_front()
+        }
+    }
+
+    /// Simulates the transaction of the operation with `eth_call` against the pending block.
+    /// Returns the failure info if the transaction is expected to revert.
+    async fn simulate_operation(&self, tx: &TxData) -> anyhow::Result<Option<FailureInfo>> {
+        if !self.options.sender.simulate_txs {
+            return Ok(None);
+        }
+
+        let options = Options {
+            gas: Some(Self::gas_limit_for_aggregated_op(&tx.operation.1)),
+            ..Default::default()
+        };
+        let outcome = self
+            .lane_gateway(self.tx_queue.lane_for_action(tx.op_type))
+            .call_with_data(tx.raw.clone(), options, BlockNumber::Pending)
+            .await?;
+
+        match outcome {
+            CallOutcome::Success(_) => Ok(None),
+            CallOutcome::Reverted(failure) => Ok(Some(failure)),
+        }
+    }
+
+    /// Holds the operation which transaction is expected to revert.
+    fn hold_operation(&mut self, tx: TxData, failure: FailureInfo) {
+        let (first_block, last_block) = tx.get_block_range();
+        vlog::warn!(
+            "Transaction of the operation <id: {}; action: {:?}; blocks: {}-{}> is expected to revert \
+             with the reason: {}. The operation is held until the simulation succeeds",
+            tx.operation.0,
+            tx.op_type,
+            first_block,
+            last_block,
+            failure.revert_reason
+        );
+        self.alert_held_operation(&tx, &failure);
+
+        if let Err(err_message) = self.tx_queue.hold(tx) {
+            panic!("Failed to hold the operation in the queue: {}", err_message);
+        }
+    }
+
+    /// Raises an alert for the held operation, unless the revert may be caused by the
+    /// unconfirmed transactions or the alert is already raised.
+    fn alert_held_operation(&mut self, tx: &TxData, failure: &FailureInfo) {
+        if self.ongoing_ops.is_empty() && self.alerted_held_ops.insert(tx.operation.0) {
+            vlog::error!(
+                "Operation <id: {}; action: {:?}> can't be sent, since its transaction is expected \
+                 to revert: {:?}",
+                tx.operation.0,
+                tx.op_type,
+                failure
+            );
+        }
+    }
+
+    /// Simulates the held operations once again and releases the ones expected to succeed.
+    async fn release_held_operations(&mut self) {
+        for tx in self.tx_queue.held_operations() {
+            match self.simulate_operation(&tx).await {
+                Ok(None) => {
+                    vlog::info!(
+                        "Operation <id: {}; action: {:?}> is expected to succeed now and is released",
+                        tx.operation.0,
+                        tx.op_type
+                    );
+                    self.alerted_held_ops.remove(&tx.operation.0);
+                    if let Err(err_message) = self.tx_queue.release(tx.op_type) {
+                        panic!("Failed to release the held operation: {}", err_message);
+                    }
+                }
+                Ok(Some(failure)) => self.alert_held_operation(&tx, &failure),
+                Err(e) => Self::process_error(e).await,
+            }
+        }
+
+        metrics::gauge!(
+            "eth_sender.held_operations",
+            self.tx_queue.held_operations().len() as f64
+        );
+    }
+
+    /// Applies the admin actions requested via the admin API: updates the pause state and
+    /// performs the pending operation actions, recording their results.
+    async fn process_admin_actions(&mut self) -> anyhow::Result<()> {
+        let state = {
+            let mut connection = self.db.acquire_connection().await?;
+            self.db.load_sender_state(&mut connection).await?
+        };
+
+        if state.paused != self.paused {
+            vlog::info!(
+                "Sending of the Ethereum transactions is {}",
+                if state.paused { "paused" } else { "resumed" }
+            );
+            self.paused = state.paused;
+        }
+        metrics::gauge!("eth_sender.paused", if self.paused { 1.0 } else { 0.0 });
+
+        for action in state.pending_actions {
+            let result = match (action.action, action.eth_op_id) {
+                (ETHSenderAdminActionType::BumpGasPrice, Some(eth_op_id)) => {
+                    self.bump_gas_price(eth_op_id, action.gas_price).await
+                }
+                (ETHSenderAdminActionType::Resend, Some(eth_op_id)) => {
+                    self.resend_last_tx(eth_op_id).await
+                }
+                _ => Err(format_err!("Action can't be performed")),
+            };
+            let result = result.unwrap_or_else(|e| format!("Failed: {}", e));
+            vlog::info!(
+                "Admin action <id: {}, type: {}, eth_op_id: {:?}> requested by {} is performed: {}",
+                action.id,
+                action.action,
+                action.eth_op_id,
+                action.requested_by,
+                result
+            );
+
+            let mut connection = self.db.acquire_connection().await?;
+            self.db
+                .complete_admin_action(&mut connection, action.id, &result)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the position of the operation in the ongoing operations queue.
+    fn ongoing_op_idx(&self, eth_op_id: EthOpId) -> anyhow::Result<usize> {
+        self.ongoing_ops
+            .iter()
+            .position(|op| op.id == eth_op_id)
+            .ok_or_else(|| format_err!("ETH Operation <id: {}> is not pending", eth_op_id))
+    }
+
+    /// Replaces the pending transaction of the operation without waiting for it to get stuck.
+    /// If the gas price is not provided, it's chosen the same way as for the stuck transactions.
+    async fn bump_gas_price(
+        &mut self,
+        eth_op_id: EthOpId,
+        gas_price: Option<U256>,
+    ) -> anyhow::Result<String> {
+        let idx = self.ongoing_op_idx(eth_op_id)?;
+        // The operation is updated only if the transaction is sent.
+        let mut op = self.ongoing_ops[idx].clone();
+        let current_block = self.ethereum.block_number().await?;
+        let tx_hash = self
+            .send_supplement_tx(&mut op, current_block.as_u64(), gas_price)
+            .await?;
+
+        let result = format!(
+            "Sent tx {:#x} with gas price {}",
+            tx_hash, op.last_used_gas_price
+        );
+        self.ongoing_ops[idx] = op;
+        Ok(result)
+    }
+
+    /// Broadcasts the last transaction of the operation once again,
+    /// e.g. if it was dropped from the mempool.
+    async fn resend_last_tx(&mut self, eth_op_id: EthOpId) -> anyhow::Result<String> {
+        let idx = self.ongoing_op_idx(eth_op_id)?;
+        // Signing the operation with the last used parameters yields the last sent transaction.
+        let signed_tx = {
+            let op = &self.ongoing_ops[idx];
+            Self::sign_new_tx(self.operation_gateway(op)?, op).await?
+        };
+
+        // Signature may differ if the signer doesn't use deterministic signatures,
+        // in which case the new hash has to be tracked as well.
+        if !self.ongoing_ops[idx]
+            .used_tx_hashes
+            .contains(&signed_tx.hash)
+        {
+            let mut connection = self.db.acquire_connection().await?;
+            self.db
+                .add_hash_entry(&mut connection, eth_op_id, &signed_tx.hash)
+                .await?;
+            self.ongoing_ops[idx].used_tx_hashes.push(signed_tx.hash);
+        }
+
+        self.operation_gateway(&self.ongoing_ops[idx])?
+            .send_raw_tx(signed_tx.raw_tx)
+            .await?;
+        Ok(format!("Re-sent tx {:#x}", signed_tx.hash))
+    }
+
     async fn process_error(err: anyhow::Error) {
         vlog::warn!("Error while trying to complete uncommitted op: {}", err);
         if err.to_string().contains(RATE_LIMIT_HTTP_CODE) {
@@ -311,10 +688,21 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     async fn initialize_operation(&mut self, tx: TxData) -> anyhow::Result<()> {
         let current_block = self.ethereum.block_number().await?;
         let deadline_block = self.get_deadline_block(current_block.as_u64());
-        let gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.ethereum, None)
-            .await?;
+        let (gas_price, priority_fee) = if self.options.sender.use_eip1559 {
+            let fees = self
+                .gas_adjuster
+                .get_eip1559_fees(&self.ethereum, None)
+                .await?;
+            (fees.max_fee_per_gas, Some(fees.max_priority_fee_per_gas))
+        } else {
+            let gas_price = self
+                .gas_adjuster
+                .get_gas_price(&self.ethereum, None)
+                .await?;
+            (gas_price, None)
+        };
+
+        let sender = self.lane_sender(self.tx_queue.lane_for_action(tx.op_type));
 
         let mut connection = self.db.acquire_connection().await?;
         let mut transaction = connection.start_transaction().await?;
@@ -331,7 +719,9 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     Some(tx.operation.clone()),
                     deadline_block as i64,
                     gas_price,
+                    priority_fee,
                     tx.raw.clone(),
+                    sender,
                 )
                 .await?;
 
@@ -339,9 +729,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 id: assigned_data.id,
                 op_type: tx.op_type,
                 op: Some(tx.operation),
+                sender,
                 nonce: assigned_data.nonce,
                 last_deadline_block: deadline_block,
                 last_used_gas_price: gas_price,
+                last_used_priority_fee: priority_fee,
                 used_tx_hashes: vec![], // No hash yet, will be added below.
                 encoded_tx_data: tx.raw,
                 confirmed: false,
@@ -349,7 +741,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             };
 
             // Sign the transaction.
-            let signed_tx = Self::sign_new_tx(&self.ethereum, &new_op).await?;
+            let signed_tx = Self::sign_new_tx(self.operation_gateway(&new_op)?, &new_op).await?;
 
             // With signed tx, update the hash in the operation entry and in the db.
             new_op.used_tx_hashes.push(signed_tx.hash);
@@ -369,7 +761,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             "Sending new tx: [ETH Operation <id: {}, type: {:?}>. ETH tx: {}. ZKSync operation: {}]",
             new_op.id, new_op.op_type, self.eth_tx_description(&signed_tx), self.zksync_operation_description(&new_op),
         );
-        if let Err(e) = self.ethereum.send_raw_tx(signed_tx.raw_tx).await {
+        if let Err(e) = self
+            .operation_gateway(&new_op)?
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+        {
             // Sending tx error is not critical: this will result in transaction being considered stuck,
             // and resent. We can't do anything about this failure either, since it's most probably is not
             // related to the node logic, so we just log this error and pretend to have this operation
@@ -387,10 +783,18 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     fn eth_tx_description(&self, tx: &SignedCallResult) -> String {
         // Gas price in gwei (wei / 10^9).
         let gas_price = tx.gas_price / (1_000_000_000);
-        format!(
-            "<hash: {:#x}; gas price: {} gwei; nonce: {}>",
-            tx.hash, gas_price, tx.nonce
-        )
+        if let Some(priority_fee) = tx.max_priority_fee_per_gas {
+            let priority_fee = priority_fee / (1_000_000_000);
+            format!(
+                "<hash: {:#x}; max fee: {} gwei; priority fee: {} gwei; nonce: {}>",
+                tx.hash, gas_price, priority_fee, tx.nonce
+            )
+        } else {
+            format!(
+                "<hash: {:#x}; gas price: {} gwei; nonce: {}>",
+                tx.hash, gas_price, tx.nonce
+            )
+        }
     }
 
     /// Helper method to obtain the string representation of the zkSync operation.
@@ -446,7 +850,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed(cost) => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -480,7 +884,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                         op.id, op.op_type, tx_hash, self.zksync_operation_description(op),
                     );
                     self.db
-                        .confirm_operation(&mut transaction, tx_hash, op)
+                        .confirm_operation(&mut transaction, tx_hash, op, cost)
                         .await?;
                     transaction.commit().await?;
                     return Ok(OperationCommitment::Committed);
@@ -504,11 +908,31 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         }
 
         // Reaching this point will mean that the latest transaction got stuck.
-        // We should create another tx based on it, and send it.
-        let deadline_block = self.get_deadline_block(current_block.as_u64());
+        // We should create another tx based on it, and send it (unless sending is paused,
+        // then the transaction will be replaced once sending is resumed).
+        if !self.paused {
+            self.send_supplement_tx(op, current_block.as_u64(), None)
+                .await?;
+        }
+
+        metrics::histogram!("eth_sender.perform_commitment_step", start.elapsed());
+        Ok(OperationCommitment::Pending)
+    }
+
+    /// Replaces the last transaction of the operation with one with the increased gas price,
+    /// or with the given one. Returns the hash of the sent transaction.
+    async fn send_supplement_tx(
+        &mut self,
+        op: &mut ETHOperation,
+        current_block: u64,
+        gas_price: Option<U256>,
+    ) -> anyhow::Result<H256> {
+        let deadline_block = self.get_deadline_block(current_block);
         // Raw tx contents are the same for every transaction, so we just
         // create a new one from the old one with updated parameters.
-        let new_tx = self.create_supplement_tx(deadline_block, op).await?;
+        let new_tx = self
+            .create_supplement_tx(deadline_block, op, gas_price)
+            .await?;
         // New transaction should be persisted in the DB *before* sending it.
 
         let mut connection = self.db.acquire_connection().await?;
@@ -519,6 +943,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 op.id,
                 deadline_block as i64,
                 new_tx.gas_price,
+                new_tx.max_priority_fee_per_gas,
             )
             .await?;
         self.db
@@ -530,11 +955,86 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             op.id,
             self.eth_tx_description(&new_tx),
         );
-        self.ethereum.send_raw_tx(new_tx.raw_tx).await?;
+        self.operation_gateway(op)?
+            .send_raw_tx(new_tx.raw_tx)
+            .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("eth_sender.perform_commitment_step", start.elapsed());
-        Ok(OperationCommitment::Pending)
+        Ok(new_tx.hash)
+    }
+
+    /// Forecasts how long the balances of the operator accounts last at the current spending rate
+    /// and raises an alert for the ones running low. Does nothing if the balances were checked
+    /// recently.
+    async fn check_operator_balances(&mut self) {
+        if let Some(last_check) = self.last_balance_check {
+            if last_check.elapsed() < self.options.sender.balance_check_interval() {
+                return;
+            }
+        }
+        self.last_balance_check = Some(Instant::now());
+
+        let accounts = std::iter::once((None, &self.ethereum)).chain(
+            self.lanes
+                .iter()
+                .map(|lane| (Some(lane.address), &lane.ethereum)),
+        );
+        for (sender, ethereum) in accounts {
+            if let Err(err) = self.check_operator_balance(sender, ethereum).await {
+                vlog::warn!("Failed to check the operator account balance: {}", err);
+            }
+        }
+    }
+
+    /// Checks the balance of the operator account (`None` for the main one).
+    async fn check_operator_balance(
+        &self,
+        sender: Option<Address>,
+        ethereum: &EthereumGateway,
+    ) -> anyhow::Result<()> {
+        let window = self.options.sender.balance_forecast_window();
+        let balance = ethereum.sender_eth_balance().await?;
+        let spent = {
+            let mut connection = self.db.acquire_connection().await?;
+            let since = Utc::now() - chrono::Duration::from_std(window)?;
+            self.db
+                .load_spent_fee(&mut connection, sender, since)
+                .await?
+        };
+
+        let account = match sender {
+            Some(address) => format!("{:#x}", address),
+            None => "main".to_owned(),
+        };
+        metrics::gauge!(
+            "eth_sender.operator_balance",
+            wei_to_eth(balance),
+            "account" => account.clone()
+        );
+
+        let lasts_for = match balance_lasts_for(balance, spent, window) {
+            Some(lasts_for) => lasts_for,
+            // Nothing was spent recently, so there is nothing to forecast.
+            None => return Ok(()),
+        };
+        metrics::gauge!(
+            "eth_sender.operator_balance_forecast",
+            lasts_for.as_secs() as f64,
+            "account" => account.clone()
+        );
+
+        if lasts_for < self.options.sender.low_balance_alert_threshold() {
+            vlog::error!(
+                "Operator account {} balance is running low: {} ETH left, which lasts for ~{} hours at the current rate ({} ETH spent within the last {} hours)",
+                account,
+                wei_to_eth(balance),
+                lasts_for.as_secs() / 3600,
+                wei_to_eth(spent),
+                window.as_secs() / 3600,
+            );
+        }
+
+        Ok(())
     }
 
     /// Handles a transaction execution failure by reporting the issue to the log
@@ -573,7 +1073,18 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    // The gas price of the operation is the one of its latest replacement,
+                    // which is not necessarily the executed transaction, so the cost is taken
+                    // from the status of the executed transaction only.
+                    let cost = status.gas_used.and_then(|gas_used| {
+                        status
+                            .effective_gas_price
+                            .map(|effective_gas_price| ETHTxCost {
+                                gas_used,
+                                effective_gas_price,
+                            })
+                    });
+                    TxCheckOutcome::Committed(cost)
                 } else {
                     TxCheckOutcome::Pending
                 }
@@ -635,19 +1146,43 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             }
         };
 
-        let signed_tx = ethereum
-            .sign_prepared_tx(op.encoded_tx_data.clone(), tx_options)
-            .await?;
+        let signed_tx = match Self::last_used_eip1559_fees(op) {
+            Some(fees) => {
+                ethereum
+                    .sign_prepared_eip1559_tx(op.encoded_tx_data.clone(), tx_options, fees)
+                    .await?
+            }
+            None => {
+                ethereum
+                    .sign_prepared_tx(op.encoded_tx_data.clone(), tx_options)
+                    .await?
+            }
+        };
 
         Ok(signed_tx)
     }
 
+    /// Returns the fees of the last transaction sent for the operation
+    /// if it was an EIP-1559 one.
+    fn last_used_eip1559_fees(op: &ETHOperation) -> Option<Eip1559Fees> {
+        op.last_used_priority_fee
+            .map(|max_priority_fee_per_gas| Eip1559Fees {
+                max_fee_per_gas: op.last_used_gas_price,
+                max_priority_fee_per_gas,
+            })
+    }
+
     /// Calculates the gas limit for transaction to be send, depending on the type of operation.
     fn gas_limit_for_op(op: &ETHOperation) -> U256 {
         let (_, op) = op
             .op
             .as_ref()
             .expect("Operation not found - can't compute gas limit");
+        Self::gas_limit_for_aggregated_op(op)
+    }
+
+    /// Calculates the gas limit for the transaction of the zkSync operation.
+    fn gas_limit_for_aggregated_op(op: &AggregatedOperation) -> U256 {
         match op {
             AggregatedOperation::CommitBlocks(commit) => {
                 GasCounter::commit_gas_limit_aggregated(&commit.blocks)
@@ -670,14 +1205,24 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         &mut self,
         deadline_block: u64,
         stuck_tx: &mut ETHOperation,
+        gas_price: Option<U256>,
     ) -> anyhow::Result<SignedCallResult> {
-        let tx_options = self.tx_options_from_stuck_tx(stuck_tx).await?;
+        let (tx_options, fees) = self.tx_options_from_stuck_tx(stuck_tx, gas_price).await?;
 
         let raw_tx = stuck_tx.encoded_tx_data.clone();
-        let signed_tx = self.ethereum.sign_prepared_tx(raw_tx, tx_options).await?;
+        let ethereum = self.operation_gateway(stuck_tx)?;
+        let signed_tx = match fees {
+            Some(fees) => {
+                ethereum
+                    .sign_prepared_eip1559_tx(raw_tx, tx_options, fees)
+                    .await?
+            }
+            None => ethereum.sign_prepared_tx(raw_tx, tx_options).await?,
+        };
 
         stuck_tx.last_deadline_block = deadline_block;
         stuck_tx.last_used_gas_price = signed_tx.gas_price;
+        stuck_tx.last_used_priority_fee = signed_tx.max_priority_fee_per_gas;
         stuck_tx.used_tx_hashes.push(signed_tx.hash);
 
         Ok(signed_tx)
@@ -685,16 +1230,53 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
     /// Creates a new tx options from a stuck transaction, with updated gas amount
     /// and nonce.
+    ///
+    /// If the stuck transaction is an EIP-1559 one, its replacement will be an EIP-1559
+    /// transaction as well, and the bumped fees are returned along with the options.
+    ///
+    /// Gas price (max fee for the EIP-1559 transactions) requested via the admin API overrides
+    /// the chosen one, and is not cut by the gas price limit.
     async fn tx_options_from_stuck_tx(
         &mut self,
         stuck_tx: &ETHOperation,
-    ) -> anyhow::Result<Options> {
+        gas_price: Option<U256>,
+    ) -> anyhow::Result<(Options, Option<Eip1559Fees>)> {
         let old_tx_gas_price = stuck_tx.last_used_gas_price;
 
-        let new_gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.ethereum, Some(old_tx_gas_price))
-            .await?;
+        let (new_gas_price, new_fees) = match Self::last_used_eip1559_fees(stuck_tx) {
+            Some(old_fees) => {
+                let new_fees = self
+                    .gas_adjuster
+                    .get_eip1559_fees(&self.ethereum, Some(old_fees))
+                    .await?;
+                (new_fees.max_fee_per_gas, Some(new_fees))
+            }
+            None => {
+                let new_gas_price = self
+                    .gas_adjuster
+                    .get_gas_price(&self.ethereum, Some(old_tx_gas_price))
+                    .await?;
+                (new_gas_price, None)
+            }
+        };
+        let (new_gas_price, new_fees) = match gas_price {
+            Some(gas_price) => {
+                // Nodes don't accept the replacement unless the price is increased by 10%.
+                let min_gas_price = old_tx_gas_price * U256::from(11) / U256::from(10);
+                anyhow::ensure!(
+                    gas_price >= min_gas_price,
+                    "Gas price {} is too low to replace the transaction with gas price {}",
+                    gas_price,
+                    old_tx_gas_price
+                );
+                let new_fees = new_fees.map(|fees| Eip1559Fees {
+                    max_fee_per_gas: gas_price,
+                    max_priority_fee_per_gas: fees.max_priority_fee_per_gas.min(gas_price),
+                });
+                (gas_price, new_fees)
+            }
+            None => (new_gas_price, new_fees),
+        };
         let nonce = stuck_tx.nonce;
         let gas_limit = Self::gas_limit_for_op(stuck_tx);
 
@@ -713,11 +1295,12 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             gas_limit,
         );
 
-        Ok(Options::with(move |opt| {
+        let options = Options::with(move |opt| {
             opt.gas_price = Some(new_gas_price);
             opt.nonce = Some(nonce);
             opt.gas = Some(gas_limit);
-        }))
+        });
+        Ok((options, new_fees))
     }
 
     /// Encodes the operation data to the Ethereum tx payload (not signs it!).
@@ -768,10 +1351,20 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 #[must_use]
 pub fn run_eth_sender(pool: ConnectionPool, options: ZkSyncConfig) -> JoinHandle<()> {
     let ethereum = EthereumGateway::from_config(&options);
+    let lanes = options
+        .eth_sender
+        .sender
+        .additional_operators()
+        .into_iter()
+        .map(|(address, private_key)| OperatorLane {
+            address,
+            ethereum: EthereumGateway::for_operator(&options, address, private_key),
+        })
+        .collect();
     let db = Database::new(pool);
 
     tokio::spawn(async move {
-        let eth_sender = ETHSender::new(options.eth_sender, db, ethereum).await;
+        let eth_sender = ETHSender::new(options.eth_sender, db, ethereum, lanes).await;
 
         eth_sender.run().await
     })
@@ -1,22 +1,27 @@
 //! Mocking utilities for tests.
 
 // Built-in deps
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::convert::TryFrom;
 // External uses
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 use web3::contract::Options;
 use zksync_basic_types::{BlockNumber, H256, U256};
 // Workspace uses
-use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
+use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, OperatorSignerType, Sender};
 use zksync_eth_client::EthereumGateway;
 use zksync_storage::{ethereum::records::ETHParams, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
-use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
+use zksync_types::ethereum::{
+    ETHOperation, ETHSenderAdminAction, ETHSenderAdminActionType, ETHSenderState, EthOpId,
+    InsertedOperationResponse,
+};
+use zksync_types::Address;
 // Local uses
-use super::ETHSender;
+use super::{ETHSender, OperatorLane};
 use crate::database::DatabaseInterface;
-use crate::transactions::ETHStats;
+use crate::transactions::{ETHStats, ETHTxCost};
 use zksync_eth_client::clients::mock::MockEthereum;
 
 /// Mock database is capable of recording all the incoming requests for the further analysis.
@@ -26,6 +31,9 @@ pub(in crate) struct MockDatabase {
     aggregated_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     unprocessed_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     eth_parameters: RwLock<ETHParams>,
+    operator_nonces: RwLock<HashMap<Address, i64>>,
+    tx_costs: RwLock<Vec<(Option<Address>, ETHTxCost, DateTime<Utc>)>>,
+    admin_actions: RwLock<Vec<ETHSenderAdminAction>>,
 }
 
 impl MockDatabase {
@@ -41,6 +49,9 @@ impl MockDatabase {
             aggregated_operations: RwLock::new(aggregated_operations),
             unprocessed_operations: RwLock::new(unprocessed_operations),
             eth_parameters: RwLock::new(eth_parameters),
+            operator_nonces: Default::default(),
+            tx_costs: Default::default(),
+            admin_actions: Default::default(),
         }
     }
 
@@ -87,6 +98,46 @@ impl MockDatabase {
 
         assert!(is_confirmed);
     }
+
+    /// Simulates the admin API request, stores the admin action.
+    /// Pause and resume are applied right away, same as in the actual database.
+    pub async fn add_admin_action(
+        &self,
+        action: ETHSenderAdminActionType,
+        eth_op_id: Option<EthOpId>,
+        gas_price: Option<U256>,
+    ) -> ETHSenderAdminAction {
+        let mut admin_actions = self.admin_actions.write().await;
+        let processed = !action.is_operation_action();
+        let admin_action = ETHSenderAdminAction {
+            id: admin_actions.len() as i64,
+            action,
+            eth_op_id,
+            gas_price,
+            requested_by: "admin".to_owned(),
+            requested_at: Utc::now(),
+            processed_at: if processed { Some(Utc::now()) } else { None },
+            result: None,
+        };
+        admin_actions.push(admin_action.clone());
+        admin_action
+    }
+
+    /// Returns the stored admin action.
+    pub async fn admin_action(&self, id: i64) -> ETHSenderAdminAction {
+        self.admin_actions.read().await[id as usize].clone()
+    }
+
+    /// Returns the stored costs of the transactions sent from the operator account.
+    pub async fn tx_costs(&self, sender: Option<Address>) -> Vec<ETHTxCost> {
+        self.tx_costs
+            .read()
+            .await
+            .iter()
+            .filter(|(cost_sender, _, _)| *cost_sender == sender)
+            .map(|(_, cost, _)| *cost)
+            .collect()
+    }
 }
 
 #[async_trait::async_trait]
@@ -197,20 +248,38 @@ impl DatabaseInterface for MockDatabase {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         encoded_tx_data: Vec<u8>,
+        sender: Option<Address>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let mut eth_operations = self.eth_operations.write().await;
         let id = eth_operations.len() as i64;
-        let nonce = eth_operations.len();
+        // Every operator account has its own nonce sequence.
+        let nonce = match sender {
+            Some(address) => {
+                let mut operator_nonces = self.operator_nonces.write().await;
+                let nonce = operator_nonces
+                    .get_mut(&address)
+                    .expect("Nonce of the operator account is not initialized");
+                *nonce += 1;
+                (*nonce - 1) as usize
+            }
+            None => eth_operations
+                .iter()
+                .filter(|eth_op| eth_op.sender.is_none())
+                .count(),
+        };
 
         // Store with the assigned ID.
         let eth_operation = ETHOperation {
             id,
             op_type,
             op,
+            sender,
             nonce: nonce.into(),
             last_deadline_block: deadline_block as u64,
             last_used_gas_price: used_gas_price,
+            last_used_priority_fee: used_priority_fee,
             used_tx_hashes: vec![],
             encoded_tx_data,
             confirmed: false,
@@ -227,6 +296,21 @@ impl DatabaseInterface for MockDatabase {
         Ok(response)
     }
 
+    async fn initialize_operator_nonce(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: U256,
+    ) -> anyhow::Result<()> {
+        self.operator_nonces
+            .write()
+            .await
+            .entry(address)
+            .or_insert(nonce.as_u64() as i64);
+
+        Ok(())
+    }
+
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
@@ -254,6 +338,7 @@ impl DatabaseInterface for MockDatabase {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let eth_op = eth_operations
@@ -263,6 +348,7 @@ impl DatabaseInterface for MockDatabase {
         if let Some(eth_op) = eth_op {
             eth_op.last_deadline_block = new_deadline_block as u64;
             eth_op.last_used_gas_price = new_gas_value;
+            eth_op.last_used_priority_fee = new_priority_fee;
         } else {
             panic!("Attempt to update tx that is not unconfirmed");
         }
@@ -274,7 +360,8 @@ impl DatabaseInterface for MockDatabase {
         &self,
         _connection: &mut StorageProcessor<'_>,
         hash: &H256,
-        _op: &ETHOperation,
+        op: &ETHOperation,
+        cost: Option<ETHTxCost>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let mut op_idx: Option<i64> = None;
@@ -291,10 +378,75 @@ impl DatabaseInterface for MockDatabase {
             op_idx.is_some(),
             "Request to confirm operation that was not stored"
         );
+        if let Some(cost) = cost {
+            self.tx_costs
+                .write()
+                .await
+                .push((op.sender, cost, Utc::now()));
+        }
+
+        Ok(())
+    }
+
+    async fn load_sender_state(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<ETHSenderState> {
+        let admin_actions = self.admin_actions.read().await;
+        let last_change = admin_actions
+            .iter()
+            .rev()
+            .find(|action| !action.action.is_operation_action())
+            .cloned();
+        let pending_actions = admin_actions
+            .iter()
+            .filter(|action| action.processed_at.is_none())
+            .cloned()
+            .collect();
+
+        Ok(ETHSenderState {
+            paused: last_change
+                .as_ref()
+                .map(|change| change.action == ETHSenderAdminActionType::Pause)
+                .unwrap_or(false),
+            last_change,
+            pending_actions,
+        })
+    }
+
+    async fn complete_admin_action(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        id: i64,
+        result: &str,
+    ) -> anyhow::Result<()> {
+        let mut admin_actions = self.admin_actions.write().await;
+        let action = &mut admin_actions[id as usize];
+        action.processed_at = Some(Utc::now());
+        action.result = Some(result.to_owned());
 
         Ok(())
     }
 
+    async fn load_spent_fee(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        sender: Option<Address>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<U256> {
+        let fee = self
+            .tx_costs
+            .read()
+            .await
+            .iter()
+            .filter(|(cost_sender, _, confirmed_at)| {
+                *cost_sender == sender && *confirmed_at >= since
+            })
+            .fold(U256::zero(), |fee, (_, cost, _)| fee + cost.fee());
+
+        Ok(fee)
+    }
+
     async fn load_gas_price_limit(
         &self,
         _connection: &mut StorageProcessor<'_>,
@@ -321,6 +473,7 @@ impl DatabaseInterface for MockDatabase {
         _connection: &mut StorageProcessor<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool> {
+        let op_sender = op.sender;
         let confirmed = {
             let op = op.op.as_ref().unwrap();
             // We're checking previous block, so for the edge case of first block we can say that previous operation was confirmed.
@@ -331,11 +484,11 @@ impl DatabaseInterface for MockDatabase {
 
             let eth_operations = self.eth_operations.read().await.clone();
 
-            // Consider an operation that affects sequential blocks.
+            // Consider an operation sent from the same account that affects sequential blocks.
             let maybe_operation = eth_operations.iter().find(|(eth_operation)| {
                 let op_block_range = eth_operation.op.as_ref().unwrap().1.get_block_range();
 
-                op_block_range.1 == first_block - 1
+                op_block_range.1 == first_block - 1 && eth_operation.sender == op_sender
             });
 
             let operation = match maybe_operation {
@@ -410,6 +563,37 @@ pub(in crate) async fn restored_eth_sender(
     .await
 }
 
+/// Creates an `ETHSender` with mock Ethereum connection/database and no operations in DB
+/// which sends the proof and execute transactions using the additional operator accounts.
+/// Mock Ethereum connections of the additional accounts share the state with the main one.
+pub(in crate) async fn lanes_eth_sender(
+    max_txs_in_flight: u64,
+    additional_operators: Vec<Address>,
+    prove_lane: usize,
+    execute_lane: usize,
+) -> ETHSender<MockDatabase> {
+    let ethereum = MockEthereum::default();
+    let lanes = additional_operators
+        .into_iter()
+        .map(|address| OperatorLane {
+            address,
+            ethereum: EthereumGateway::Mock(ethereum.clone()),
+        })
+        .collect();
+    let db = MockDatabase::with_restorable_state(
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        default_eth_parameters(),
+    );
+
+    let mut options = eth_sender_options(max_txs_in_flight);
+    options.sender.prove_lane = prove_lane;
+    options.sender.execute_lane = execute_lane;
+
+    ETHSender::new(options, db, EthereumGateway::Mock(ethereum), lanes).await
+}
+
 /// Helper method for configurable creation of `ETHSender`.
 async fn build_eth_sender(
     max_txs_in_flight: u64,
@@ -425,14 +609,33 @@ async fn build_eth_sender(
         unprocessed_operations,
         eth_parameters,
     );
+    let options = eth_sender_options(max_txs_in_flight);
+
+    ETHSender::new(options, db, ethereum, Vec::new()).await
+}
 
-    let options = ETHSenderConfig {
+/// Creates the `ETHSender` config to be used in tests.
+fn eth_sender_options(max_txs_in_flight: u64) -> ETHSenderConfig {
+    ETHSenderConfig {
         sender: Sender {
             max_txs_in_flight,
             expected_wait_time_block: super::EXPECTED_WAIT_TIME_BLOCKS,
             wait_confirmations: super::WAIT_CONFIRMATIONS,
             tx_poll_period: 0,
             is_enabled: true,
+            use_eip1559: false,
+            signer_type: OperatorSignerType::PrivateKey,
+            remote_signer_url: None,
+            remote_signer_auth_token: None,
+            remote_signer_timeout: 10,
+            additional_operator_eth_addrs: None,
+            additional_operator_private_keys: None,
+            prove_lane: 0,
+            execute_lane: 0,
+            balance_check_interval: 300,
+            balance_forecast_window: 86400,
+            low_balance_alert_threshold: 259200,
+            simulate_txs: true,
             operator_commit_eth_addr: Default::default(),
             operator_private_key: Default::default(),
         },
@@ -441,10 +644,9 @@ async fn build_eth_sender(
             sample_interval: 15,
             update_interval: 15,
             scale_factor: 1.0f64,
+            max_priority_fee_per_gas: 10,
         },
-    };
-
-    ETHSender::new(options, db, ethereum).await
+    }
 }
 
 /// Behaves the same as `ETHSender::sign_new_tx`, but does not affect nonce.
@@ -475,9 +677,11 @@ pub(in crate) async fn create_signed_tx(
         id,
         op_type,
         op: Some(aggregated_operation.clone()),
+        sender: None,
         nonce: signed_tx.nonce,
         last_deadline_block: deadline_block,
         last_used_gas_price: signed_tx.gas_price,
+        last_used_priority_fee: None,
         used_tx_hashes: vec![signed_tx.hash],
         encoded_tx_data: raw_tx,
         confirmed: false,
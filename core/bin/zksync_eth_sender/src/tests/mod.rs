@@ -1,10 +1,18 @@
 // Local uses
 use self::mock::{
     concurrent_eth_sender, create_signed_tx, default_eth_parameters, default_eth_sender,
-    restored_eth_sender,
+    lanes_eth_sender, restored_eth_sender,
+};
+use super::{
+    transactions::{ETHTxCost, TxCheckOutcome},
+    ETHSender, TxCheckMode,
 };
-use super::{transactions::TxCheckOutcome, ETHSender, TxCheckMode};
 use zksync_eth_client::ethereum_gateway::ExecutedTxStatus;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    ethereum::{ETHSenderAdminAction, ETHSenderAdminActionType},
+    Address, U256,
+};
 
 const EXPECTED_WAIT_TIME_BLOCKS: u64 = 30;
 const WAIT_CONFIRMATIONS: u64 = 3;
@@ -49,6 +57,7 @@ async fn transaction_state() {
         test_data::commit_blocks_operation(3), // Will be failed and pending (not enough confirmations).
         test_data::commit_blocks_operation(4), // Will be stuck.
         test_data::commit_blocks_operation(5), // Will be pending due no response.
+        test_data::commit_blocks_operation(6), // Will be committed without the reported gas price.
     ];
     let mut eth_operations = Vec::with_capacity(operations.len());
 
@@ -70,6 +79,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: Some(100_000.into()),
+        effective_gas_price: Some(eth_operations[0].last_used_gas_price + 1),
     };
     eth_sender
         .ethereum
@@ -78,11 +89,28 @@ async fn transaction_state() {
         .add_execution(&eth_operations[0].used_tx_hashes[0], &committed_response)
         .await;
 
+    // Committed operation, but the node didn't report the gas price.
+    let committed_no_price_response = ExecutedTxStatus {
+        effective_gas_price: None,
+        ..committed_response.clone()
+    };
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .add_execution(
+            &eth_operations[6].used_tx_hashes[0],
+            &committed_no_price_response,
+        )
+        .await;
+
     // Pending operation.
     let pending_response = ExecutedTxStatus {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -96,6 +124,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -109,6 +139,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -131,7 +163,25 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        // The gas price of the executed transaction is taken, not the last used one.
+        TxCheckOutcome::Committed(Some(ETHTxCost {
+            gas_used: 100_000.into(),
+            effective_gas_price: eth_operations[0].last_used_gas_price + 1,
+        }))
+    );
+
+    // Committed operation without the reported gas price has no cost.
+    assert_eq!(
+        eth_sender
+            .check_transaction_state(
+                TxCheckMode::Latest,
+                &eth_operations[6],
+                eth_operations[6].used_tx_hashes[0],
+                current_block + committed_no_price_response.confirmations,
+            )
+            .await
+            .unwrap(),
+        TxCheckOutcome::Committed(None)
     );
 
     // Pending operation (no enough confirmations).
@@ -333,6 +383,7 @@ async fn stuck_transaction() {
         .create_supplement_tx(
             eth_sender.get_deadline_block(eth_sender.ethereum.get_mock().unwrap().block_number),
             &mut stuck_tx,
+            None,
         )
         .await
         .unwrap();
@@ -360,6 +411,53 @@ async fn stuck_transaction() {
     eth_sender.db.assert_confirmed(&stuck_tx).await;
 }
 
+/// Checks that with EIP-1559 enabled operations are sent as type 2 transactions,
+/// and both the max fee and the priority fee are bumped for the stuck ones.
+#[tokio::test]
+async fn stuck_eip1559_transaction() {
+    // Priority fee used by `GasAdjuster` in tests.
+    const PRIORITY_FEE: u64 = 10;
+
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender.options.sender.use_eip1559 = true;
+    let base_fee = eth_sender.ethereum.get_mock().unwrap().base_fee_per_gas;
+
+    let aggregated_operation = test_data::commit_blocks_operation(0);
+    eth_sender
+        .db
+        .send_aggregated_operation(aggregated_operation)
+        .await
+        .unwrap();
+
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+
+    // Max fee of the new transaction is twice the base fee plus the priority fee.
+    let sent_op = eth_sender.ongoing_ops[0].clone();
+    let expected_max_fee = base_fee * 2 + PRIORITY_FEE;
+    assert_eq!(sent_op.last_used_priority_fee, Some(PRIORITY_FEE.into()));
+    assert_eq!(sent_op.last_used_gas_price, expected_max_fee);
+    eth_sender.db.assert_stored(&sent_op).await;
+
+    // Skip some blocks and expect sender to send a new tx with both fees increased by 15%.
+    eth_sender.ethereum.get_mut_mock().unwrap().block_number += EXPECTED_WAIT_TIME_BLOCKS;
+    eth_sender.proceed_next_operations().await;
+
+    let bumped_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(bumped_op.used_tx_hashes.len(), 2);
+    assert_eq!(
+        bumped_op.last_used_priority_fee,
+        Some((PRIORITY_FEE * 115 / 100).into())
+    );
+    assert_eq!(bumped_op.last_used_gas_price, expected_max_fee * 115 / 100);
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .assert_sent(&bumped_op.used_tx_hashes[1].as_bytes().to_vec())
+        .await;
+}
+
 /// This test verifies that with multiple operations received all-together,
 /// their order is respected and no processing of the next operation is started until
 /// the previous one is committed.
@@ -694,6 +792,7 @@ async fn confirmations_independence() {
         .create_supplement_tx(
             eth_sender.get_deadline_block(eth_sender.ethereum.get_mock().unwrap().block_number),
             &mut stuck_tx,
+            None,
         )
         .await
         .unwrap();
@@ -872,3 +971,284 @@ async fn concurrent_operations_order() {
         }
     }
 }
+
+/// Checks that proof and execute transactions are sent from the additional operator account
+/// using its own nonce sequence, and that pending transactions in that lane don't block
+/// the confirmation of the commit transactions.
+#[tokio::test]
+async fn operator_lanes() {
+    const MAX_TXS_IN_FLIGHT: u64 = 3;
+    let operator = Address::repeat_byte(0x42);
+    let mut eth_sender = lanes_eth_sender(MAX_TXS_IN_FLIGHT, vec![operator], 1, 1).await;
+
+    for operation in &[
+        test_data::commit_blocks_operation(0),
+        test_data::publish_proof_blocks_onchain_operations(0),
+        test_data::execute_blocks_operations(0),
+        test_data::commit_blocks_operation(1),
+    ] {
+        eth_sender
+            .db
+            .send_aggregated_operation(operation.clone())
+            .await
+            .unwrap();
+    }
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+
+    // Proof is sent using another lane, so it waits for the commit to be confirmed.
+    assert_eq!(eth_sender.ongoing_ops.len(), 2);
+    for (nonce, op) in eth_sender.ongoing_ops.iter().enumerate() {
+        assert_eq!(op.op_type, AggregatedActionType::CommitBlocks);
+        assert_eq!(op.sender, None);
+        assert_eq!(op.nonce, U256::from(nonce));
+    }
+
+    // Confirm the first commit. Proof and execute are sent on the next round.
+    let commit_hash = eth_sender.ongoing_ops[0].used_tx_hashes[0];
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .add_successfull_execution(commit_hash, WAIT_CONFIRMATIONS)
+        .await;
+    eth_sender.proceed_next_operations().await;
+    eth_sender.proceed_next_operations().await;
+
+    assert_eq!(eth_sender.ongoing_ops.len(), 3);
+    let lane_ops: Vec<_> = eth_sender
+        .ongoing_ops
+        .iter()
+        .filter(|op| op.sender == Some(operator))
+        .cloned()
+        .collect();
+    assert_eq!(lane_ops.len(), 2);
+    assert_eq!(
+        lane_ops[0].op_type,
+        AggregatedActionType::PublishProofBlocksOnchain
+    );
+    assert_eq!(lane_ops[0].nonce, U256::from(0));
+    assert_eq!(lane_ops[1].op_type, AggregatedActionType::ExecuteBlocks);
+    assert_eq!(lane_ops[1].nonce, U256::from(1));
+    for op in &lane_ops {
+        eth_sender
+            .ethereum
+            .get_mock()
+            .unwrap()
+            .assert_sent(&op.used_tx_hashes[0].as_bytes().to_vec())
+            .await;
+    }
+
+    // The second commit is confirmed even though the proof sent before it is still pending.
+    let second_commit = eth_sender
+        .ongoing_ops
+        .iter()
+        .find(|op| op.sender.is_none())
+        .cloned()
+        .unwrap();
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .add_successfull_execution(second_commit.used_tx_hashes[0], WAIT_CONFIRMATIONS)
+        .await;
+    eth_sender.proceed_next_operations().await;
+
+    eth_sender.db.assert_confirmed(&second_commit).await;
+    assert_eq!(eth_sender.ongoing_ops.len(), 2);
+    assert!(eth_sender
+        .ongoing_ops
+        .iter()
+        .all(|op| op.sender == Some(operator)));
+}
+
+/// Checks that the costs of the confirmed transactions are recorded,
+/// and the operator balance is checked once per the configured interval.
+#[tokio::test]
+async fn transaction_costs() {
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender.ethereum.get_mut_mock().unwrap().balance = U256::exp10(18);
+
+    eth_sender
+        .db
+        .send_aggregated_operation(test_data::commit_blocks_operation(0))
+        .await
+        .unwrap();
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+
+    let tx_hash = eth_sender.ongoing_ops[0].used_tx_hashes[0];
+    let cost = ETHTxCost {
+        gas_used: 100_000.into(),
+        effective_gas_price: 50.into(),
+    };
+    let status = ExecutedTxStatus {
+        confirmations: WAIT_CONFIRMATIONS,
+        success: true,
+        receipt: None,
+        gas_used: Some(cost.gas_used),
+        effective_gas_price: Some(cost.effective_gas_price),
+    };
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .add_execution(&tx_hash, &status)
+        .await;
+    eth_sender.proceed_next_operations().await;
+
+    assert!(eth_sender.ongoing_ops.is_empty());
+    assert_eq!(eth_sender.db.tx_costs(None).await, vec![cost]);
+    assert_eq!(eth_sender.db.tx_costs(Some(Address::zero())).await, vec![]);
+
+    eth_sender.check_operator_balances().await;
+    let last_check = eth_sender.last_balance_check.unwrap();
+    // The next check is skipped until the interval passes.
+    eth_sender.check_operator_balances().await;
+    assert_eq!(eth_sender.last_balance_check, Some(last_check));
+}
+
+/// Checks the admin actions with `ETHSender`:
+/// - Nothing is sent while sending is paused.
+/// - Gas price bump replaces the pending transaction right away, and the requested
+///   gas price is checked to be high enough for the replacement.
+/// - Last transaction can be re-broadcast.
+#[tokio::test]
+async fn admin_actions() {
+    let mut eth_sender = default_eth_sender().await;
+
+    eth_sender
+        .db
+        .add_admin_action(ETHSenderAdminActionType::Pause, None, None)
+        .await;
+    eth_sender.process_admin_actions().await.unwrap();
+    assert!(eth_sender.paused);
+
+    eth_sender
+        .db
+        .send_aggregated_operation(test_data::commit_blocks_operation(0))
+        .await
+        .unwrap();
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+    assert!(eth_sender.ongoing_ops.is_empty());
+
+    eth_sender
+        .db
+        .add_admin_action(ETHSenderAdminActionType::Resume, None, None)
+        .await;
+    eth_sender.process_admin_actions().await.unwrap();
+    assert!(!eth_sender.paused);
+    eth_sender.proceed_next_operations().await;
+    assert_eq!(eth_sender.ongoing_ops.len(), 1);
+
+    let op = eth_sender.ongoing_ops[0].clone();
+    let low_price_bump = eth_sender
+        .db
+        .add_admin_action(
+            ETHSenderAdminActionType::BumpGasPrice,
+            Some(op.id),
+            Some(op.last_used_gas_price),
+        )
+        .await;
+    let bump = eth_sender
+        .db
+        .add_admin_action(
+            ETHSenderAdminActionType::BumpGasPrice,
+            Some(op.id),
+            Some(op.last_used_gas_price * 2),
+        )
+        .await;
+    let unknown_op_bump = eth_sender
+        .db
+        .add_admin_action(
+            ETHSenderAdminActionType::BumpGasPrice,
+            Some(op.id + 1),
+            None,
+        )
+        .await;
+    eth_sender.process_admin_actions().await.unwrap();
+
+    let result = |action: ETHSenderAdminAction| action.result.unwrap();
+    assert!(result(eth_sender.db.admin_action(low_price_bump.id).await).starts_with("Failed"));
+    assert!(result(eth_sender.db.admin_action(unknown_op_bump.id).await).starts_with("Failed"));
+    assert!(result(eth_sender.db.admin_action(bump.id).await).starts_with("Sent tx"));
+
+    let bumped_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(bumped_op.used_tx_hashes.len(), 2);
+    assert_eq!(bumped_op.last_used_gas_price, op.last_used_gas_price * 2);
+    eth_sender.db.assert_stored(&bumped_op).await;
+    eth_sender
+        .ethereum
+        .get_mock()
+        .unwrap()
+        .assert_sent(&bumped_op.used_tx_hashes[1].as_bytes().to_vec())
+        .await;
+
+    // Re-sent transaction is the same as the last one.
+    let resend = eth_sender
+        .db
+        .add_admin_action(ETHSenderAdminActionType::Resend, Some(op.id), None)
+        .await;
+    eth_sender.process_admin_actions().await.unwrap();
+    assert_eq!(
+        result(eth_sender.db.admin_action(resend.id).await),
+        format!("Re-sent tx {:#x}", bumped_op.used_tx_hashes[1])
+    );
+    assert_eq!(eth_sender.ongoing_ops[0], bumped_op);
+}
+
+/// Checks that the operation which transaction is expected to revert is held
+/// instead of being sent, and is sent once the simulation succeeds.
+#[tokio::test]
+async fn reverted_simulation() {
+    let mut eth_sender = default_eth_sender().await;
+
+    let operation = test_data::commit_blocks_operation(0);
+    let raw_tx = eth_sender.operation_to_raw_tx(&operation);
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_call_revert_reason(raw_tx.clone(), Some("i"))
+        .await;
+
+    eth_sender
+        .db
+        .send_aggregated_operation(operation)
+        .await
+        .unwrap();
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+
+    // Operation is held and the alert is raised, since there are no unconfirmed transactions.
+    assert!(eth_sender.ongoing_ops.is_empty());
+    assert_eq!(eth_sender.tx_queue.held_operations().len(), 1);
+    assert_eq!(eth_sender.alerted_held_ops.len(), 1);
+
+    // Operation stays held while the simulation fails.
+    eth_sender.proceed_next_operations().await;
+    assert!(eth_sender.ongoing_ops.is_empty());
+
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_call_revert_reason(raw_tx, None)
+        .await;
+    eth_sender.proceed_next_operations().await;
+
+    assert_eq!(eth_sender.ongoing_ops.len(), 1);
+    assert!(eth_sender.tx_queue.held_operations().is_empty());
+    assert!(eth_sender.alerted_held_ops.is_empty());
+    eth_sender
+        .ethereum
+        .get_mock()
+        .unwrap()
+        .assert_sent(
+            &eth_sender.ongoing_ops[0].used_tx_hashes[0]
+                .as_bytes()
+                .to_vec(),
+        )
+        .await;
+}
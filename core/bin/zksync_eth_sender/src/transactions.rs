@@ -5,7 +5,7 @@
 
 // Built-in deps
 // External uses
-use zksync_basic_types::TransactionReceipt;
+use zksync_basic_types::{TransactionReceipt, U256};
 // Workspace uses
 use zksync_storage::ethereum::records::ETHStats as StorageETHStats;
 
@@ -33,11 +33,28 @@ impl From<StorageETHStats> for ETHStats {
     }
 }
 
+/// Cost of the confirmed Ethereum transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ETHTxCost {
+    /// Amount of gas used by the transaction.
+    pub gas_used: U256,
+    /// Price paid per unit of gas.
+    pub effective_gas_price: U256,
+}
+
+impl ETHTxCost {
+    /// Fee paid for the transaction in wei.
+    pub fn fee(&self) -> U256 {
+        self.gas_used * self.effective_gas_price
+    }
+}
+
 /// The result of the check for the Ethereum transaction commitment.
 #[derive(Debug, PartialEq)]
 pub enum TxCheckOutcome {
-    /// Transaction was committed and confirmed.
-    Committed,
+    /// Transaction was committed and confirmed. Cost is `None` if the node
+    /// didn't report the amount of gas used or the gas price.
+    Committed(Option<ETHTxCost>),
     /// Transaction is pending yet.
     Pending,
     /// Transaction is considered stuck, a replacement should be made.
@@ -58,6 +58,12 @@ pub struct TxQueueBuilder {
     commit_operations_count: usize,
     verify_operations_count: usize,
     execute_operations_count: usize,
+
+    confirmed_commit_block: usize,
+    confirmed_verify_block: usize,
+
+    prove_lane: usize,
+    execute_lane: usize,
 }
 
 impl TxQueueBuilder {
@@ -69,6 +75,10 @@ impl TxQueueBuilder {
             commit_operations_count: 0,
             verify_operations_count: 0,
             execute_operations_count: 0,
+            confirmed_commit_block: 0,
+            confirmed_verify_block: 0,
+            prove_lane: 0,
+            execute_lane: 0,
         }
     }
 
@@ -104,6 +114,32 @@ impl TxQueueBuilder {
         }
     }
 
+    /// Sets the last block for which the `commit` operation is confirmed.
+    pub fn with_confirmed_commit_block(self, confirmed_commit_block: usize) -> Self {
+        Self {
+            confirmed_commit_block,
+            ..self
+        }
+    }
+
+    /// Sets the last block for which the `verify` operation is confirmed.
+    pub fn with_confirmed_verify_block(self, confirmed_verify_block: usize) -> Self {
+        Self {
+            confirmed_verify_block,
+            ..self
+        }
+    }
+
+    /// Sets the nonce lanes used to send the `verify` and `execute` operations.
+    /// `commit` operations are always sent using the lane `0`.
+    pub fn with_lanes(self, prove_lane: usize, execute_lane: usize) -> Self {
+        Self {
+            prove_lane,
+            execute_lane,
+            ..self
+        }
+    }
+
     /// Finishes the queue building process.
     pub fn build(self) -> TxQueue {
         TxQueue {
@@ -119,6 +155,12 @@ impl TxQueueBuilder {
             execute_operations: OperationQueue::new(BlockNumber(
                 self.execute_operations_count as u32,
             )),
+
+            confirmed_commit_block: BlockNumber(self.confirmed_commit_block as u32),
+            confirmed_verify_block: BlockNumber(self.confirmed_verify_block as u32),
+
+            prove_lane: self.prove_lane,
+            execute_lane: self.execute_lane,
         }
     }
 }
@@ -136,6 +178,15 @@ impl TxQueueBuilder {
 ///     for corresponding blocks is committed, the `verify` operation is yielded.
 ///   - Otherwise, if `commit` queue is not empty, a `commit` operation is yielded.
 /// 3. If all the queues are empty, no operation is returned.
+///
+/// Every operation type is assigned to a nonce lane (i.e. an operator account). Within one
+/// lane the order of transactions is enforced by the nonce, so an operation can be yielded
+/// right after the operation it depends on was sent. If the operations are sent using
+/// different lanes, the dependent operation is yielded only after its predecessor is
+/// confirmed, since otherwise it may get mined first and fail.
+///
+/// An operation can be held (e.g. if its transaction is expected to fail), in which case
+/// neither it nor the next operations of the same type are yielded until it's released.
 #[derive(Debug)]
 pub struct TxQueue {
     max_pending_txs: usize,
@@ -144,9 +195,24 @@ pub struct TxQueue {
     commit_operations: OperationQueue,
     verify_operations: OperationQueue,
     execute_operations: OperationQueue,
+
+    confirmed_commit_block: BlockNumber,
+    confirmed_verify_block: BlockNumber,
+
+    prove_lane: usize,
+    execute_lane: usize,
 }
 
 impl TxQueue {
+    /// Returns the nonce lane assigned to the operation type.
+    pub fn lane_for_action(&self, op_type: AggregatedActionType) -> usize {
+        match op_type {
+            AggregatedActionType::PublishProofBlocksOnchain => self.prove_lane,
+            AggregatedActionType::ExecuteBlocks => self.execute_lane,
+            AggregatedActionType::CommitBlocks | AggregatedActionType::CreateProofBlocks => 0,
+        }
+    }
+
     /// Adds the `commit` operation to the queue.
     pub fn add_commit_operation(&mut self, commit_operation: TxData) -> anyhow::Result<()> {
         self.commit_operations.push_back(commit_operation)?;
@@ -195,6 +261,21 @@ impl TxQueue {
         Ok(())
     }
 
+    /// Returns the underlying queue for the operations of the given type.
+    fn operation_queue(
+        &mut self,
+        op_type: AggregatedActionType,
+    ) -> anyhow::Result<&mut OperationQueue> {
+        match op_type {
+            AggregatedActionType::CommitBlocks => Ok(&mut self.commit_operations),
+            AggregatedActionType::PublishProofBlocksOnchain => Ok(&mut self.verify_operations),
+            AggregatedActionType::ExecuteBlocks => Ok(&mut self.execute_operations),
+            AggregatedActionType::CreateProofBlocks => Err(format_err!(
+                "Proof creation should never be sent to Ethereum"
+            )),
+        }
+    }
+
     /// Returns a previously popped element to the front of the queue.
     pub fn return_popped(&mut self, element: TxData) -> anyhow::Result<()> {
         assert!(
@@ -202,22 +283,8 @@ impl TxQueue {
             "No transactions are expected to be returned"
         );
 
-        match &element.op_type {
-            AggregatedActionType::CommitBlocks => {
-                self.commit_operations.return_popped(element)?;
-            }
-            AggregatedActionType::PublishProofBlocksOnchain => {
-                self.verify_operations.return_popped(element)?;
-            }
-            AggregatedActionType::ExecuteBlocks => {
-                self.execute_operations.return_popped(element)?;
-            }
-            AggregatedActionType::CreateProofBlocks => {
-                return Err(format_err!(
-                    "Proof creation should never be sent to Ethereum"
-                ));
-            }
-        }
+        self.operation_queue(element.op_type)?
+            .return_popped(element)?;
 
         // We've incremented the counter when transaction was popped.
         // Now it's returned and counter should be decremented back.
@@ -225,6 +292,33 @@ impl TxQueue {
         Ok(())
     }
 
+    /// Returns a previously popped element to the front of the queue and holds it there:
+    /// neither this operation nor the next operations of the same type are yielded
+    /// until it's released.
+    pub fn hold(&mut self, element: TxData) -> anyhow::Result<()> {
+        let op_type = element.op_type;
+        self.return_popped(element)?;
+        self.operation_queue(op_type)?.hold()
+    }
+
+    /// Releases the held operation of the given type, so it can be yielded again.
+    pub fn release(&mut self, op_type: AggregatedActionType) -> anyhow::Result<()> {
+        self.operation_queue(op_type)?.release();
+        Ok(())
+    }
+
+    /// Returns the currently held operations.
+    pub fn held_operations(&self) -> Vec<TxData> {
+        [
+            &self.commit_operations,
+            &self.verify_operations,
+            &self.execute_operations,
+        ]
+        .iter()
+        .filter_map(|queue| queue.held().cloned())
+        .collect()
+    }
+
     /// Gets the next transaction to send, according to the transaction sending policy.
     /// For details, see the structure doc-comment.
     pub fn pop_front(&mut self) -> Option<TxData> {
@@ -247,7 +341,11 @@ impl TxQueue {
     fn get_next_operation(&mut self) -> Option<TxData> {
         // 1. Highest priority: execute operations.
         if let Some(next_execute_block) = self.execute_operations.get_next_last_block_number() {
-            let current_verify_block = self.verify_operations.get_last_block_number();
+            let current_verify_block = if self.execute_lane == self.prove_lane {
+                self.verify_operations.get_last_block_number()
+            } else {
+                self.confirmed_verify_block
+            };
             if *next_execute_block <= *current_verify_block {
                 return Some(self.execute_operations.pop_front().unwrap());
             }
@@ -255,7 +353,12 @@ impl TxQueue {
 
         // 2. After execute operations we should process verify operation.
         if let Some(next_verify_block) = self.verify_operations.get_next_last_block_number() {
-            let current_commit_block = self.commit_operations.get_last_block_number();
+            let current_commit_block =
+                if self.prove_lane == self.lane_for_action(AggregatedActionType::CommitBlocks) {
+                    self.commit_operations.get_last_block_number()
+                } else {
+                    self.confirmed_commit_block
+                };
             if *next_verify_block <= *current_commit_block {
                 return Some(self.verify_operations.pop_front().unwrap());
             }
@@ -266,14 +369,22 @@ impl TxQueue {
     }
 
     /// Notifies the queue about the transaction being confirmed on the Ethereum blockchain.
-    /// Decrements the amount of transactions "in the fly".
-    pub fn report_commitment(&mut self) {
+    /// Decrements the amount of transactions "in the fly" and updates the last confirmed
+    /// block for the operation type.
+    pub fn report_commitment(&mut self, op_type: AggregatedActionType, last_block: BlockNumber) {
         assert!(
             self.sent_pending_txs > 0,
             "No transactions are expected to be confirmed"
         );
 
         self.sent_pending_txs -= 1;
+
+        let confirmed_block = match op_type {
+            AggregatedActionType::CommitBlocks => &mut self.confirmed_commit_block,
+            AggregatedActionType::PublishProofBlocksOnchain => &mut self.confirmed_verify_block,
+            _ => return,
+        };
+        *confirmed_block = std::cmp::max(*confirmed_block, last_block);
     }
 }
 
@@ -367,7 +478,7 @@ mod tests {
         assert_eq!(queue.pop_front(), None);
 
         // Report that one operation is completed.
-        queue.report_commitment();
+        queue.report_commitment(op_1.op_type, op_1.get_block_range().1);
 
         // Now we should obtain the next commit operation.
         let op_4 = queue.pop_front().unwrap();
@@ -378,8 +489,8 @@ mod tests {
 
         // Report the remaining three operations as completed.
         assert_eq!(queue.sent_pending_txs, MAX_IN_FLY);
-        for _ in 0..MAX_IN_FLY {
-            queue.report_commitment();
+        for op in &[op_2, op_3, op_4] {
+            queue.report_commitment(op.op_type, op.get_block_range().1);
         }
         assert_eq!(queue.sent_pending_txs, 0);
 
@@ -408,6 +519,152 @@ mod tests {
         assert_eq!(queue.sent_pending_txs, pending_count);
     }
 
+    /// Checks that operations sent using different nonce lanes are yielded only after
+    /// the operations they depend on are confirmed, while operations within one lane
+    /// only have to be sent in order.
+    #[test]
+    fn lanes_ordering() {
+        const MAX_IN_FLY: usize = 3;
+        const COMMIT_MARK: u8 = 0;
+        const VERIFY_MARK: u8 = 1;
+        const EXECUTE_MARK: u8 = 2;
+
+        // Proofs and executes are sent from the same additional account.
+        let mut queue = TxQueueBuilder::new(MAX_IN_FLY).with_lanes(1, 1).build();
+        assert_eq!(queue.lane_for_action(AggregatedActionType::CommitBlocks), 0);
+        assert_eq!(
+            queue.lane_for_action(AggregatedActionType::PublishProofBlocksOnchain),
+            1
+        );
+        assert_eq!(
+            queue.lane_for_action(AggregatedActionType::ExecuteBlocks),
+            1
+        );
+
+        for block in 1..=2 {
+            queue
+                .add_commit_operation(get_tx_data(
+                    AggregatedActionType::CommitBlocks,
+                    BlockNumber(block),
+                    vec![COMMIT_MARK, block as u8],
+                ))
+                .unwrap();
+            queue
+                .add_verify_operation(get_tx_data(
+                    AggregatedActionType::PublishProofBlocksOnchain,
+                    BlockNumber(block),
+                    vec![VERIFY_MARK, block as u8],
+                ))
+                .unwrap();
+            queue
+                .add_execute_operation(get_tx_data(
+                    AggregatedActionType::ExecuteBlocks,
+                    BlockNumber(block),
+                    vec![EXECUTE_MARK, block as u8],
+                ))
+                .unwrap();
+        }
+
+        // Proofs can't be sent until the commits are confirmed, so only commits are yielded.
+        let commit_1 = queue.pop_front().unwrap();
+        assert_eq!(commit_1.raw, vec![COMMIT_MARK, 1]);
+        let commit_2 = queue.pop_front().unwrap();
+        assert_eq!(commit_2.raw, vec![COMMIT_MARK, 2]);
+        assert_eq!(queue.pop_front(), None);
+
+        // Once the first commit is confirmed, the proof for the first block and the
+        // corresponding execute (which shares the lane with proofs) are yielded.
+        queue.report_commitment(commit_1.op_type, commit_1.get_block_range().1);
+        let verify_1 = queue.pop_front().unwrap();
+        assert_eq!(verify_1.raw, vec![VERIFY_MARK, 1]);
+        let execute_1 = queue.pop_front().unwrap();
+        assert_eq!(execute_1.raw, vec![EXECUTE_MARK, 1]);
+        assert_eq!(queue.pop_front(), None);
+
+        queue.report_commitment(commit_2.op_type, commit_2.get_block_range().1);
+        queue.report_commitment(verify_1.op_type, verify_1.get_block_range().1);
+        queue.report_commitment(execute_1.op_type, execute_1.get_block_range().1);
+        assert_eq!(queue.pop_front().unwrap().raw, vec![VERIFY_MARK, 2]);
+        assert_eq!(queue.pop_front().unwrap().raw, vec![EXECUTE_MARK, 2]);
+
+        // Now executes have their own lane, so they wait for the proofs to be confirmed.
+        // The proof for the first block is sent, but not confirmed yet.
+        let mut queue = TxQueueBuilder::new(MAX_IN_FLY)
+            .with_sent_pending_txs(1)
+            .with_commit_operations_count(1)
+            .with_verify_operations_count(1)
+            .with_confirmed_commit_block(1)
+            .with_lanes(0, 1)
+            .build();
+        queue
+            .add_execute_operation(get_tx_data(
+                AggregatedActionType::ExecuteBlocks,
+                BlockNumber(1),
+                vec![EXECUTE_MARK, 1],
+            ))
+            .unwrap();
+        assert_eq!(queue.pop_front(), None);
+
+        queue.report_commitment(
+            AggregatedActionType::PublishProofBlocksOnchain,
+            BlockNumber(1),
+        );
+        assert_eq!(queue.pop_front().unwrap().raw, vec![EXECUTE_MARK, 1]);
+    }
+
+    /// Checks that the held operation blocks the operations of the same type until it's
+    /// released, while the operations of other types are yielded as usual.
+    #[test]
+    fn held_operations() {
+        const MAX_IN_FLY: usize = 3;
+        const COMMIT_MARK: u8 = 0;
+        const VERIFY_MARK: u8 = 1;
+
+        let mut queue = TxQueueBuilder::new(MAX_IN_FLY)
+            .with_commit_operations_count(1)
+            .with_confirmed_commit_block(1)
+            .build();
+        for block in 2..=3 {
+            queue
+                .add_commit_operation(get_tx_data(
+                    AggregatedActionType::CommitBlocks,
+                    BlockNumber(block),
+                    vec![COMMIT_MARK, block as u8],
+                ))
+                .unwrap();
+        }
+        queue
+            .add_verify_operation(get_tx_data(
+                AggregatedActionType::PublishProofBlocksOnchain,
+                BlockNumber(1),
+                vec![VERIFY_MARK, 1],
+            ))
+            .unwrap();
+
+        let verify_1 = queue.pop_front().unwrap();
+        assert_eq!(verify_1.raw, vec![VERIFY_MARK, 1]);
+        let commit_2 = queue.pop_front().unwrap();
+        assert_eq!(commit_2.raw, vec![COMMIT_MARK, 2]);
+
+        // Holding the commit blocks the next commits, but doesn't occupy the slot of the sent tx.
+        queue.hold(commit_2).unwrap();
+        assert_eq!(queue.sent_pending_txs, 1);
+        assert_eq!(queue.pop_front(), None);
+        assert_eq!(
+            queue
+                .held_operations()
+                .into_iter()
+                .map(|op| op.raw)
+                .collect::<Vec<_>>(),
+            vec![vec![COMMIT_MARK, 2]]
+        );
+
+        queue.release(AggregatedActionType::CommitBlocks).unwrap();
+        assert!(queue.held_operations().is_empty());
+        assert_eq!(queue.pop_front().unwrap().raw, vec![COMMIT_MARK, 2]);
+        assert_eq!(queue.pop_front().unwrap().raw, vec![COMMIT_MARK, 3]);
+    }
+
     #[test]
     #[should_panic(expected = "No transactions are expected to be returned")]
     fn return_popped_empty() {
@@ -10,10 +10,14 @@ use zksync_types::BlockNumber;
 /// contains `TxData` and tracks the last popped block number.
 ///
 /// Must receive operations in ascending order of affected blocks.
+///
+/// The front element can be held, in which case no elements are taken
+/// from the queue until it's released.
 #[derive(Debug)]
 pub struct OperationQueue {
     pub(super) elements: VecDeque<TxData>,
     last_block_number: BlockNumber,
+    held: bool,
 }
 
 impl Default for OperationQueue {
@@ -21,6 +25,7 @@ impl Default for OperationQueue {
         Self {
             last_block_number: BlockNumber(0),
             elements: VecDeque::new(),
+            held: false,
         }
     }
 }
@@ -46,6 +51,30 @@ impl OperationQueue {
         Ok(())
     }
 
+    /// Holds the front element of the queue. Fails if the queue is empty.
+    pub fn hold(&mut self) -> anyhow::Result<()> {
+        if self.elements.is_empty() {
+            return Err(format_err!("There is no element to hold in the queue"));
+        }
+
+        self.held = true;
+        Ok(())
+    }
+
+    /// Releases the held element, so it can be taken from the queue again.
+    pub fn release(&mut self) {
+        self.held = false;
+    }
+
+    /// Returns the held element, if any.
+    pub fn held(&self) -> Option<&TxData> {
+        if self.held {
+            self.elements.front()
+        } else {
+            None
+        }
+    }
+
     /// Inserts an element to the end of the queue.
     pub fn push_back(&mut self, element: TxData) -> anyhow::Result<()> {
         let next_block_number = BlockNumber(
@@ -68,10 +97,14 @@ impl OperationQueue {
     }
 
     /// Attempts to take the next element from the queue. Returns `None`
-    /// if the queue is empty.
+    /// if the queue is empty or the front element is held.
     ///
     /// Taking the actual value updates the last affected block.
     pub fn pop_front(&mut self) -> Option<TxData> {
+        if self.held {
+            return None;
+        }
+
         match self.elements.pop_front() {
             Some(element) => {
                 self.last_block_number = element.get_block_range().1;
@@ -89,6 +122,10 @@ impl OperationQueue {
     /// Returns the value of the next affected block
     /// if will pop the top item out of the queue.
     pub fn get_next_last_block_number(&self) -> Option<BlockNumber> {
+        if self.held {
+            return None;
+        }
+
         self.elements
             .front()
             .map(|element| element.get_block_range().1)
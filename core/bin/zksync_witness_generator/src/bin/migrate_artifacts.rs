@@ -0,0 +1,38 @@
+//! Moves the witnesses and proofs stored in the database to the artifact store configured
+//! via `DATABASE_ARTIFACTS_DIR`, leaving only the references to them in the database.
+//! Can be run while the server is working, the artifacts are moved in small batches.
+
+use zksync_storage::ConnectionPool;
+
+/// Amount of the artifacts of each kind moved at once.
+const BATCH_SIZE: u32 = 10;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    vlog::init();
+
+    let connection_pool = ConnectionPool::new(Some(1));
+    let mut storage = connection_pool.access_storage().await?;
+    if storage.artifact_store().is_none() {
+        anyhow::bail!("Artifact store is not configured, set `DATABASE_ARTIFACTS_DIR`");
+    }
+
+    let mut total_moved = 0;
+    loop {
+        let moved = storage
+            .prover_schema()
+            .move_artifacts_to_store(BATCH_SIZE)
+            .await?;
+        if moved == 0 {
+            break;
+        }
+        total_moved += moved;
+        vlog::info!("Moved {} artifacts to the artifact store", total_moved);
+    }
+
+    vlog::info!(
+        "Migration completed, {} artifacts moved to the artifact store",
+        total_moved
+    );
+    Ok(())
+}
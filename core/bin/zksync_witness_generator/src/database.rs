@@ -5,13 +5,19 @@
 
 // Built-in
 use std::clone::Clone;
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_prover_utils::api::ProverInputRequestAuxData;
+use zksync_storage::{
+    prover::records::{StoragePendingJobs, StorageProofTime, StorageProverInfo},
+    ConnectionPool, StorageProcessor,
+};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobFilter, ProverJobType},
     AccountMap, AccountUpdates, BlockNumber,
 };
 // Local uses
@@ -62,6 +68,20 @@ impl DatabaseInterface for Database {
         Ok(witness)
     }
 
+    async fn witness_exists(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<bool> {
+        let exists = connection
+            .prover_schema()
+            .witness_exists(block_number)
+            .await?;
+
+        Ok(exists)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn add_prover_job_to_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -70,10 +90,18 @@ impl DatabaseInterface for Database {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        block_size: Option<usize>,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .add_prover_job_to_job_queue(first_block, last_block, job_data, job_priority, job_type)
+            .add_prover_job_to_job_queue(
+                first_block,
+                last_block,
+                job_data,
+                job_priority,
+                job_type,
+                block_size,
+            )
             .await?;
 
         Ok(())
@@ -107,10 +135,63 @@ impl DatabaseInterface for Database {
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<()> {
-        connection.prover_schema().mark_stale_jobs_as_idle().await?;
+        max_attempts: u32,
+    ) -> anyhow::Result<Vec<i32>> {
+        let failed_jobs = connection
+            .prover_schema()
+            .mark_stale_jobs_as_idle(max_attempts)
+            .await?;
 
-        Ok(())
+        Ok(failed_jobs)
+    }
+
+    async fn failed_jobs_count(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<u32> {
+        let count = connection.prover_schema().failed_jobs_count().await?;
+
+        Ok(count)
+    }
+
+    async fn load_prover_job(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+    ) -> anyhow::Result<Option<ProverJob>> {
+        let job = connection
+            .prover_schema()
+            .get_prover_job_by_id(job_id)
+            .await?;
+
+        Ok(job)
+    }
+
+    async fn record_invalid_proof(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        max_attempts: u32,
+    ) -> anyhow::Result<bool> {
+        let failed = connection
+            .prover_schema()
+            .record_invalid_proof(job_id, max_attempts)
+            .await?;
+
+        Ok(failed)
+    }
+
+    async fn requeue_failed_job(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+    ) -> anyhow::Result<bool> {
+        let requeued = connection
+            .prover_schema()
+            .requeue_failed_job(job_id)
+            .await?;
+
+        Ok(requeued)
     }
 
     async fn load_last_verified_block(
@@ -152,10 +233,11 @@ impl DatabaseInterface for Database {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        filter: &ProverJobFilter,
     ) -> anyhow::Result<Option<ProverJob>> {
         let proof = connection
             .prover_schema()
-            .get_idle_prover_job_from_job_queue()
+            .get_idle_prover_job_from_job_queue(filter)
             .await?;
 
         Ok(proof)
@@ -285,4 +367,63 @@ impl DatabaseInterface for Database {
 
         Ok(count)
     }
+
+    async fn load_pending_jobs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StoragePendingJobs>> {
+        let pending_jobs = connection.prover_schema().load_pending_jobs().await?;
+
+        Ok(pending_jobs)
+    }
+
+    async fn load_average_proof_times(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProofTime>> {
+        let proof_times = connection
+            .prover_schema()
+            .load_average_proof_times(since)
+            .await?;
+
+        Ok(proof_times)
+    }
+
+    async fn record_prover_request(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        aux_data: &ProverInputRequestAuxData,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .record_prover_request(prover_name, aux_data)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn record_prover_job_assigned(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        job_id: i32,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .record_prover_job_assigned(prover_name, job_id)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn load_provers(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverInfo>> {
+        let provers = connection.prover_schema().load_provers().await?;
+
+        Ok(provers)
+    }
 }
@@ -6,13 +6,19 @@
 // Built-in
 use std::clone::Clone;
 use std::marker::{Send, Sync};
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_storage::StorageProcessor;
+use zksync_prover_utils::api::ProverInputRequestAuxData;
+use zksync_storage::{
+    prover::records::{StoragePendingJobs, StorageProofTime, StorageProverInfo},
+    StorageProcessor,
+};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::{
     block::Block,
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobFilter, ProverJobType},
     AccountMap, AccountUpdates, BlockNumber,
 };
 
@@ -36,6 +42,15 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         block_number: BlockNumber,
     ) -> anyhow::Result<Option<serde_json::Value>>;
 
+    /// Checks whether the witness for a block is stored, without loading it.
+    async fn witness_exists(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<bool>;
+
+    /// Adds the job to the queue. The block chunk size is expected for the single block proofs.
+    #[allow(clippy::too_many_arguments)]
     async fn add_prover_job_to_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -44,6 +59,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        block_size: Option<usize>,
     ) -> anyhow::Result<()>;
 
     async fn load_aggregated_op_that_affects_block(
@@ -59,10 +75,39 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         block_number: BlockNumber,
     ) -> anyhow::Result<Option<SingleProof>>;
 
+    /// Returns stale jobs back to the queue. Jobs that were already given to the provers
+    /// `max_attempts` times are marked as failed instead, and their IDs are returned.
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<()>;
+        max_attempts: u32,
+    ) -> anyhow::Result<Vec<i32>>;
+
+    async fn failed_jobs_count(&self, connection: &mut StorageProcessor<'_>)
+        -> anyhow::Result<u32>;
+
+    /// Loads the prover job with the given ID.
+    async fn load_prover_job(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+    ) -> anyhow::Result<Option<ProverJob>>;
+
+    /// Returns the job with an invalid proof back to the queue, or marks it as failed if it was
+    /// already given to the provers `max_attempts` times. Returns `true` if the job was marked as failed.
+    async fn record_invalid_proof(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        max_attempts: u32,
+    ) -> anyhow::Result<bool>;
+
+    /// Returns the failed job back to the queue. Returns `false` if there is no failed job with the given ID.
+    async fn requeue_failed_job(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+    ) -> anyhow::Result<bool>;
 
     async fn load_last_verified_block(
         &self,
@@ -80,9 +125,11 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<Option<(BlockNumber, serde_json::Value)>>;
 
+    /// Gives the next idle job satisfying the filter.
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        filter: &ProverJobFilter,
     ) -> anyhow::Result<Option<ProverJob>>;
 
     async fn record_prover_is_working(
@@ -146,4 +193,40 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         &self,
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<u32>;
+
+    /// Returns the pending jobs grouped by the job type and block chunk size.
+    async fn load_pending_jobs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StoragePendingJobs>>;
+
+    /// Returns the average proof times of the jobs completed since the given moment,
+    /// grouped by the job type and block chunk size.
+    async fn load_average_proof_times(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProofTime>>;
+
+    /// Registers the prover requesting a job along with the capabilities it advertised.
+    async fn record_prover_request(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        aux_data: &ProverInputRequestAuxData,
+    ) -> anyhow::Result<()>;
+
+    /// Records the job given to the prover.
+    async fn record_prover_job_assigned(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        job_id: i32,
+    ) -> anyhow::Result<()>;
+
+    /// Returns all the registered provers.
+    async fn load_provers(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverInfo>>;
 }
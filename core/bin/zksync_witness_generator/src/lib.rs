@@ -10,6 +10,7 @@ use actix_web_httpauth::extractors::{
     AuthenticationError,
 };
 use actix_web_httpauth::middleware::HttpAuthentication;
+use chrono::{DateTime, Utc};
 use futures::channel::mpsc;
 use jsonwebtoken::errors::Error as JwtError;
 use jsonwebtoken::{decode, DecodingKey, Validation};
@@ -19,7 +20,9 @@ use tokio::sync::RwLock;
 use zksync_config::ZkSyncConfig;
 // Local deps
 use self::database_interface::DatabaseInterface;
-use self::scaler::ScalerOracle;
+use self::proof_verifier::ProofVerifier;
+use self::scaler::{JobsProversRequired, ScalerOracle};
+use self::setup_requirements::SetupRequirements;
 use zksync_circuit::serialization::ProverData;
 use zksync_prover_utils::api::{
     JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
@@ -39,7 +42,9 @@ mod tests;
 
 pub mod database;
 mod database_interface;
+mod proof_verifier;
 mod scaler;
+mod setup_requirements;
 mod witness_generator;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,19 +60,49 @@ struct AppState<DB: DatabaseInterface> {
     secret_auth: String,
     database: DB,
     scaler_oracle: Arc<RwLock<ScalerOracle<DB>>>,
+    /// Time given to prove a job since it was added to the queue.
+    block_prove_deadline: chrono::Duration,
+    /// Time without heartbeats after which the prover is considered offline.
+    gone_timeout: chrono::Duration,
+    /// Used to give the provers only the jobs they are able to perform.
+    setup_requirements: SetupRequirements,
+    /// Used to reject invalid proofs, `None` if the proof verification is disabled.
+    proof_verifier: Option<ProofVerifier>,
+    /// Amount of times a job is given to the provers before it's considered failed.
+    max_job_attempts: u32,
 }
 
 impl<DB: DatabaseInterface> AppState<DB> {
-    pub fn new(secret_auth: String, database: DB, idle_provers: u32) -> Self {
+    pub fn new(database: DB, config: &ZkSyncConfig) -> Self {
+        let core_opts = &config.prover.core;
         let scaler_oracle = Arc::new(RwLock::new(ScalerOracle::new(
             database.clone(),
-            idle_provers,
+            core_opts.idle_provers,
+            core_opts.max_provers,
+            config.chain.state_keeper.block_prove_deadline(),
         )));
+        let circuit = &config.chain.circuit;
+        let proof_verifier = if core_opts.verify_proofs {
+            Some(ProofVerifier::new(
+                circuit.supported_block_chunks_sizes.clone(),
+            ))
+        } else {
+            None
+        };
 
         Self {
-            secret_auth,
+            secret_auth: config.api.prover.secret_auth.clone(),
             database,
             scaler_oracle,
+            block_prove_deadline: chrono::Duration::from_std(
+                config.chain.state_keeper.block_prove_deadline(),
+            )
+            .expect("Block prove deadline is out of range"),
+            gone_timeout: chrono::Duration::from_std(core_opts.gone_timeout())
+                .expect("Prover gone timeout is out of range"),
+            setup_requirements: SetupRequirements::new(circuit, core_opts.ram_per_constraint),
+            proof_verifier,
+            max_job_attempts: core_opts.max_job_attempts,
         }
     }
 
@@ -125,16 +160,37 @@ async fn get_job<DB: DatabaseInterface>(
         return Err(actix_web::error::ErrorBadRequest("empty name"));
     }
     let mut storage = data.access_storage().await?;
+    data.database
+        .record_prover_request(&mut storage, &r.prover_name, &r.aux_data)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to record prover request: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    let job_filter = data
+        .setup_requirements
+        .job_filter(r.aux_data.capabilities.as_ref());
     let ret = data
         .database
-        .load_idle_prover_job_from_job_queue(&mut storage)
+        .load_idle_prover_job_from_job_queue(&mut storage, &job_filter)
         .await
         .map_err(|e| {
             vlog::warn!("could not get next unverified commit operation: {}", e);
             actix_web::error::ErrorInternalServerError("storage layer error")
         })?;
     if let Some(prover_job) = ret {
-        vlog::info!("satisfied request to prove from worker");
+        vlog::info!(
+            "satisfied request to prove from worker, job id: {}, attempt: {}",
+            prover_job.job_id,
+            prover_job.attempts
+        );
+        data.database
+            .record_prover_job_assigned(&mut storage, &r.prover_name, prover_job.job_id)
+            .await
+            .map_err(|e| {
+                vlog::warn!("failed to record the job given to the prover: {}", e);
+                actix_web::error::ErrorInternalServerError("storage layer error")
+            })?;
         Ok(HttpResponse::Ok().json(ProverInputResponse {
             job_id: prover_job.job_id,
             first_block: prover_job.first_block,
@@ -143,6 +199,7 @@ async fn get_job<DB: DatabaseInterface>(
                 serde_json::from_value(prover_job.job_data)
                     .expect("Failed to parse prover job from db"),
             ),
+            deadline: Some(prover_job.created_at + data.block_prove_deadline),
         }))
     } else {
         Ok(HttpResponse::Ok().json(ProverInputResponse {
@@ -150,6 +207,7 @@ async fn get_job<DB: DatabaseInterface>(
             first_block: BlockNumber(0),
             last_block: BlockNumber(0),
             data: None,
+            deadline: None,
         }))
     }
 }
@@ -184,6 +242,9 @@ async fn publish<DB: DatabaseInterface>(
         .access_storage()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
+    if let Some(proof_verifier) = &data.proof_verifier {
+        verify_published_proof(&data, &mut storage, proof_verifier, &r).await?;
+    }
     let storage_result = match &r.data {
         JobResultData::BlockProof(single_proof) => {
             vlog::info!(
@@ -226,6 +287,67 @@ async fn publish<DB: DatabaseInterface>(
     Ok(HttpResponse::Ok().finish())
 }
 
+/// Checks the published proof against the job it was created for. If the proof is invalid, the job
+/// is returned to the queue and the error is returned, so the proof is not stored.
+async fn verify_published_proof<DB: DatabaseInterface>(
+    data: &AppState<DB>,
+    storage: &mut zksync_storage::StorageProcessor<'_>,
+    proof_verifier: &ProofVerifier,
+    r: &ProverOutputRequest,
+) -> actix_web::Result<()> {
+    let job = data
+        .database
+        .load_prover_job(storage, r.job_id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load prover job: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("unknown job"))?;
+
+    let valid = if job.first_block == r.first_block && job.last_block == r.last_block {
+        let job_data: JobRequestData =
+            serde_json::from_value(job.job_data).expect("Failed to parse prover job from db");
+        let proof_verifier = proof_verifier.clone();
+        let result = r.data.clone();
+        web::block(move || proof_verifier.verify(&job_data, &result))
+            .await
+            .map_err(|e| {
+                vlog::error!("failed to verify the proof for job {}: {}", r.job_id, e);
+                actix_web::error::ErrorInternalServerError("proof verification error")
+            })?
+    } else {
+        false
+    };
+    if valid {
+        return Ok(());
+    }
+
+    let failed = data
+        .database
+        .record_invalid_proof(storage, r.job_id, data.max_job_attempts)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to record invalid proof: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::warn!(
+        "Received an invalid proof for job: {}, blocks: [{},{}]",
+        r.job_id,
+        r.first_block,
+        r.last_block
+    );
+    if failed {
+        vlog::error!(
+            "Prover job {} was given to the provers {} times and is marked as failed",
+            r.job_id,
+            data.max_job_attempts
+        );
+    }
+
+    Err(actix_web::error::ErrorBadRequest("invalid proof"))
+}
+
 async fn stopped<DB: DatabaseInterface>(
     data: web::Data<AppState<DB>>,
     prover_name: web::Json<String>,
@@ -251,6 +373,101 @@ async fn stopped<DB: DatabaseInterface>(
     Ok(HttpResponse::Ok().finish())
 }
 
+/// Returns the failed prover job back to the queue.
+async fn requeue_job<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    job_id: web::Json<i32>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let requeued = data
+        .database
+        .requeue_failed_job(&mut storage, *job_id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to requeue job: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if !requeued {
+        return Err(actix_web::error::ErrorNotFound("failed job not found"));
+    }
+
+    vlog::info!("Failed prover job {} is returned to the queue", *job_id);
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Status of the prover in the output of the `/provers` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverStatus {
+    name: String,
+    version: Option<String>,
+    preferred_block_size: Option<usize>,
+    prefer_aggregated_proof: Option<bool>,
+    block_sizes: Option<Vec<usize>>,
+    aggregated_proof_sizes: Option<Vec<usize>>,
+    /// Available RAM in bytes.
+    available_ram: Option<u64>,
+    registered_at: DateTime<Utc>,
+    last_heartbeat: DateTime<Utc>,
+    stopped_at: Option<DateTime<Utc>>,
+    /// Whether the prover is running and sent a heartbeat recently.
+    online: bool,
+    current_job_id: Option<i32>,
+    jobs_completed: u64,
+    jobs_failed: u64,
+    /// Amount of the proofs submitted by the prover which didn't pass the verification.
+    invalid_proofs: u64,
+    /// Average time to complete a job in ms.
+    average_proof_time: Option<u64>,
+}
+
+async fn provers<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let provers = data
+        .database
+        .load_provers(&mut storage)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load provers: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    let now = Utc::now();
+    let response: Vec<_> = provers
+        .into_iter()
+        .map(|prover| ProverStatus {
+            online: prover.stopped_at.is_none() && now - prover.last_heartbeat < data.gone_timeout,
+            average_proof_time: if prover.jobs_completed > 0 {
+                Some((prover.total_proof_time_ms / prover.jobs_completed) as u64)
+            } else {
+                None
+            },
+            name: prover.name,
+            version: prover.version,
+            preferred_block_size: prover.preferred_block_size.map(|size| size as usize),
+            prefer_aggregated_proof: prover.prefer_aggregated_proof,
+            block_sizes: prover
+                .block_sizes
+                .map(|sizes| sizes.into_iter().map(|size| size as usize).collect()),
+            aggregated_proof_sizes: prover
+                .aggregated_proof_sizes
+                .map(|sizes| sizes.into_iter().map(|size| size as usize).collect()),
+            available_ram: prover.available_ram.map(|ram| ram as u64),
+            registered_at: prover.registered_at,
+            last_heartbeat: prover.last_heartbeat,
+            stopped_at: prover.stopped_at,
+            current_job_id: prover.current_job_id,
+            jobs_completed: prover.jobs_completed as u64,
+            jobs_failed: prover.jobs_failed as u64,
+            invalid_proofs: prover.invalid_proofs as u64,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
 /// Input of the `/scaler/replicas` endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequiredReplicasInput {
@@ -264,6 +481,8 @@ pub struct RequiredReplicasOutput {
     /// Amount of the prover entities required for server
     /// to run optimally.
     needed_count: u32,
+    /// Provers required for each kind of the pending jobs.
+    jobs: Vec<JobsProversRequired>,
 }
 
 async fn required_replicas<DB: DatabaseInterface>(
@@ -272,22 +491,25 @@ async fn required_replicas<DB: DatabaseInterface>(
 ) -> actix_web::Result<HttpResponse> {
     let mut oracle = data.scaler_oracle.write().await;
 
-    let needed_count = oracle
+    let provers_required = oracle
         .provers_required()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
 
-    let response = RequiredReplicasOutput { needed_count };
+    let response = RequiredReplicasOutput {
+        needed_count: provers_required.needed_count,
+        jobs: provers_required.jobs,
+    };
 
     Ok(HttpResponse::Ok().json(response))
 }
 
-async fn update_prover_job_queue_loop<DB: DatabaseInterface>(database: DB) {
+async fn update_prover_job_queue_loop<DB: DatabaseInterface>(database: DB, max_job_attempts: u32) {
     let mut interval = tokio::time::interval(Duration::from_secs(5));
     loop {
         interval.tick().await;
 
-        update_prover_job_queue(database.clone())
+        update_prover_job_queue(database.clone(), max_job_attempts)
             .await
             .unwrap_or_else(|e| {
                 vlog::warn!("Failed to update prover job queue: {}", e);
@@ -295,7 +517,10 @@ async fn update_prover_job_queue_loop<DB: DatabaseInterface>(database: DB) {
     }
 }
 
-async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow::Result<()> {
+async fn update_prover_job_queue<DB: DatabaseInterface>(
+    database: DB,
+    max_job_attempts: u32,
+) -> anyhow::Result<()> {
     let mut connection = database.acquire_connection().await?;
     {
         let next_single_block_to_add = database
@@ -320,6 +545,7 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                     job_data,
                     SINGLE_PROOF_JOB_PRIORITY,
                     ProverJobType::SingleProof,
+                    Some(block_size),
                 )
                 .await?;
         }
@@ -369,11 +595,23 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                     job_data,
                     AGGREGATED_PROOF_JOB_PRIORITY,
                     ProverJobType::AggregatedProof,
+                    None,
                 )
                 .await?;
         }
     }
-    database.mark_stale_jobs_as_idle(&mut connection).await?;
+    let failed_jobs = database
+        .mark_stale_jobs_as_idle(&mut connection, max_job_attempts)
+        .await?;
+    for job_id in failed_jobs {
+        vlog::error!(
+            "Prover job {} was not completed after {} attempts and is marked as failed",
+            job_id,
+            max_job_attempts
+        );
+    }
+    let failed_jobs_count = database.failed_jobs_count(&mut connection).await?;
+    metrics::gauge!("prover.failed_jobs", failed_jobs_count as f64);
 
     Ok(())
 }
@@ -383,9 +621,9 @@ pub fn run_prover_server<DB: DatabaseInterface>(
     panic_notify: mpsc::Sender<bool>,
     config: ZkSyncConfig,
 ) {
-    let witness_generator_opts = config.prover.witness_generator;
-    let core_opts = config.prover.core;
-    let prover_api_opts = config.api.prover;
+    let witness_generator_opts = config.prover.witness_generator.clone();
+    let max_job_attempts = config.prover.core.max_job_attempts;
+    let prover_api_opts = config.api.prover.clone();
 
     thread::Builder::new()
         .name("prover_server".to_string())
@@ -394,7 +632,10 @@ pub fn run_prover_server<DB: DatabaseInterface>(
             let mut actix_runtime = actix_rt::System::new("prover-server");
 
             actix_runtime.block_on(async move {
-                tokio::spawn(update_prover_job_queue_loop(database.clone()));
+                tokio::spawn(update_prover_job_queue_loop(
+                    database.clone(),
+                    max_job_attempts,
+                ));
 
                 let last_verified_block = {
                     let mut storage = database
@@ -402,36 +643,29 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                         .await
                         .expect("Failed to access storage");
 
-                    *database
+                    database
                         .load_last_verified_block(&mut storage)
                         .await
                         .expect("Failed to get last verified block number")
-                        as usize
                 };
 
-                // Start pool maintainer threads.
-                for offset in 0..witness_generator_opts.witness_generators {
-                    let start_block = (last_verified_block + offset + 1) as u32;
-                    let block_step = witness_generator_opts.witness_generators as u32;
-                    vlog::info!(
-                        "Starting witness generator ({},{})",
-                        start_block,
-                        block_step
-                    );
-                    let pool_maintainer = witness_generator::WitnessGenerator::new(
-                        database.clone(),
-                        witness_generator_opts.prepare_data_interval(),
-                        BlockNumber(start_block),
-                        BlockNumber(block_step),
-                    );
-                    pool_maintainer.start(panic_notify.clone());
-                }
+                // Start pool maintainer thread.
+                let start_block = last_verified_block + 1;
+                vlog::info!(
+                    "Starting witness generator ({},{})",
+                    start_block,
+                    witness_generator_opts.witness_generators
+                );
+                let pool_maintainer = witness_generator::WitnessGenerator::new(
+                    database.clone(),
+                    witness_generator_opts.prepare_data_interval(),
+                    start_block,
+                    witness_generator_opts.witness_generators,
+                );
+                pool_maintainer.start(panic_notify.clone());
                 // Start HTTP server.
-                let secret_auth = prover_api_opts.secret_auth.clone();
-                let idle_provers = core_opts.idle_provers;
                 HttpServer::new(move || {
-                    let app_state =
-                        AppState::new(secret_auth.clone(), database.clone(), idle_provers);
+                    let app_state = AppState::new(database.clone(), &config);
 
                     let auth = HttpAuthentication::bearer(move |req, credentials| async {
                         let secret_auth = req
@@ -450,10 +684,12 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                         .wrap(auth)
                         .app_data(web::Data::new(app_state))
                         .route("/status", web::get().to(status))
+                        .route("/provers", web::get().to(provers::<DB>))
                         .route("/get_job", web::get().to(get_job::<DB>))
                         .route("/working_on", web::post().to(working_on::<DB>))
                         .route("/publish", web::post().to(publish::<DB>))
                         .route("/stopped", web::post().to(stopped::<DB>))
+                        .route("/requeue_job", web::post().to(requeue_job::<DB>))
                         .route(
                             "/api/internal/prover/replicas",
                             web::post().to(required_replicas::<DB>),
@@ -0,0 +1,37 @@
+//! Module with utilities for verifying the proofs submitted by the provers.
+
+// Workspace deps
+use zksync_prover_utils::aggregated_proofs::verify_aggregated_proof;
+use zksync_prover_utils::api::{JobRequestData, JobResultData};
+use zksync_prover_utils::verify_single_proof;
+
+/// Verifier of the proofs submitted by the provers.
+/// Used to reject invalid proofs before they are stored and sent to the L1.
+#[derive(Debug, Clone)]
+pub struct ProofVerifier {
+    /// Block chunk sizes supported by the circuit, used to find the verification keys
+    /// of the aggregated proofs.
+    block_chunks_sizes: Vec<usize>,
+}
+
+impl ProofVerifier {
+    pub fn new(block_chunks_sizes: Vec<usize>) -> Self {
+        Self { block_chunks_sizes }
+    }
+
+    /// Checks that the submitted proof is a valid proof for the given job.
+    /// Proofs of a different kind than the one requested by the job are considered invalid.
+    pub fn verify(&self, job: &JobRequestData, result: &JobResultData) -> anyhow::Result<bool> {
+        match (job, result) {
+            (
+                JobRequestData::BlockProof(prover_data, block_size),
+                JobResultData::BlockProof(proof),
+            ) => verify_single_proof(proof, prover_data.public_data_commitment, *block_size),
+            (
+                JobRequestData::AggregatedBlockProof(single_proofs),
+                JobResultData::AggregatedBlockProof(proof),
+            ) => verify_aggregated_proof(proof, single_proofs, &self.block_chunks_sizes),
+            _ => Ok(false),
+        }
+    }
+}
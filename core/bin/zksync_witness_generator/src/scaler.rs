@@ -1,7 +1,41 @@
 //! Module with utilities for prover scaler service.
 
+// Built-in deps
+use std::time::Duration;
+// External deps
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
 // Workspace deps
+use zksync_types::prover::ProverJobType;
+// Local deps
 use crate::database_interface::DatabaseInterface;
+
+/// Period of the completed jobs used to estimate the proof times.
+const PROOF_TIMES_PERIOD_HOURS: i64 = 24;
+
+/// Amount of provers required for the pending jobs of the same type and block chunk size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobsProversRequired {
+    pub job_type: String,
+    /// Block chunk size of the single block proof jobs.
+    pub block_size: Option<usize>,
+    pub pending_jobs: u32,
+    /// Age of the oldest pending job in seconds.
+    pub oldest_job_age: u64,
+    /// Average time to complete the job in ms, `None` if no jobs were completed recently.
+    pub average_proof_time: Option<u64>,
+    pub needed_count: u32,
+}
+
+/// Amount of provers required for the server to operate optimally.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProversRequired {
+    /// Total amount of provers, including the idle ones and limited by the maximum fleet size.
+    pub needed_count: u32,
+    /// Provers required for each kind of the pending jobs.
+    pub jobs: Vec<JobsProversRequired>,
+}
+
 /// Scaler oracle provides information for prover scaler
 /// service about required amount of provers for server
 /// to operate optimally.
@@ -12,23 +46,115 @@ pub struct ScalerOracle<DB: DatabaseInterface> {
 
     /// Number of idle provers running for faster up-scaling.
     idle_provers: u32,
+    /// Maximum number of provers running at once.
+    max_provers: u32,
+    /// Time given to prove a job since it was added to the queue.
+    block_prove_deadline: Duration,
 }
 
 impl<DB: DatabaseInterface> ScalerOracle<DB> {
-    pub fn new(db: DB, idle_provers: u32) -> Self {
-        Self { db, idle_provers }
+    pub fn new(
+        db: DB,
+        idle_provers: u32,
+        max_provers: u32,
+        block_prove_deadline: Duration,
+    ) -> Self {
+        Self {
+            db,
+            idle_provers,
+            max_provers,
+            block_prove_deadline,
+        }
     }
 
-    /// Decides how many prover entities should be created depending on the amount of pending blocks.
-    pub async fn provers_required(&mut self) -> anyhow::Result<u32> {
-        // Currently the logic of this method is very simple:
-        // We require a prover for each pending block or IDLE_RROVERS amount if there are not so many
-        // pending jobs.
-
+    /// Decides how many prover entities should be created depending on the pending jobs.
+    ///
+    /// Pending jobs of each type and block chunk size are expected to be proven before the deadline
+    /// of the oldest of them, given the recent proof times of such jobs. Single block proofs
+    /// additionally leave time for the aggregated proof to be created. Jobs without the recent
+    /// proof times are given a prover each.
+    pub async fn provers_required(&mut self) -> anyhow::Result<ProversRequired> {
         let mut storage = self.db.acquire_connection().await?;
-        let pending_jobs = self.db.pending_jobs_count(&mut storage).await?;
-        let provers_required = std::cmp::max(pending_jobs, self.idle_provers);
+        let pending_jobs = self.db.load_pending_jobs(&mut storage).await?;
+        let proof_times_since = Utc::now() - chrono::Duration::hours(PROOF_TIMES_PERIOD_HOURS);
+        let proof_times = self
+            .db
+            .load_average_proof_times(&mut storage, proof_times_since)
+            .await?;
+
+        let aggregated_job_type = ProverJobType::AggregatedProof.to_string();
+        let average_proof_time = |job_type: &str, block_size: Option<i64>| {
+            proof_times
+                .iter()
+                .find(|time| time.job_type == job_type && time.block_size == block_size)
+                .map(|time| Duration::from_millis(time.average_proof_time_ms.max(0) as u64))
+        };
+        let aggregated_proof_time = average_proof_time(&aggregated_job_type, None);
 
-        Ok(provers_required)
+        let now = Utc::now();
+        let jobs: Vec<_> = pending_jobs
+            .into_iter()
+            .map(|group| {
+                let oldest_job_age = (now - group.oldest_job_created_at)
+                    .to_std()
+                    .unwrap_or_default();
+                let proof_time = average_proof_time(&group.job_type, group.block_size);
+                let deadline = if group.job_type == aggregated_job_type {
+                    self.block_prove_deadline
+                } else {
+                    let aggregation_time = aggregated_proof_time.unwrap_or_default();
+                    self.block_prove_deadline
+                        .checked_sub(aggregation_time)
+                        .unwrap_or_default()
+                };
+                let pending_jobs = group.jobs_count as u32;
+
+                JobsProversRequired {
+                    needed_count: provers_for_jobs(
+                        pending_jobs,
+                        oldest_job_age,
+                        proof_time,
+                        deadline,
+                    ),
+                    job_type: group.job_type,
+                    block_size: group.block_size.map(|size| size as usize),
+                    pending_jobs,
+                    oldest_job_age: oldest_job_age.as_secs(),
+                    average_proof_time: proof_time.map(|time| time.as_millis() as u64),
+                }
+            })
+            .collect();
+
+        let needed_count = jobs
+            .iter()
+            .map(|jobs| jobs.needed_count)
+            .sum::<u32>()
+            .max(self.idle_provers)
+            .min(self.max_provers);
+
+        Ok(ProversRequired { needed_count, jobs })
+    }
+}
+
+/// Estimates the amount of provers required to prove the jobs of the same kind before the deadline
+/// of the oldest of them. Every job is given its own prover if the proof time is unknown, or if
+/// the deadline cannot be met anyway.
+pub fn provers_for_jobs(
+    jobs_count: u32,
+    oldest_job_age: Duration,
+    average_proof_time: Option<Duration>,
+    deadline: Duration,
+) -> u32 {
+    let average_proof_time = match average_proof_time {
+        Some(time) if jobs_count > 0 => time,
+        _ => return jobs_count,
+    };
+
+    let time_left = deadline.checked_sub(oldest_job_age).unwrap_or_default();
+    let jobs_per_prover = (time_left.as_millis() / average_proof_time.as_millis().max(1)) as u32;
+    if jobs_per_prover == 0 {
+        return jobs_count;
     }
+
+    (jobs_count + jobs_per_prover - 1) / jobs_per_prover
 }
@@ -0,0 +1,91 @@
+//! Module with utilities for matching the prover jobs with the prover capabilities.
+
+// Workspace deps
+use zksync_config::configs::chain::Circuit;
+use zksync_prover_utils::api::ProverCapabilities;
+use zksync_types::prover::ProverJobFilter;
+
+/// Setup requirements of the jobs given to the provers.
+/// Used to give the provers only the jobs they have setups for, and for which these setups fit into the
+/// prover RAM.
+#[derive(Debug, Clone)]
+pub struct SetupRequirements {
+    /// Block chunk sizes along with the setup powers needed to prove blocks of these sizes.
+    block_sizes_with_setup_pow: Vec<(usize, u32)>,
+    /// Aggregated proof sizes along with the setup powers needed to create these proofs.
+    aggregated_proof_sizes_with_setup_pow: Vec<(usize, u32)>,
+    /// Estimated amount of RAM in bytes required per constraint of the circuit setup.
+    ram_per_constraint: u64,
+}
+
+impl SetupRequirements {
+    pub fn new(circuit: &Circuit, ram_per_constraint: u64) -> Self {
+        let block_sizes_with_setup_pow = circuit
+            .supported_block_chunks_sizes
+            .iter()
+            .cloned()
+            .zip(
+                circuit
+                    .supported_block_chunks_sizes_setup_powers
+                    .iter()
+                    .map(|&power| power as u32),
+            )
+            .collect();
+
+        Self {
+            block_sizes_with_setup_pow,
+            aggregated_proof_sizes_with_setup_pow: circuit
+                .supported_aggregated_proof_sizes_with_setup_pow(),
+            ram_per_constraint,
+        }
+    }
+
+    /// Returns the filter of the jobs the prover with given capabilities is able to perform.
+    /// Provers which didn't advertise their capabilities are given any jobs.
+    pub fn job_filter(&self, capabilities: Option<&ProverCapabilities>) -> ProverJobFilter {
+        let capabilities = match capabilities {
+            Some(capabilities) => capabilities,
+            None => return ProverJobFilter::default(),
+        };
+
+        let max_setup_pow = capabilities
+            .available_ram
+            .map(|ram| self.max_setup_pow_for_ram(ram));
+        let setup_fits = |setup_pow: u32| max_setup_pow.map_or(true, |max| setup_pow <= max);
+
+        let block_sizes = self
+            .block_sizes_with_setup_pow
+            .iter()
+            .filter(|(size, setup_pow)| {
+                capabilities.block_sizes.contains(size) && setup_fits(*setup_pow)
+            })
+            .map(|(size, _)| *size)
+            .collect();
+        // The proofs are padded to the smallest supported aggregated proof size, so the prover is able
+        // to aggregate any amount of proofs not exceeding its largest aggregated proof size.
+        let max_aggregated_blocks = self
+            .aggregated_proof_sizes_with_setup_pow
+            .iter()
+            .filter(|(size, setup_pow)| {
+                capabilities.aggregated_proof_sizes.contains(size) && setup_fits(*setup_pow)
+            })
+            .map(|(size, _)| *size)
+            .max()
+            .unwrap_or(0);
+
+        ProverJobFilter {
+            block_sizes: Some(block_sizes),
+            max_aggregated_blocks: Some(max_aggregated_blocks),
+        }
+    }
+
+    /// Returns the largest setup power which fits into the given amount of RAM.
+    fn max_setup_pow_for_ram(&self, ram: u64) -> u32 {
+        let constraints = ram / self.ram_per_constraint.max(1);
+        if constraints == 0 {
+            0
+        } else {
+            63 - constraints.leading_zeros()
+        }
+    }
+}
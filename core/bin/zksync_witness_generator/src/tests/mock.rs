@@ -1,22 +1,27 @@
 // Built-in
 use std::clone::Clone;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 // External uses
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 use tokio::time::delay_for;
 // Workspace uses
 use zksync_crypto::params::account_tree_depth;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
+use zksync_prover_utils::api::ProverInputRequestAuxData;
 use zksync_storage::chain::block::records::AccountTreeCache;
-use zksync_storage::prover::records::{StorageBlockWitness, StorageProverJobQueue, StoredProof};
+use zksync_storage::prover::records::{
+    StorageBlockWitness, StoragePendingJobs, StorageProofTime, StorageProverInfo,
+    StorageProverJobQueue, StoredProof,
+};
 use zksync_storage::StorageProcessor;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobStatus, ProverJobType},
+    prover::{ProverJob, ProverJobFilter, ProverJobStatus, ProverJobType},
     AccountId, AccountMap, AccountTree, AccountUpdates, Address, BlockNumber,
 };
 // Local uses
@@ -26,6 +31,7 @@ use crate::DatabaseInterface;
 pub struct MockDatabase {
     /// Next free id and the prover job queue.
     prover_job_queue: Arc<RwLock<(i32, Vec<StorageProverJobQueue>)>>,
+    provers: Arc<RwLock<Vec<StorageProverInfo>>>,
     proofs: Arc<RwLock<Vec<StoredProof>>>,
     block_witness: Arc<RwLock<Vec<StorageBlockWitness>>>,
     blocks: Arc<RwLock<Vec<Block>>>,
@@ -40,6 +46,7 @@ impl MockDatabase {
 
         Self {
             prover_job_queue: Arc::new(RwLock::new((0, Vec::new()))),
+            provers: Arc::new(RwLock::new(Vec::new())),
             proofs: Arc::new(RwLock::new(Vec::new())),
             block_witness: Arc::new(RwLock::new(Vec::new())),
             blocks: Arc::new(RwLock::new(Vec::new())),
@@ -84,6 +91,7 @@ impl DatabaseInterface for MockDatabase {
         StorageProcessor::establish_connection().await
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn add_prover_job_to_job_queue(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -92,6 +100,7 @@ impl DatabaseInterface for MockDatabase {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        block_size: Option<usize>,
     ) -> anyhow::Result<()> {
         let mut prover_job_queue = self.prover_job_queue.write().await;
         let id = prover_job_queue.0;
@@ -108,6 +117,9 @@ impl DatabaseInterface for MockDatabase {
             id,
             job_priority,
             job_data,
+            attempts: 0,
+            block_size: block_size.map(|size| size as i64),
+            proof_time_ms: None,
         };
 
         prover_job_queue.1.push(new_job);
@@ -147,6 +159,140 @@ impl DatabaseInterface for MockDatabase {
         Ok(count as u32)
     }
 
+    async fn load_pending_jobs(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StoragePendingJobs>> {
+        let mut pending_jobs = BTreeMap::new();
+        for job in self.prover_job_queue.read().await.1.iter().filter(|job| {
+            job.job_status == ProverJobStatus::Idle.to_number()
+                || job.job_status == ProverJobStatus::InProgress.to_number()
+        }) {
+            let group = pending_jobs
+                .entry((job.job_type.clone(), job.block_size))
+                .or_insert((0, job.created_at));
+            group.0 += 1;
+            group.1 = group.1.min(job.created_at);
+        }
+
+        Ok(pending_jobs
+            .into_iter()
+            .map(
+                |((job_type, block_size), (jobs_count, oldest_job_created_at))| {
+                    StoragePendingJobs {
+                        job_type,
+                        block_size,
+                        jobs_count,
+                        oldest_job_created_at,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    async fn load_average_proof_times(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProofTime>> {
+        let mut proof_times = BTreeMap::new();
+        for job in self.prover_job_queue.read().await.1.iter().filter(|job| {
+            job.job_status == ProverJobStatus::Done.to_number() && job.updated_at >= since
+        }) {
+            if let Some(proof_time_ms) = job.proof_time_ms {
+                let group = proof_times
+                    .entry((job.job_type.clone(), job.block_size))
+                    .or_insert((0, 0));
+                group.0 += proof_time_ms;
+                group.1 += 1;
+            }
+        }
+
+        Ok(proof_times
+            .into_iter()
+            .map(
+                |((job_type, block_size), (total_proof_time_ms, jobs_count))| StorageProofTime {
+                    job_type,
+                    block_size,
+                    average_proof_time_ms: total_proof_time_ms / jobs_count,
+                },
+            )
+            .collect())
+    }
+
+    async fn record_prover_request(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        aux_data: &ProverInputRequestAuxData,
+    ) -> anyhow::Result<()> {
+        let provers = &mut self.provers.write().await;
+        let now = Utc::now();
+        let position = match provers.iter().position(|prover| prover.name == prover_name) {
+            Some(position) => position,
+            None => {
+                provers.push(StorageProverInfo {
+                    name: prover_name.to_string(),
+                    version: None,
+                    preferred_block_size: None,
+                    prefer_aggregated_proof: None,
+                    registered_at: now,
+                    last_heartbeat: now,
+                    stopped_at: None,
+                    current_job_id: None,
+                    job_started_at: None,
+                    jobs_completed: 0,
+                    jobs_failed: 0,
+                    total_proof_time_ms: 0,
+                    block_sizes: None,
+                    aggregated_proof_sizes: None,
+                    available_ram: None,
+                    invalid_proofs: 0,
+                });
+                provers.len() - 1
+            }
+        };
+
+        let prover = &mut provers[position];
+        prover.version = aux_data.version.clone();
+        prover.preferred_block_size = aux_data.preferred_block_size.map(|size| size as i64);
+        prover.prefer_aggregated_proof = aux_data.prefer_aggregated_proof;
+        let capabilities = aux_data.capabilities.as_ref();
+        let to_i64 = |sizes: &[usize]| sizes.iter().map(|&size| size as i64).collect();
+        prover.block_sizes = capabilities.map(|capabilities| to_i64(&capabilities.block_sizes));
+        prover.aggregated_proof_sizes =
+            capabilities.map(|capabilities| to_i64(&capabilities.aggregated_proof_sizes));
+        prover.available_ram = capabilities
+            .and_then(|capabilities| capabilities.available_ram)
+            .map(|ram| ram as i64);
+        prover.last_heartbeat = now;
+        prover.stopped_at = None;
+
+        Ok(())
+    }
+
+    async fn record_prover_job_assigned(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        job_id: i32,
+    ) -> anyhow::Result<()> {
+        let provers = &mut self.provers.write().await;
+        if let Some(prover) = provers.iter_mut().find(|prover| prover.name == prover_name) {
+            prover.current_job_id = Some(job_id);
+            prover.job_started_at = Some(Utc::now());
+        }
+
+        Ok(())
+    }
+
+    async fn load_provers(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverInfo>> {
+        Ok(self.provers.read().await.clone())
+    }
+
     async fn load_aggregated_op_that_affects_block(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -165,24 +311,131 @@ impl DatabaseInterface for MockDatabase {
         let single_proof = proofs
             .iter()
             .find(|proof| proof.block_number == *block_number as i64)
-            .map(|stored| serde_json::from_value(stored.proof.clone()).unwrap());
+            .map(|stored| serde_json::from_value(stored.proof.clone().unwrap()).unwrap());
 
         Ok(single_proof)
     }
 
-    async fn mark_stale_jobs_as_idle(&self, _: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    async fn mark_stale_jobs_as_idle(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        max_attempts: u32,
+    ) -> anyhow::Result<Vec<i32>> {
         let now = Utc::now();
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
+        let mut failed_jobs = Vec::new();
 
         for job in prover_job_queue.iter_mut() {
-            if now - job.updated_at > chrono::Duration::seconds(10) {
-                job.job_status = ProverJobStatus::Idle.to_number();
+            if job.job_status == ProverJobStatus::InProgress.to_number()
+                && now - job.updated_at > chrono::Duration::seconds(10)
+            {
                 job.updated_at = now;
-                job.updated_by = "server_clean_idle".to_string();
+                if job.attempts >= max_attempts as i32 {
+                    job.job_status = ProverJobStatus::Failed.to_number();
+                    job.updated_by = "server_fail_job".to_string();
+                    failed_jobs.push(job.id);
+                } else {
+                    job.job_status = ProverJobStatus::Idle.to_number();
+                    job.updated_by = "server_clean_idle".to_string();
+                }
             }
         }
 
-        Ok(())
+        Ok(failed_jobs)
+    }
+
+    async fn failed_jobs_count(&self, _: &mut StorageProcessor<'_>) -> anyhow::Result<u32> {
+        let count = self
+            .prover_job_queue
+            .read()
+            .await
+            .1
+            .iter()
+            .filter(|job| job.job_status == ProverJobStatus::Failed.to_number())
+            .count();
+
+        Ok(count as u32)
+    }
+
+    async fn load_prover_job(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        job_id: i32,
+    ) -> anyhow::Result<Option<ProverJob>> {
+        let job = self
+            .prover_job_queue
+            .read()
+            .await
+            .1
+            .iter()
+            .find(|job| job.id == job_id)
+            .map(|job| {
+                ProverJob::new(
+                    job.id,
+                    BlockNumber(job.first_block as u32),
+                    BlockNumber(job.last_block as u32),
+                    job.job_data.clone(),
+                    job.attempts,
+                    job.created_at,
+                )
+            });
+
+        Ok(job)
+    }
+
+    async fn record_invalid_proof(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        job_id: i32,
+        max_attempts: u32,
+    ) -> anyhow::Result<bool> {
+        let mut failed = false;
+        let prover_job_queue = &mut self.prover_job_queue.write().await.1;
+        if let Some(job) = prover_job_queue.iter_mut().find(|job| {
+            job.id == job_id && job.job_status == ProverJobStatus::InProgress.to_number()
+        }) {
+            job.updated_at = Utc::now();
+            job.updated_by = "server_reject_proof".to_string();
+            failed = job.attempts >= max_attempts as i32;
+            job.job_status = if failed {
+                ProverJobStatus::Failed.to_number()
+            } else {
+                ProverJobStatus::Idle.to_number()
+            };
+        }
+
+        let provers = &mut self.provers.write().await;
+        if let Some(prover) = provers
+            .iter_mut()
+            .find(|prover| prover.current_job_id == Some(job_id))
+        {
+            prover.jobs_failed += 1;
+            prover.invalid_proofs += 1;
+            prover.current_job_id = None;
+            prover.job_started_at = None;
+        }
+
+        Ok(failed)
+    }
+
+    async fn requeue_failed_job(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        job_id: i32,
+    ) -> anyhow::Result<bool> {
+        let prover_job_queue = &mut self.prover_job_queue.write().await.1;
+        if let Some(job) = prover_job_queue
+            .iter_mut()
+            .find(|job| job.id == job_id && job.job_status == ProverJobStatus::Failed.to_number())
+        {
+            job.updated_at = Utc::now();
+            job.updated_by = "server_requeue_job".to_string();
+            job.attempts = 0;
+            job.job_status = ProverJobStatus::Idle.to_number();
+            return Ok(true);
+        }
+
+        Ok(false)
     }
 
     async fn load_last_verified_block(
@@ -224,23 +477,40 @@ impl DatabaseInterface for MockDatabase {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         _: &mut StorageProcessor<'_>,
+        filter: &ProverJobFilter,
     ) -> anyhow::Result<Option<ProverJob>> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
         let idle_prover_job = prover_job_queue
             .iter_mut()
             .filter(|job| job.job_status == ProverJobStatus::Idle.to_number())
-            .max_by_key(|job| (job.job_priority, job.id));
+            .filter(|job| {
+                if job.job_type == ProverJobType::SingleProof.to_string() {
+                    match (&filter.block_sizes, job.block_size) {
+                        (Some(sizes), Some(size)) => sizes.contains(&(size as usize)),
+                        _ => true,
+                    }
+                } else {
+                    let blocks = (job.last_block - job.first_block + 1) as usize;
+                    filter
+                        .max_aggregated_blocks
+                        .map_or(true, |max_blocks| blocks <= max_blocks)
+                }
+            })
+            .min_by_key(|job| (job.job_priority, job.first_block, job.id));
 
         let prover_job = if let Some(job) = idle_prover_job {
             job.job_status = ProverJobStatus::InProgress.to_number();
             job.updated_at = Utc::now();
             job.updated_by = "server_give_job".to_string();
+            job.attempts += 1;
 
             Some(ProverJob::new(
                 job.id,
                 BlockNumber(job.first_block as u32),
                 BlockNumber(job.last_block as u32),
                 job.job_data.clone(),
+                job.attempts,
+                job.created_at,
             ))
         } else {
             None
@@ -281,10 +551,24 @@ impl DatabaseInterface for MockDatabase {
             job.job_status = ProverJobStatus::Done.to_number();
             job.updated_by = "server_finish_job".to_string();
         }
+        let provers = &mut self.provers.write().await;
+        if let Some(prover) = provers
+            .iter_mut()
+            .find(|prover| prover.current_job_id == Some(job_id))
+        {
+            let job = prover_job_queue.iter_mut().find(|job| job.id == job_id);
+            if let (Some(job), Some(started_at)) = (job, prover.job_started_at) {
+                job.proof_time_ms = Some((Utc::now() - started_at).num_milliseconds());
+            }
+            prover.jobs_completed += 1;
+            prover.current_job_id = None;
+            prover.job_started_at = None;
+        }
         let proof = StoredProof {
             block_number: i64::from(*block_number),
             created_at: Utc::now(),
-            proof: serde_json::to_value(proof).unwrap(),
+            proof: Some(serde_json::to_value(proof).unwrap()),
+            proof_ref: None,
         };
         self.proofs.write().await.push(proof);
 
@@ -369,11 +653,27 @@ impl DatabaseInterface for MockDatabase {
         let witness = block_witness
             .iter()
             .find(|witness| witness.block == *block_number as i64)
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness"));
+            .and_then(|w| w.witness.as_ref())
+            .map(|w| serde_json::from_str(w).expect("Failed to deserialize witness"));
 
         Ok(witness)
     }
 
+    async fn witness_exists(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<bool> {
+        let exists = self
+            .block_witness
+            .read()
+            .await
+            .iter()
+            .any(|witness| witness.block == *block_number as i64);
+
+        Ok(exists)
+    }
+
     async fn store_witness(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -390,7 +690,8 @@ impl DatabaseInterface for MockDatabase {
         if is_block_not_saved_yet {
             block_witness.push(StorageBlockWitness {
                 block: *block as i64,
-                witness: witness_str,
+                witness: Some(witness_str),
+                witness_ref: None,
             });
         }
 
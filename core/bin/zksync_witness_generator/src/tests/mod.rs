@@ -1,2 +1,4 @@
-mod mock;
+pub(crate) mod mock;
 mod prover_server;
+mod scaler;
+mod setup_requirements;
@@ -6,8 +6,9 @@ use num::BigUint;
 // Workspace deps
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFieldRepr};
+use zksync_crypto::proof::SingleProof;
 use zksync_prover::{client, ApiClient};
-use zksync_prover_utils::api::ProverInputRequest;
+use zksync_prover_utils::api::{JobResultData, ProverInputRequest, ProverOutputRequest};
 use zksync_types::{block::Block, AccountId, BlockNumber, TokenId, H256};
 // Local deps
 use super::mock::MockDatabase;
@@ -32,6 +33,7 @@ impl Default for MockProverOptions {
         zksync_config.prover.witness_generator.prepare_data_interval = 0;
         zksync_config.prover.witness_generator.witness_generators = 1;
         zksync_config.prover.core.idle_provers = 1;
+        zksync_config.prover.core.verify_proofs = true;
 
         MockProverOptions(zksync_config)
     }
@@ -143,6 +145,39 @@ async fn test_api_client_simple_simulation(prover_name: &str, database: MockData
         .await
         .unwrap();
     assert!(witness.is_some());
+
+    // Prover is registered once it requests a job.
+    let provers = database.load_provers(&mut storage).await.unwrap();
+    let prover = provers
+        .iter()
+        .find(|prover| prover.name == prover_name)
+        .expect("Prover is not registered");
+    assert_eq!(prover.current_job_id, Some(job.job_id));
+
+    // Invalid proof is rejected and counted for the prover.
+    client
+        .publish(ProverOutputRequest {
+            job_id: job.job_id,
+            first_block: job.first_block,
+            last_block: job.last_block,
+            data: JobResultData::BlockProof(SingleProof::default()),
+        })
+        .await
+        .unwrap();
+    let proof = database
+        .load_proof(&mut storage, BlockNumber(1))
+        .await
+        .unwrap();
+    assert!(proof.is_none());
+
+    let provers = database.load_provers(&mut storage).await.unwrap();
+    let prover = provers
+        .iter()
+        .find(|prover| prover.name == prover_name)
+        .expect("Prover is not registered");
+    assert_eq!(prover.invalid_proofs, 1);
+    assert_eq!(prover.jobs_failed, 1);
+    assert_eq!(prover.current_job_id, None);
 }
 
 pub async fn get_test_block() -> Block {
@@ -0,0 +1,39 @@
+// Built-in deps
+use std::time::Duration;
+// Local deps
+use crate::scaler::provers_for_jobs;
+
+const DEADLINE: Duration = Duration::from_secs(3600);
+const PROOF_TIME: Duration = Duration::from_secs(30);
+
+/// Checks that every job is given a prover if the proof time is unknown.
+#[test]
+fn unknown_proof_time() {
+    assert_eq!(provers_for_jobs(0, Duration::default(), None, DEADLINE), 0);
+    assert_eq!(provers_for_jobs(5, Duration::default(), None, DEADLINE), 5);
+}
+
+/// Checks that a small burst of fast jobs far from the deadline doesn't require many provers.
+#[test]
+fn fresh_jobs() {
+    let provers = provers_for_jobs(10, Duration::from_secs(60), Some(PROOF_TIME), DEADLINE);
+    assert_eq!(provers, 1);
+
+    let slow_proof_time = Duration::from_secs(1500);
+    let provers = provers_for_jobs(10, Duration::from_secs(60), Some(slow_proof_time), DEADLINE);
+    assert_eq!(provers, 5);
+}
+
+/// Checks that more provers are required as the oldest job approaches the deadline.
+#[test]
+fn jobs_close_to_deadline() {
+    // 100 seconds left, so a prover is able to complete 3 jobs.
+    let provers = provers_for_jobs(10, Duration::from_secs(3500), Some(PROOF_TIME), DEADLINE);
+    assert_eq!(provers, 4);
+
+    // The deadline cannot be met anymore.
+    let provers = provers_for_jobs(10, Duration::from_secs(3590), Some(PROOF_TIME), DEADLINE);
+    assert_eq!(provers, 10);
+    let provers = provers_for_jobs(10, Duration::from_secs(7200), Some(PROOF_TIME), DEADLINE);
+    assert_eq!(provers, 10);
+}
@@ -0,0 +1,99 @@
+// Workspace deps
+use zksync_config::configs::chain::Circuit;
+use zksync_prover_utils::api::ProverCapabilities;
+use zksync_types::prover::ProverJobFilter;
+// Local deps
+use crate::setup_requirements::SetupRequirements;
+
+/// Estimated RAM per constraint, so the setup of power `N` requires `2^N` KB.
+const RAM_PER_CONSTRAINT: u64 = 1024;
+
+fn setup_requirements() -> SetupRequirements {
+    let circuit = Circuit {
+        key_dir: Default::default(),
+        supported_block_chunks_sizes: vec![10, 32, 72],
+        supported_block_chunks_sizes_setup_powers: vec![21, 22, 23],
+        supported_aggregated_proof_sizes: vec![1, 4, 8],
+        supported_aggregated_proof_sizes_setup_power2: vec![22, 23, 24],
+        account_tree_depth: 32,
+        balance_tree_depth: 11,
+    };
+
+    SetupRequirements::new(&circuit, RAM_PER_CONSTRAINT)
+}
+
+/// Checks that the provers without capabilities are given any jobs.
+#[test]
+fn no_capabilities() {
+    let filter = setup_requirements().job_filter(None);
+    assert_eq!(filter, ProverJobFilter::default());
+}
+
+/// Checks that the prover is given only the jobs it has setups for.
+#[test]
+fn supported_sizes() {
+    let capabilities = ProverCapabilities {
+        block_sizes: vec![10, 72, 156],
+        aggregated_proof_sizes: vec![1, 4],
+        available_ram: None,
+    };
+
+    let filter = setup_requirements().job_filter(Some(&capabilities));
+    assert_eq!(
+        filter,
+        ProverJobFilter {
+            // Block size of 156 chunks isn't supported by the circuit.
+            block_sizes: Some(vec![10, 72]),
+            max_aggregated_blocks: Some(4),
+        }
+    );
+
+    let capabilities = ProverCapabilities {
+        block_sizes: vec![32],
+        aggregated_proof_sizes: Vec::new(),
+        available_ram: None,
+    };
+
+    let filter = setup_requirements().job_filter(Some(&capabilities));
+    assert_eq!(
+        filter,
+        ProverJobFilter {
+            block_sizes: Some(vec![32]),
+            max_aggregated_blocks: Some(0),
+        }
+    );
+}
+
+/// Checks that the prover is not given the jobs with setups not fitting into its RAM.
+#[test]
+fn available_ram() {
+    let capabilities = ProverCapabilities {
+        block_sizes: vec![10, 32, 72],
+        aggregated_proof_sizes: vec![1, 4, 8],
+        // Enough for the setup of power 22, but not for the setup of power 23.
+        available_ram: Some((1 << 22) * RAM_PER_CONSTRAINT + 1),
+    };
+
+    let filter = setup_requirements().job_filter(Some(&capabilities));
+    assert_eq!(
+        filter,
+        ProverJobFilter {
+            block_sizes: Some(vec![10, 32]),
+            max_aggregated_blocks: Some(1),
+        }
+    );
+
+    let capabilities = ProverCapabilities {
+        available_ram: Some(1024),
+        ..capabilities
+    };
+
+    let filter = setup_requirements().job_filter(Some(&capabilities));
+    assert_eq!(
+        filter,
+        ProverJobFilter {
+            block_sizes: Some(Vec::new()),
+            max_aggregated_blocks: Some(0),
+        }
+    );
+}
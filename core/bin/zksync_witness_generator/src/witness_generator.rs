@@ -1,8 +1,9 @@
 // Built-in
+use std::collections::BTreeSet;
 use std::{thread, time};
 // External
-use futures::channel::mpsc;
-use tokio::time::delay_for;
+use futures::{channel::mpsc, stream::FuturesUnordered, StreamExt};
+use tokio::{task::JoinHandle, time::delay_for};
 // Workspace deps
 use crate::database_interface::DatabaseInterface;
 use zksync_circuit::serialization::ProverData;
@@ -10,14 +11,15 @@ use zksync_circuit::witness::utils::build_block_witness;
 use zksync_crypto::circuit::CircuitAccountTree;
 use zksync_crypto::params::account_tree_depth;
 use zksync_types::block::Block;
-use zksync_types::BlockNumber;
+use zksync_types::helpers::apply_updates;
+use zksync_types::{AccountMap, BlockNumber};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 /// The essential part of this structure is `maintain` function
 /// which runs forever and adds data to the database.
 ///
-/// This will generate and store in db witnesses for blocks with indexes
-/// start_block, start_block + block_step, start_block + 2*block_step, ...
+/// This will generate and store in db witnesses for blocks starting from
+/// `start_block`, preparing up to `workers` blocks concurrently.
 pub struct WitnessGenerator<DB: DatabaseInterface> {
     /// Connection to the database.
     database: DB,
@@ -25,7 +27,8 @@ pub struct WitnessGenerator<DB: DatabaseInterface> {
     rounds_interval: time::Duration,
 
     start_block: BlockNumber,
-    block_step: BlockNumber,
+    /// Maximum amount of blocks to prepare witnesses for concurrently.
+    workers: usize,
 }
 
 enum BlockInfo {
@@ -34,19 +37,59 @@ enum BlockInfo {
     NoWitness(Block),
 }
 
+/// State of the accounts after the block, shared by the witness generator workers.
+/// The state is advanced block by block with the state diffs, and every worker is given
+/// a copy of the account tree as it was before the block it prepares the witness for.
+struct AccountTreeState {
+    block: BlockNumber,
+    accounts: AccountMap,
+    tree: CircuitAccountTree,
+    /// Last block for which the account tree cache is stored.
+    cached_block: BlockNumber,
+}
+
+/// Witness generator workers preparing the witnesses concurrently.
+#[derive(Default)]
+struct WorkerPool {
+    /// Blocks the witnesses are being prepared for.
+    in_progress: BTreeSet<BlockNumber>,
+    handles: FuturesUnordered<JoinHandle<(BlockNumber, anyhow::Result<()>)>>,
+}
+
+impl WorkerPool {
+    /// Handles the result of the worker. The block the worker failed to prepare the witness for
+    /// is retried, so `next_block` is moved back to it.
+    fn complete(
+        &mut self,
+        block_number: BlockNumber,
+        result: anyhow::Result<()>,
+        next_block: &mut BlockNumber,
+    ) {
+        self.in_progress.remove(&block_number);
+        if let Err(err) = result {
+            vlog::warn!(
+                "Witness generator failed to prepare witness for block: {}, err: {}",
+                block_number,
+                err
+            );
+            *next_block = (*next_block).min(block_number);
+        }
+    }
+}
+
 impl<DB: DatabaseInterface> WitnessGenerator<DB> {
     /// Creates a new `WitnessGenerator` object.
     pub fn new(
         database: DB,
         rounds_interval: time::Duration,
         start_block: BlockNumber,
-        block_step: BlockNumber,
+        workers: usize,
     ) -> Self {
         Self {
             database,
             rounds_interval,
             start_block,
-            block_step,
+            workers: workers.max(1),
         }
     }
 
@@ -81,14 +124,14 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
             .load_block(&mut transaction, block_number)
             .await?;
         let block_info = if let Some(block) = block {
-            let witness = self
+            let witness_exists = self
                 .database
-                .load_witness(&mut transaction, block_number)
+                .witness_exists(&mut transaction, block_number)
                 .await?;
-            if witness.is_none() {
-                BlockInfo::NoWitness(block)
-            } else {
+            if witness_exists {
                 BlockInfo::WithWitness
+            } else {
+                BlockInfo::NoWitness(block)
             }
         } else {
             BlockInfo::NotReadyBlock
@@ -97,30 +140,24 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         Ok(block_info)
     }
 
-    async fn load_account_tree(
-        &self,
-        block: BlockNumber,
-    ) -> Result<CircuitAccountTree, anyhow::Error> {
+    /// Restores the account tree for the given block from the cache.
+    async fn load_account_tree(&self, block: BlockNumber) -> anyhow::Result<AccountTreeState> {
         let start = time::Instant::now();
         let mut storage = self.database.acquire_connection().await?;
         let mut circuit_account_tree = CircuitAccountTree::new(account_tree_depth());
 
-        if let Some((cached_block, account_tree_cache)) =
+        let (_, accounts) = self
+            .database
+            .load_committed_state(&mut storage, Some(block))
+            .await?;
+        for (id, account) in accounts.iter() {
+            circuit_account_tree.insert(**id, account.clone().into());
+        }
+        let cached_block = if let Some((cached_block, account_tree_cache)) =
             self.database.load_account_tree_cache(&mut storage).await?
         {
-            let (_, accounts) = self
-                .database
-                .load_committed_state(&mut storage, Some(block))
-                .await?;
-            for (id, account) in accounts {
-                circuit_account_tree.insert(*id, account.into());
-            }
             circuit_account_tree.set_internals(serde_json::from_value(account_tree_cache)?);
             if block != cached_block {
-                let (_, accounts) = self
-                    .database
-                    .load_committed_state(&mut storage, Some(block))
-                    .await?;
                 if let Some((_, account_updates)) = self
                     .database
                     .load_state_diff(&mut storage, block, Some(cached_block))
@@ -137,74 +174,127 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
                             .insert(*idx, accounts.get(&idx).cloned().unwrap_or_default().into());
                     }
                 }
-                circuit_account_tree.root_hash();
-                let account_tree_cache = circuit_account_tree.get_internals();
-                self.database
-                    .store_account_tree_cache(
-                        &mut storage,
-                        block,
-                        serde_json::to_value(account_tree_cache)?,
-                    )
-                    .await?;
             }
+            cached_block
         } else {
-            let (_, accounts) = self
-                .database
-                .load_committed_state(&mut storage, Some(block))
-                .await?;
-            for (id, account) in accounts {
-                circuit_account_tree.insert(*id, account.into());
+            BlockNumber(0)
+        };
+
+        let mut state = AccountTreeState {
+            block,
+            accounts,
+            tree: circuit_account_tree,
+            cached_block,
+        };
+        self.check_root_hash(&state).await?;
+        self.store_account_tree_cache(&mut state).await?;
+
+        metrics::histogram!("witness_generator.load_account_tree", start.elapsed());
+        Ok(state)
+    }
+
+    /// Advances the account tree to the state after the given block.
+    async fn advance_account_tree(
+        &self,
+        state: &mut AccountTreeState,
+        block: BlockNumber,
+    ) -> anyhow::Result<()> {
+        if block == state.block {
+            return Ok(());
+        }
+        if block < state.block {
+            *state = self.load_account_tree(block).await?;
+            return Ok(());
+        }
+
+        let start = time::Instant::now();
+        let mut storage = self.database.acquire_connection().await?;
+        if let Some((_, account_updates)) = self
+            .database
+            .load_state_diff(&mut storage, state.block, Some(block))
+            .await?
+        {
+            let mut updated_accounts = account_updates
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            updated_accounts.sort_unstable();
+            updated_accounts.dedup();
+            apply_updates(&mut state.accounts, account_updates);
+            for idx in updated_accounts {
+                state.tree.insert(
+                    *idx,
+                    state.accounts.get(&idx).cloned().unwrap_or_default().into(),
+                );
             }
-            circuit_account_tree.root_hash();
-            let account_tree_cache = circuit_account_tree.get_internals();
-            self.database
-                .store_account_tree_cache(
-                    &mut storage,
-                    block,
-                    serde_json::to_value(account_tree_cache)?,
-                )
-                .await?;
         }
+        state.block = block;
+        self.check_root_hash(state).await?;
+
+        metrics::histogram!("witness_generator.advance_account_tree", start.elapsed());
+        Ok(())
+    }
 
-        if block != BlockNumber(0) {
+    async fn check_root_hash(&self, state: &AccountTreeState) -> anyhow::Result<()> {
+        if state.block != BlockNumber(0) {
+            let mut storage = self.database.acquire_connection().await?;
             let storage_block = self
                 .database
-                .load_block(&mut storage, block)
+                .load_block(&mut storage, state.block)
                 .await?
                 .expect("Block for witness generator must exist");
             assert_eq!(
                 storage_block.new_root_hash,
-                circuit_account_tree.root_hash(),
+                state.tree.root_hash(),
                 "account tree root hash restored incorrectly"
             );
         }
-
-        metrics::histogram!("witness_generator.load_account_tree", start.elapsed());
-        Ok(circuit_account_tree)
+        Ok(())
     }
 
-    async fn prepare_witness_and_save_it(&self, block: Block) -> anyhow::Result<()> {
-        let start = time::Instant::now();
-        let timer = time::Instant::now();
+    /// Stores the account tree cache unless it's already stored for the current block.
+    async fn store_account_tree_cache(&self, state: &mut AccountTreeState) -> anyhow::Result<()> {
+        if state.block <= state.cached_block {
+            return Ok(());
+        }
+
         let mut storage = self.database.acquire_connection().await?;
+        let account_tree_cache = state.tree.get_internals();
+        self.database
+            .store_account_tree_cache(
+                &mut storage,
+                state.block,
+                serde_json::to_value(account_tree_cache)?,
+            )
+            .await?;
+        state.cached_block = state.block;
 
-        let mut circuit_account_tree = self.load_account_tree(block.block_number - 1).await?;
-        vlog::trace!(
-            "Witness generator loading circuit account tree {}s",
-            timer.elapsed().as_secs()
-        );
+        Ok(())
+    }
+
+    async fn prepare_witness_and_save_it(
+        database: DB,
+        block: Block,
+        mut circuit_account_tree: CircuitAccountTree,
+    ) -> anyhow::Result<()> {
+        let start = time::Instant::now();
+        let block_number = block.block_number;
 
         let timer = time::Instant::now();
-        let witness: ProverData = build_block_witness(&mut circuit_account_tree, &block)?.into();
+        let witness: ProverData = tokio::task::spawn_blocking(move || {
+            build_block_witness(&mut circuit_account_tree, &block).map(ProverData::from)
+        })
+        .await??;
         vlog::trace!(
             "Witness generator witness build {}s",
             timer.elapsed().as_secs()
         );
 
-        self.database
+        let mut storage = database.acquire_connection().await?;
+        database
             .store_witness(
                 &mut storage,
-                block.block_number,
+                block_number,
                 serde_json::to_value(witness).expect("Witness serialize to json"),
             )
             .await?;
@@ -216,51 +306,95 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         Ok(())
     }
 
-    /// Returns next block for generating witness
-    fn next_witness_block(
-        current_block: BlockNumber,
-        block_step: BlockNumber,
-        block_info: &BlockInfo,
-    ) -> BlockNumber {
-        match block_info {
-            BlockInfo::NotReadyBlock => current_block, // Keep waiting
-            BlockInfo::WithWitness | BlockInfo::NoWitness(_) => {
-                BlockNumber(*current_block + *block_step)
-            } // Go to the next block
+    /// Gives the workers the blocks without witnesses, starting from `next_block`, until all
+    /// the workers are busy or there are no blocks ready.
+    async fn give_blocks_to_workers(
+        &self,
+        state: &mut Option<AccountTreeState>,
+        next_block: &mut BlockNumber,
+        workers: &mut WorkerPool,
+    ) -> anyhow::Result<()> {
+        while workers.in_progress.len() < self.workers {
+            if workers.in_progress.contains(next_block) {
+                *next_block = *next_block + 1;
+                continue;
+            }
+            let block = match self.should_work_on_block(*next_block).await? {
+                BlockInfo::NotReadyBlock => break,
+                BlockInfo::WithWitness => {
+                    *next_block = *next_block + 1;
+                    continue;
+                }
+                BlockInfo::NoWitness(block) => block,
+            };
+
+            let previous_block = block.block_number - 1;
+            match state {
+                Some(state) => self.advance_account_tree(state, previous_block).await?,
+                None => *state = Some(self.load_account_tree(previous_block).await?),
+            }
+            let circuit_account_tree = state
+                .as_ref()
+                .expect("Account tree state is initialized")
+                .tree
+                .clone();
+
+            let block_number = block.block_number;
+            let database = self.database.clone();
+            workers.in_progress.insert(block_number);
+            workers.handles.push(tokio::spawn(async move {
+                let result =
+                    Self::prepare_witness_and_save_it(database, block, circuit_account_tree).await;
+                (block_number, result)
+            }));
+            *next_block = *next_block + 1;
+        }
+
+        if let Some(state) = state {
+            self.store_account_tree_cache(state).await?;
         }
+        Ok(())
     }
 
     /// Updates witness data in database in an infinite loop,
     /// awaiting `rounds_interval` time between updates.
     async fn maintain(self) {
         vlog::info!(
-            "preparing prover data routine started with start_block({}), block_step({})",
+            "preparing prover data routine started with start_block({}), workers({})",
             *self.start_block,
-            *self.block_step
+            self.workers
         );
-        let mut current_block = self.start_block;
+        let mut next_block = self.start_block;
+        let mut state = None;
+        let mut workers = WorkerPool::default();
         loop {
-            delay_for(self.rounds_interval).await;
-            let should_work = match self.should_work_on_block(current_block).await {
-                Ok(should_work) => should_work,
-                Err(err) => {
-                    vlog::warn!("witness for block {} check failed: {}", current_block, err);
-                    continue;
-                }
-            };
-
-            let next_block = Self::next_witness_block(current_block, self.block_step, &should_work);
-            if let BlockInfo::NoWitness(block) = should_work {
-                let block_number = block.block_number;
-                if let Err(err) = self.prepare_witness_and_save_it(block).await {
-                    vlog::warn!("Witness generator ({},{}) failed to prepare witness for block: {}, err: {}",
-                        self.start_block, self.block_step, block_number, err);
-                    continue; // Retry the same block on the next iteration.
-                }
+            if let Err(err) = self
+                .give_blocks_to_workers(&mut state, &mut next_block, &mut workers)
+                .await
+            {
+                vlog::warn!("witness for block {} check failed: {}", next_block, err);
+                // The account tree may be partially updated, so it's restored from scratch.
+                state = None;
             }
+            metrics::gauge!(
+                "witness_generator.active_workers",
+                workers.in_progress.len() as f64
+            );
 
-            // Update current block.
-            current_block = next_block;
+            // Wait for any of the workers to complete, but not longer than the refresh interval.
+            let completed = if workers.in_progress.is_empty() {
+                delay_for(self.rounds_interval).await;
+                None
+            } else {
+                tokio::time::timeout(self.rounds_interval, workers.handles.next())
+                    .await
+                    .ok()
+                    .flatten()
+            };
+            if let Some(completed) = completed {
+                let (block_number, result) = completed.expect("Witness generator worker panicked");
+                workers.complete(block_number, result, &mut next_block);
+            }
         }
     }
 }
@@ -268,47 +402,171 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::database::Database;
-    use zksync_crypto::Fr;
-    use zksync_types::{AccountId, H256, U256};
-
-    #[test]
-    fn test_next_witness_block() {
-        assert_eq!(
-            WitnessGenerator::<Database>::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::NotReadyBlock
-            ),
-            BlockNumber(3)
-        );
-        assert_eq!(
-            WitnessGenerator::<Database>::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::WithWitness
-            ),
-            BlockNumber(7)
-        );
-        let empty_block = Block::new(
-            BlockNumber(0),
-            Fr::default(),
+    use crate::tests::mock::MockDatabase;
+    use anyhow::format_err;
+    use zksync_types::{Account, AccountId, Address, H256, U256};
+
+    fn get_empty_block(block_number: u32) -> Block {
+        let (tree, _) = MockDatabase::get_default_tree_and_accounts();
+        Block::new(
+            BlockNumber(block_number),
+            tree.root_hash(),
             AccountId(0),
             vec![],
             (0, 0),
-            0,
+            6,
             U256::default(),
             U256::default(),
             H256::default(),
             0,
+        )
+    }
+
+    /// Creates the witness generator for the database with `blocks_count` blocks without witnesses.
+    async fn get_witness_generator(
+        blocks_count: u32,
+        workers: usize,
+    ) -> WitnessGenerator<MockDatabase> {
+        let database = MockDatabase::new();
+        for block_number in 1..=blocks_count {
+            database.add_block(get_empty_block(block_number)).await;
+        }
+
+        WitnessGenerator::new(
+            database,
+            time::Duration::from_millis(10),
+            BlockNumber(1),
+            workers,
+        )
+    }
+
+    /// Checks that the blocks with witnesses are skipped, and the blocks are not given
+    /// to the workers until they are ready.
+    #[tokio::test]
+    async fn test_next_witness_block() {
+        let witness_generator = get_witness_generator(3, 4).await;
+        let mut storage = witness_generator
+            .database
+            .acquire_connection()
+            .await
+            .unwrap();
+        witness_generator
+            .database
+            .store_witness(&mut storage, BlockNumber(1), serde_json::Value::Null)
+            .await
+            .unwrap();
+
+        let mut state = None;
+        let mut next_block = BlockNumber(1);
+        let mut workers = WorkerPool::default();
+        witness_generator
+            .give_blocks_to_workers(&mut state, &mut next_block, &mut workers)
+            .await
+            .unwrap();
+
+        let in_progress: Vec<_> = workers.in_progress.iter().copied().collect();
+        assert_eq!(in_progress, vec![BlockNumber(2), BlockNumber(3)]);
+        assert_eq!(workers.handles.len(), 2);
+        // Block 4 is not ready yet, so it's the next one to check.
+        assert_eq!(next_block, BlockNumber(4));
+        assert_eq!(state.unwrap().block, BlockNumber(2));
+    }
+
+    /// Checks that no more than `workers` blocks are prepared concurrently.
+    #[tokio::test]
+    async fn test_concurrent_blocks_dispatch() {
+        let witness_generator = get_witness_generator(5, 2).await;
+
+        let mut state = None;
+        let mut next_block = BlockNumber(1);
+        let mut workers = WorkerPool::default();
+        witness_generator
+            .give_blocks_to_workers(&mut state, &mut next_block, &mut workers)
+            .await
+            .unwrap();
+
+        let in_progress: Vec<_> = workers.in_progress.iter().copied().collect();
+        assert_eq!(in_progress, vec![BlockNumber(1), BlockNumber(2)]);
+        assert_eq!(workers.handles.len(), 2);
+        assert_eq!(next_block, BlockNumber(3));
+
+        // All the workers are busy, so no block is given out.
+        witness_generator
+            .give_blocks_to_workers(&mut state, &mut next_block, &mut workers)
+            .await
+            .unwrap();
+        assert_eq!(workers.in_progress.len(), 2);
+        assert_eq!(next_block, BlockNumber(3));
+
+        // Once the worker completes, the next block is given out.
+        workers.complete(BlockNumber(1), Ok(()), &mut next_block);
+        witness_generator
+            .give_blocks_to_workers(&mut state, &mut next_block, &mut workers)
+            .await
+            .unwrap();
+
+        let in_progress: Vec<_> = workers.in_progress.iter().copied().collect();
+        assert_eq!(in_progress, vec![BlockNumber(2), BlockNumber(3)]);
+        assert_eq!(next_block, BlockNumber(4));
+        assert_eq!(state.unwrap().block, BlockNumber(2));
+    }
+
+    /// Checks that the block the worker failed to prepare the witness for is given out again.
+    #[tokio::test]
+    async fn test_failed_block_redispatch() {
+        let witness_generator = get_witness_generator(5, 2).await;
+
+        let mut state = None;
+        let mut next_block = BlockNumber(3);
+        let mut workers = WorkerPool::default();
+        workers.in_progress.insert(BlockNumber(1));
+        workers.in_progress.insert(BlockNumber(2));
+
+        workers.complete(
+            BlockNumber(1),
+            Err(format_err!("witness build failed")),
+            &mut next_block,
         );
-        assert_eq!(
-            WitnessGenerator::<Database>::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::NoWitness(empty_block)
-            ),
-            BlockNumber(7)
-        );
+        assert_eq!(next_block, BlockNumber(1));
+        assert!(!workers.in_progress.contains(&BlockNumber(1)));
+
+        // The failed block is given out again, while the block still in progress is not.
+        witness_generator
+            .give_blocks_to_workers(&mut state, &mut next_block, &mut workers)
+            .await
+            .unwrap();
+
+        let in_progress: Vec<_> = workers.in_progress.iter().copied().collect();
+        assert_eq!(in_progress, vec![BlockNumber(1), BlockNumber(2)]);
+        assert_eq!(workers.handles.len(), 1);
+        assert_eq!(next_block, BlockNumber(2));
+        assert_eq!(state.unwrap().block, BlockNumber(0));
+    }
+
+    /// Checks that the shared account tree is restored when the block before the current state is requested.
+    #[tokio::test]
+    async fn test_account_tree_rewind() {
+        let witness_generator = get_witness_generator(3, 1).await;
+
+        let mut state = witness_generator
+            .load_account_tree(BlockNumber(3))
+            .await
+            .unwrap();
+        let root_hash = state.tree.root_hash();
+        assert_eq!(state.block, BlockNumber(3));
+
+        // Make the state diverge from the stored one to check that it's restored from scratch.
+        let account = Account::default_with_address(&Address::repeat_byte(1));
+        state.accounts.insert(AccountId(1), account.clone());
+        state.tree.insert(1, account.into());
+        assert_ne!(state.tree.root_hash(), root_hash);
+
+        witness_generator
+            .advance_account_tree(&mut state, BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(state.block, BlockNumber(1));
+        assert_eq!(state.tree.root_hash(), root_hash);
+        assert!(!state.accounts.contains_key(&AccountId(1)));
     }
 }
@@ -1,6 +1,7 @@
 //! First stable API implementation client.
 
 // External uses
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
@@ -16,8 +17,8 @@ pub use self::{
     search::BlockSearchQuery,
     tokens::{TokenPriceKind, TokenPriceQuery},
     transactions::{
-        FastProcessingQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee,
-        Receipt, TxData,
+        FastProcessingQuery, FeeHistoryQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee,
+        IncomingTxForFee, Receipt, TxData,
     },
 };
 
@@ -35,6 +36,16 @@ mod transactions;
 /// Maximum limit value in the requests.
 pub const MAX_LIMIT: u32 = 100;
 
+/// Time range of the history requests: `?limit=..&[from={time}]&[to={time}]`.
+///
+/// Both bounds are inclusive, the latest entries are returned first.
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+pub struct HistoryQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: u32,
+}
+
 /// Internal pagination query representation in according to spec:
 ///
 /// `?limit=..&[before={id}|after={id}]` where:
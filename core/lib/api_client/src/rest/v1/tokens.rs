@@ -7,10 +7,16 @@ use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_types::{Token, TokenLike};
+use zksync_types::{
+    tokens::{FeeTokenOverride, FeeTokenOverrideChange},
+    Token, TokenLike, TokenPrice,
+};
 
 // Local uses
-use super::client::{self, Client};
+use super::{
+    client::{self, Client},
+    HistoryQuery,
+};
 
 // Data transfer objects.
 
@@ -48,4 +54,34 @@ impl Client {
             .send()
             .await
     }
+
+    /// Returns the token prices received by the fee ticker, latest prices first.
+    pub async fn token_price_history(
+        &self,
+        token: &TokenLike,
+        query: HistoryQuery,
+    ) -> client::Result<Vec<TokenPrice>> {
+        self.get(&format!("tokens/{}/price/history", token))
+            .query(&query)
+            .send()
+            .await
+    }
+
+    pub async fn fee_token_override(
+        &self,
+        token: &TokenLike,
+    ) -> client::Result<Option<FeeTokenOverride>> {
+        self.get(&format!("tokens/{}/fee_override", token))
+            .send()
+            .await
+    }
+
+    pub async fn fee_token_override_history(
+        &self,
+        token: &TokenLike,
+    ) -> client::Result<Vec<FeeTokenOverrideChange>> {
+        self.get(&format!("tokens/{}/fee_override/history", token))
+            .send()
+            .await
+    }
 }
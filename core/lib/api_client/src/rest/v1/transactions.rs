@@ -3,16 +3,18 @@
 // Built-in uses
 
 // External uses
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
 use zksync_types::{
     tx::{EthBatchSignatures, EthSignData, TxEthSignature, TxHash},
-    Address, BatchFee, BlockNumber, Fee, SignedZkSyncTx, TokenLike, TxFeeTypes, ZkSyncTx,
+    Address, BatchFee, BlockNumber, Fee, FeeHistoryEntry, SignedZkSyncTx, TokenLike, TxFeeTypes,
+    ZkSyncTx,
 };
 
 // Local uses
-use super::{client::Client, client::ClientError, Pagination};
+use super::{client::Client, client::ClientError, HistoryQuery, Pagination};
 
 // Data transfer objects.
 
@@ -60,6 +62,29 @@ pub struct IncomingTxBatchForFee {
     pub tx_types: Vec<TxFeeTypes>,
     pub addresses: Vec<Address>,
     pub token_like: TokenLike,
+    /// Tokens of the batch transactions, used to suggest the cheapest way to pay the batch fee.
+    #[serde(default)]
+    pub tx_tokens: Vec<TokenLike>,
+}
+
+/// Fee history request: `?token={token}&limit=..&[from={time}]&[to={time}]`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FeeHistoryQuery {
+    pub token: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: u32,
+}
+
+impl FeeHistoryQuery {
+    pub fn new(token: &TokenLike, query: HistoryQuery) -> Self {
+        Self {
+            token: token.to_string(),
+            from: query.from,
+            to: query.to,
+            limit: query.limit,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -143,17 +168,43 @@ impl Client {
         tx_types: Vec<TxFeeTypes>,
         addresses: Vec<Address>,
         token_like: TokenLike,
+    ) -> Result<BatchFee, ClientError> {
+        self.get_batched_txs_fee_details(tx_types, addresses, token_like, Vec::new())
+            .await
+    }
+
+    /// Get txs fee for batch along with the options to pay it in the `tx_tokens`,
+    /// which are the tokens of the batch transactions, the cheapest option first.
+    pub async fn get_batched_txs_fee_details(
+        &self,
+        tx_types: Vec<TxFeeTypes>,
+        addresses: Vec<Address>,
+        token_like: TokenLike,
+        tx_tokens: Vec<TokenLike>,
     ) -> Result<BatchFee, ClientError> {
         self.post("transactions/fee/batch")
             .body(&IncomingTxBatchForFee {
                 tx_types,
                 addresses,
                 token_like,
+                tx_tokens,
             })
             .send()
             .await
     }
 
+    /// Returns the fees in the given token sampled by the fee ticker, latest fees first.
+    pub async fn fee_history(
+        &self,
+        token: &TokenLike,
+        query: HistoryQuery,
+    ) -> Result<Vec<FeeHistoryEntry>, ClientError> {
+        self.get("transactions/fee/history")
+            .query(&FeeHistoryQuery::new(token, query))
+            .send()
+            .await
+    }
+
     /// Sends a new transactions batch to the memory pool.
     pub async fn submit_tx_batch(
         &self,
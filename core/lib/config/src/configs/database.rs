@@ -18,6 +18,9 @@ pub struct DBConfig {
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// Directory of the store keeping the witnesses and proofs outside of the database.
+    /// If not set, they are stored in the database itself.
+    pub artifacts_dir: Option<String>,
 }
 
 impl DBConfig {
@@ -47,6 +50,7 @@ mod tests {
             url: "postgres://postgres@localhost/plasma".into(),
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            artifacts_dir: Some("artifacts".into()),
         }
     }
 
@@ -57,6 +61,7 @@ DATABASE_POOL_SIZE="10"
 DATABASE_URL="postgres://postgres@localhost/plasma"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_ARTIFACTS_DIR="artifacts"
         "#;
         set_env(config);
 
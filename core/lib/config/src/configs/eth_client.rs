@@ -1,3 +1,5 @@
+// Built-in uses
+use std::time::Duration;
 // External uses
 use serde::Deserialize;
 // Local uses
@@ -14,6 +16,28 @@ pub struct ETHClientConfig {
     pub gas_price_factor: f64,
     /// Address of the Ethereum node API.
     pub web3_url: Vec<String>,
+    /// Strategy of choosing the Ethereum node to send the request to, if several nodes are configured.
+    pub multiplexer_strategy: MultiplexerStrategy,
+    /// Amount of consecutive failed requests after which the node is not used until the cooldown expires.
+    /// Used only with the `HealthScored` strategy.
+    pub circuit_breaker_threshold: u32,
+    /// Cooldown of the node with the opened circuit breaker in seconds.
+    pub circuit_breaker_cooldown: u64,
+    /// Amount of nodes that must agree on the result of the critical reads (`block_number`, `logs` and
+    /// `get_tx_status`). `1` means that the first successful response is used.
+    pub read_quorum: usize,
+    /// Whether the signed transactions should be sent to all the configured nodes.
+    pub broadcast_txs: bool,
+}
+
+/// Strategy of choosing the Ethereum node to send the request to.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum MultiplexerStrategy {
+    /// Nodes are tried in the order they are configured.
+    Ordered,
+    /// Nodes are tried starting from the healthiest one (with the highest rate of recent
+    /// successful requests). Nodes failing repeatedly are skipped until the cooldown expires.
+    HealthScored,
 }
 
 impl ETHClientConfig {
@@ -28,6 +52,11 @@ impl ETHClientConfig {
             .cloned()
             .expect("Should be at least one")
     }
+
+    /// Converts `self.circuit_breaker_cooldown` into `Duration`.
+    pub fn circuit_breaker_cooldown(&self) -> Duration {
+        Duration::from_secs(self.circuit_breaker_cooldown)
+    }
 }
 
 #[cfg(test)]
@@ -43,6 +72,11 @@ mod tests {
                 "http://127.0.0.1:8545".into(),
                 "http://127.0.0.1:8546".into(),
             ],
+            multiplexer_strategy: MultiplexerStrategy::HealthScored,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown: 30,
+            read_quorum: 2,
+            broadcast_txs: true,
         }
     }
 
@@ -52,11 +86,17 @@ mod tests {
 ETH_CLIENT_CHAIN_ID="9"
 ETH_CLIENT_GAS_PRICE_FACTOR="1"
 ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545,http://127.0.0.1:8546"
+ETH_CLIENT_MULTIPLEXER_STRATEGY="HealthScored"
+ETH_CLIENT_CIRCUIT_BREAKER_THRESHOLD="3"
+ETH_CLIENT_CIRCUIT_BREAKER_COOLDOWN="30"
+ETH_CLIENT_READ_QUORUM="2"
+ETH_CLIENT_BROADCAST_TXS="true"
         "#;
         set_env(config);
 
         let actual = ETHClientConfig::from_env();
         assert_eq!(actual, expected_config());
         assert_eq!(actual.web3_url(), "http://127.0.0.1:8545");
+        assert_eq!(actual.circuit_breaker_cooldown(), Duration::from_secs(30));
     }
 }
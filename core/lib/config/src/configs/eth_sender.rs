@@ -28,10 +28,19 @@ impl ETHSenderConfig {
     }
 }
 
+/// Source of the signatures for the operator transactions.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum OperatorSignerType {
+    /// Transactions are signed with `operator_private_key`.
+    PrivateKey,
+    /// Transactions are signed by the remote signing service at `remote_signer_url`.
+    Remote,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Sender {
-    /// Private key of the operator account.
-    pub operator_private_key: H256,
+    /// Private key of the operator account. Required only for the `PrivateKey` signer.
+    pub operator_private_key: Option<H256>,
     /// Address of the operator account.
     pub operator_commit_eth_addr: Address,
     /// mount of confirmations required to consider L1 transaction committed.
@@ -44,6 +53,38 @@ pub struct Sender {
     pub max_txs_in_flight: u64,
     /// Whether sender should interact with L1 or not.
     pub is_enabled: bool,
+    /// Whether sender should send EIP-1559 (type 2) transactions instead of the legacy ones.
+    pub use_eip1559: bool,
+    /// Source of the signatures for the operator transactions.
+    pub signer_type: OperatorSignerType,
+    /// URL of the remote signing service. Required only for the `Remote` signer.
+    pub remote_signer_url: Option<String>,
+    /// Token to authorize in the remote signing service, if required.
+    pub remote_signer_auth_token: Option<String>,
+    /// Timeout of the requests to the remote signing service in seconds.
+    pub remote_signer_timeout: u64,
+    /// Addresses of the additional operator accounts. Each account has its own nonce lane,
+    /// so a stuck transaction in one lane doesn't block the transactions sent from the others.
+    pub additional_operator_eth_addrs: Option<Vec<Address>>,
+    /// Private keys of the additional operator accounts, in the same order as the addresses.
+    /// Required only for the `PrivateKey` signer.
+    pub additional_operator_private_keys: Option<Vec<H256>>,
+    /// Lane used to send the proof transactions: `0` is the main operator account,
+    /// `N` is the N-th account from `additional_operator_eth_addrs`.
+    pub prove_lane: usize,
+    /// Lane used to send the execute transactions, numbered the same way as `prove_lane`.
+    pub execute_lane: usize,
+    /// Interval between the checks of the operator accounts balances in seconds.
+    pub balance_check_interval: u64,
+    /// Period in seconds, the costs of the transactions confirmed within which are used
+    /// to estimate the operator spending rate.
+    pub balance_forecast_window: u64,
+    /// Alert is raised if the balance of the operator account is expected to last less
+    /// than this amount of seconds at the current spending rate.
+    pub low_balance_alert_threshold: u64,
+    /// Whether the transactions are simulated with `eth_call` against the pending block before
+    /// being sent. Operations which are expected to revert are held instead of being sent.
+    pub simulate_txs: bool,
 }
 
 impl Sender {
@@ -51,6 +92,40 @@ impl Sender {
     pub fn tx_poll_period(&self) -> Duration {
         Duration::from_secs(self.tx_poll_period)
     }
+
+    /// Converts `self.balance_check_interval` into `Duration`.
+    pub fn balance_check_interval(&self) -> Duration {
+        Duration::from_secs(self.balance_check_interval)
+    }
+
+    /// Converts `self.balance_forecast_window` into `Duration`.
+    pub fn balance_forecast_window(&self) -> Duration {
+        Duration::from_secs(self.balance_forecast_window)
+    }
+
+    /// Converts `self.low_balance_alert_threshold` into `Duration`.
+    pub fn low_balance_alert_threshold(&self) -> Duration {
+        Duration::from_secs(self.low_balance_alert_threshold)
+    }
+
+    /// Returns the addresses of the additional operator accounts paired with their private keys
+    /// (if provided), in the order of their lanes.
+    pub fn additional_operators(&self) -> Vec<(Address, Option<H256>)> {
+        let addresses = self
+            .additional_operator_eth_addrs
+            .clone()
+            .unwrap_or_default();
+        let private_keys = self
+            .additional_operator_private_keys
+            .clone()
+            .unwrap_or_default();
+
+        addresses
+            .into_iter()
+            .enumerate()
+            .map(|(idx, address)| (address, private_keys.get(idx).copied()))
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -63,6 +138,8 @@ pub struct GasLimit {
     pub sample_interval: u64,
     /// Scale factor for gas price limit (used by GasAdjuster).
     pub scale_factor: f64,
+    /// Priority fee (tip) per gas in wei offered in the EIP-1559 transactions.
+    pub max_priority_fee_per_gas: u64,
 }
 
 impl GasLimit {
@@ -90,9 +167,25 @@ mod tests {
                 tx_poll_period: 3,
                 max_txs_in_flight: 3,
                 is_enabled: true,
-                operator_private_key: hash(
+                use_eip1559: true,
+                signer_type: OperatorSignerType::Remote,
+                remote_signer_url: Some("http://127.0.0.1:8090".into()),
+                remote_signer_auth_token: None,
+                remote_signer_timeout: 10,
+                additional_operator_eth_addrs: Some(vec![
+                    addr("7a8d1b4c6e2f3a5b9c0d1e2f3a4b5c6d7e8f9a0b"),
+                    addr("1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c"),
+                ]),
+                additional_operator_private_keys: None,
+                prove_lane: 1,
+                execute_lane: 2,
+                balance_check_interval: 300,
+                balance_forecast_window: 86400,
+                low_balance_alert_threshold: 259200,
+                simulate_txs: true,
+                operator_private_key: Some(hash(
                     "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
-                ),
+                )),
                 operator_commit_eth_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
             },
             gas_price_limit: GasLimit {
@@ -100,6 +193,7 @@ mod tests {
                 update_interval: 150,
                 sample_interval: 15,
                 scale_factor: 1.0f64,
+                max_priority_fee_per_gas: 2000000000,
             },
         }
     }
@@ -112,12 +206,24 @@ ETH_SENDER_SENDER_EXPECTED_WAIT_TIME_BLOCK="30"
 ETH_SENDER_SENDER_TX_POLL_PERIOD="3"
 ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
 ETH_SENDER_SENDER_IS_ENABLED="true"
+ETH_SENDER_SENDER_USE_EIP1559="true"
+ETH_SENDER_SENDER_SIGNER_TYPE="Remote"
+ETH_SENDER_SENDER_REMOTE_SIGNER_URL="http://127.0.0.1:8090"
+ETH_SENDER_SENDER_REMOTE_SIGNER_TIMEOUT="10"
+ETH_SENDER_SENDER_ADDITIONAL_OPERATOR_ETH_ADDRS="0x7a8d1b4c6e2f3a5b9c0d1e2f3a4b5c6d7e8f9a0b,0x1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c"
+ETH_SENDER_SENDER_PROVE_LANE="1"
+ETH_SENDER_SENDER_EXECUTE_LANE="2"
+ETH_SENDER_SENDER_BALANCE_CHECK_INTERVAL="300"
+ETH_SENDER_SENDER_BALANCE_FORECAST_WINDOW="86400"
+ETH_SENDER_SENDER_LOW_BALANCE_ALERT_THRESHOLD="259200"
+ETH_SENDER_SENDER_SIMULATE_TXS="true"
 ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
 ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
 ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_GAS_PRICE_LIMIT_MAX_PRIORITY_FEE_PER_GAS="2000000000"
         "#;
         set_env(config);
 
@@ -134,6 +240,26 @@ ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
             config.sender.tx_poll_period(),
             Duration::from_secs(config.sender.tx_poll_period)
         );
+        assert_eq!(
+            config.sender.balance_check_interval(),
+            Duration::from_secs(config.sender.balance_check_interval)
+        );
+        assert_eq!(
+            config.sender.balance_forecast_window(),
+            Duration::from_secs(config.sender.balance_forecast_window)
+        );
+        assert_eq!(
+            config.sender.low_balance_alert_threshold(),
+            Duration::from_secs(config.sender.low_balance_alert_threshold)
+        );
+
+        assert_eq!(
+            config.sender.additional_operators(),
+            vec![
+                (addr("7a8d1b4c6e2f3a5b9c0d1e2f3a4b5c6d7e8f9a0b"), None),
+                (addr("1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c"), None),
+            ]
+        );
 
         assert_eq!(
             config.gas_price_limit.update_interval(),
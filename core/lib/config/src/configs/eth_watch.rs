@@ -14,6 +14,9 @@ pub struct ETHWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Maximum amount of Ethereum blocks processed at once when the watcher catches up
+    /// with the chain, e.g. after restart.
+    pub backfill_step: u64,
 }
 
 impl ETHWatchConfig {
@@ -36,6 +39,7 @@ mod tests {
         ETHWatchConfig {
             confirmations_for_eth_event: 0,
             eth_node_poll_interval: 300,
+            backfill_step: 10000,
         }
     }
 
@@ -44,6 +48,7 @@ mod tests {
         let config = r#"
 ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
 ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+ETH_WATCH_BACKFILL_STEP="10000"
         "#;
         set_env(config);
 
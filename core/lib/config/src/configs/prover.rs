@@ -32,6 +32,9 @@ pub struct Prover {
     pub cycle_wait: u64,
     /// Timeout for the requests to the prover server in seconds.
     pub request_timeout: u64,
+    /// RAM available to the prover in bytes. If set, the prover is not given the jobs
+    /// with setups too large to fit into it.
+    pub available_ram: Option<u64>,
 }
 
 impl Prover {
@@ -58,6 +61,14 @@ pub struct Core {
     pub gone_timeout: u64,
     /// Amount of provers in the cluser if there is no pending jobs.
     pub idle_provers: u32,
+    /// Maximum amount of provers in the cluster.
+    pub max_provers: u32,
+    /// Amount of times a job is given to the provers before it's considered failed.
+    pub max_job_attempts: u32,
+    /// Estimated amount of RAM in bytes required by the prover per constraint of the circuit setup.
+    pub ram_per_constraint: u64,
+    /// Whether the proofs submitted by the provers should be verified before being accepted.
+    pub verify_proofs: bool,
 }
 
 impl Core {
@@ -71,7 +82,7 @@ impl Core {
 pub struct WitnessGenerator {
     /// Interval to check whether a new witness generation job should be started in ms.
     pub prepare_data_interval: u64,
-    /// Amount of witness generator threads.
+    /// Amount of blocks the witness generator prepares witnesses for concurrently.
     pub witness_generators: usize,
 }
 
@@ -93,10 +104,15 @@ mod tests {
                 heartbeat_interval: 1000,
                 cycle_wait: 500,
                 request_timeout: 10,
+                available_ram: Some(68719476736),
             },
             core: Core {
                 gone_timeout: 60000,
                 idle_provers: 1,
+                max_provers: 20,
+                max_job_attempts: 3,
+                ram_per_constraint: 2048,
+                verify_proofs: true,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
@@ -111,8 +127,13 @@ mod tests {
 PROVER_PROVER_HEARTBEAT_INTERVAL="1000"
 PROVER_PROVER_CYCLE_WAIT="500"
 PROVER_PROVER_REQUEST_TIMEOUT="10"
+PROVER_PROVER_AVAILABLE_RAM="68719476736"
 PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
+PROVER_CORE_MAX_PROVERS="20"
+PROVER_CORE_MAX_JOB_ATTEMPTS="3"
+PROVER_CORE_RAM_PER_CONSTRAINT="2048"
+PROVER_CORE_VERIFY_PROOFS="true"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
         "#;
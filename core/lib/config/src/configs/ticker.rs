@@ -1,4 +1,5 @@
 // Built-in uses
+use std::time::Duration;
 // External uses
 use serde::Deserialize;
 // Workspace uses
@@ -10,6 +11,19 @@ use crate::envy_load;
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    /// Prices listed in the local JSON file, for the networks without access to the Internet.
+    Fixed,
+}
+
+/// Source of the token liquidity data used to decide whether token can be used to pay fees.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum TokenLiquiditySource {
+    /// Trade volume reported by the Uniswap GraphQL subgraph.
+    UniswapSubgraph,
+    /// Reserves of the Uniswap V2-style pairs, read directly from the Ethereum node.
+    UniswapV2Contracts,
+    /// Volumes listed in the local JSON file.
+    File,
 }
 
 /// Configuration for the fee ticker.
@@ -21,10 +35,22 @@ pub struct TickerConfig {
     pub coinmarketcap_base_url: String,
     /// URL of CoinGecko API. Can be set to the mock server for local development.
     pub coingecko_base_url: String,
+    /// Path to the JSON file with the fixed token prices and gas price.
+    pub fixed_prices_file_path: String,
     /// Coefficient for the fee price for fast withdrawal requests.
     pub fast_processing_coeff: f64,
+    /// Indicator of the source to be used for getting token liquidity.
+    pub token_liquidity_source: TokenLiquiditySource,
     /// Url to uniswap api
     pub uniswap_url: String,
+    /// Address of the Uniswap V2-style factory contract used to find token pairs.
+    pub uniswap_v2_factory_address: Address,
+    /// Address of the USD stablecoin, pairs with which are used to estimate token liquidity.
+    pub liquidity_quote_token: Address,
+    /// Number of decimals of the liquidity quote token.
+    pub liquidity_quote_token_decimals: u8,
+    /// Path to the JSON file with token liquidity volumes.
+    pub liquidity_file_path: String,
     /// The volume of tokens to confirm their liquidity
     pub liquidity_volume: f64,
     /// Time when liquidity check results are valid
@@ -37,6 +63,10 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// List of tokens for which subsidions are disabled.
     pub not_subsidized_tokens: Vec<Address>,
+    /// Interval between sampling the fees into the fee history, in seconds.
+    pub history_sampling_interval: u64,
+    /// Price and fee history entries are stored for this amount of days.
+    pub history_retention_days: u64,
 }
 
 impl TickerConfig {
@@ -44,14 +74,26 @@ impl TickerConfig {
         envy_load!("fee_ticker", "FEE_TICKER_")
     }
 
-    /// Returns the token price source type and the corresponding API URL.
+    /// Returns the token price source type and the location of the prices:
+    /// the corresponding API URL or the path to the fixed prices file.
     pub fn price_source(&self) -> (TokenPriceSource, &str) {
-        let url = match self.token_price_source {
+        let location = match self.token_price_source {
             TokenPriceSource::CoinGecko => self.coingecko_base_url.as_ref(),
             TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.as_ref(),
+            TokenPriceSource::Fixed => self.fixed_prices_file_path.as_ref(),
         };
 
-        (self.token_price_source, url)
+        (self.token_price_source, location)
+    }
+
+    /// Converts `self.history_sampling_interval` into `Duration`.
+    pub fn history_sampling_interval(&self) -> Duration {
+        Duration::from_secs(self.history_sampling_interval)
+    }
+
+    /// Converts `self.history_retention_days` into `chrono::Duration`.
+    pub fn history_retention(&self) -> chrono::Duration {
+        chrono::Duration::days(self.history_retention_days as i64)
     }
 }
 
@@ -65,8 +107,14 @@ mod tests {
             token_price_source: TokenPriceSource::CoinGecko,
             coinmarketcap_base_url: "http://127.0.0.1:9876".into(),
             coingecko_base_url: "http://127.0.0.1:9876".into(),
+            fixed_prices_file_path: "etc/tokens/fixed_prices.json".into(),
             fast_processing_coeff: 10.0f64,
+            token_liquidity_source: TokenLiquiditySource::UniswapSubgraph,
             uniswap_url: "http://127.0.0.1:9975/graphql".to_string(),
+            uniswap_v2_factory_address: addr("5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f"),
+            liquidity_quote_token: addr("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+            liquidity_quote_token_decimals: 6,
+            liquidity_file_path: "etc/tokens/liquidity.json".to_string(),
             liquidity_volume: 100.0,
             available_liquidity_seconds: 1000,
             unconditionally_valid_tokens: vec![addr("0000000000000000000000000000000000000000")],
@@ -76,6 +124,8 @@ mod tests {
                 addr("2b591e99afe9f32eaa6214f7b7629768c40eeb39"),
                 addr("34083bbd70d394110487feaa087da875a54624ec"),
             ],
+            history_sampling_interval: 600,
+            history_retention_days: 30,
         }
     }
 
@@ -85,14 +135,22 @@ mod tests {
 FEE_TICKER_TOKEN_PRICE_SOURCE="CoinGecko"
 FEE_TICKER_COINMARKETCAP_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_COINGECKO_BASE_URL="http://127.0.0.1:9876"
+FEE_TICKER_FIXED_PRICES_FILE_PATH="etc/tokens/fixed_prices.json"
 FEE_TICKER_FAST_PROCESSING_COEFF="10"
+FEE_TICKER_TOKEN_LIQUIDITY_SOURCE="UniswapSubgraph"
 FEE_TICKER_UNISWAP_URL=http://127.0.0.1:9975/graphql
+FEE_TICKER_UNISWAP_V2_FACTORY_ADDRESS="0x5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f"
+FEE_TICKER_LIQUIDITY_QUOTE_TOKEN="0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+FEE_TICKER_LIQUIDITY_QUOTE_TOKEN_DECIMALS=6
+FEE_TICKER_LIQUIDITY_FILE_PATH="etc/tokens/liquidity.json"
 FEE_TICKER_NOT_SUBSIDIZED_TOKENS="0x2b591e99afe9f32eaa6214f7b7629768c40eeb39,0x34083bbd70d394110487feaa087da875a54624ec"
 FEE_TICKER_AVAILABLE_LIQUIDITY_SECONDS=1000
 FEE_TICKER_TOKEN_MARKET_UPDATE_TIME=120
 FEE_TICKER_UNCONDITIONALLY_VALID_TOKENS="0x0000000000000000000000000000000000000000"
 FEE_TICKER_LIQUIDITY_VOLUME=100
 FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
+FEE_TICKER_HISTORY_SAMPLING_INTERVAL=600
+FEE_TICKER_HISTORY_RETENTION_DAYS=30
         "#;
         set_env(config);
 
@@ -105,11 +163,13 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
     fn methods() {
         const COINGECKO_URL: &str = "http://coingecko";
         const COINMARKETCAP_URL: &str = "http://coinmarketcap";
+        const FIXED_PRICES_PATH: &str = "fixed_prices.json";
 
         let mut config = expected_config();
 
         config.coingecko_base_url = COINGECKO_URL.into();
         config.coinmarketcap_base_url = COINMARKETCAP_URL.into();
+        config.fixed_prices_file_path = FIXED_PRICES_PATH.into();
 
         config.token_price_source = TokenPriceSource::CoinGecko;
         assert_eq!(
@@ -122,5 +182,11 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL)
         );
+
+        config.token_price_source = TokenPriceSource::Fixed;
+        assert_eq!(
+            config.price_source(),
+            (TokenPriceSource::Fixed, FIXED_PRICES_PATH)
+        );
     }
 }
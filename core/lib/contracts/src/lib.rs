@@ -17,6 +17,10 @@ const IEIP1271_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/dev-contracts/IEIP1271.sol/IEIP1271.json";
 const UPGRADE_GATEKEEPER_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/UpgradeGatekeeper.sol/UpgradeGatekeeper.json";
+const UNISWAP_V2_FACTORY_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IUniswapV2Factory.sol/IUniswapV2Factory.json";
+const UNISWAP_V2_PAIR_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IUniswapV2Pair.sol/IUniswapV2Pair.json";
 
 fn read_file_to_json_value(path: &str) -> io::Result<serde_json::Value> {
     let zksync_home = std::env::var("ZKSYNC_HOME").unwrap_or_else(|_| ".".into());
@@ -105,3 +109,21 @@ pub fn upgrade_gatekeeper() -> Contract {
         .to_string();
     Contract::load(abi_string.as_bytes()).expect("gatekeeper contract abi")
 }
+
+pub fn uniswap_v2_factory_contract() -> Contract {
+    let abi_string = read_file_to_json_value(UNISWAP_V2_FACTORY_CONTRACT_FILE)
+        .expect("couldn't read UNISWAP_V2_FACTORY_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from UNISWAP_V2_FACTORY_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("uniswap v2 factory contract abi")
+}
+
+pub fn uniswap_v2_pair_contract() -> Contract {
+    let abi_string = read_file_to_json_value(UNISWAP_V2_PAIR_CONTRACT_FILE)
+        .expect("couldn't read UNISWAP_V2_PAIR_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from UNISWAP_V2_PAIR_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("uniswap v2 pair contract abi")
+}
@@ -12,19 +12,38 @@ use web3::{
         Address, BlockId, BlockNumber, Bytes, Filter, Log, TransactionReceipt, H160, H256, U256,
         U64,
     },
-    Web3,
+    Transport, Web3,
 };
 
 // Workspace uses
 use zksync_eth_signer::{raw_ethereum_tx::RawTransaction, EthereumSigner};
 
-use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
+use crate::ethereum_gateway::{
+    CallOutcome, Eip1559Fees, ExecutedTxStatus, FailureInfo, SignedCallResult,
+};
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
 /// This is an emergency value, which will not be used normally.
 const FALLBACK_GAS_LIMIT: u64 = 3_000_000;
 
+/// Selector of the `Error(string)` function, which is used to encode the revert reasons.
+const REVERT_REASON_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Decodes the revert reason from the data returned by the reverted call.
+/// Returns `None` if the data is not an encoded `Error(string)`.
+fn decode_revert_reason(data: &[u8]) -> Option<String> {
+    if data.len() < 4 || data[..4] != REVERT_REASON_SELECTOR {
+        return None;
+    }
+
+    ethabi::decode(&[ethabi::ParamType::String], &data[4..])
+        .ok()?
+        .into_iter()
+        .next()?
+        .to_string()
+}
+
 #[derive(Clone)]
 pub struct ETHDirectClient<S: EthereumSigner> {
     eth_signer: S,
@@ -109,6 +128,17 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(block_number)
     }
 
+    pub async fn block_hash(&self, block_number: U64) -> Result<Option<H256>, anyhow::Error> {
+        let start = Instant::now();
+        let block = self
+            .web3
+            .eth()
+            .block(BlockId::Number(BlockNumber::Number(block_number)))
+            .await?;
+        metrics::histogram!("eth_client.direct.block_hash", start.elapsed());
+        Ok(block.and_then(|block| block.hash))
+    }
+
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         let start = Instant::now();
         let mut network_gas_price = self.web3.eth().gas_price().await?;
@@ -118,6 +148,22 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(network_gas_price)
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> Result<U256, anyhow::Error> {
+        let start = Instant::now();
+        // `web3` doesn't know about the EIP-1559 block fields, so the block is requested as is.
+        let block = self
+            .web3
+            .transport()
+            .execute("eth_getBlockByNumber", vec!["latest".into(), false.into()])
+            .await?;
+        let base_fee = block.get("baseFeePerGas").cloned().ok_or_else(|| {
+            anyhow::format_err!("Latest block has no base fee, EIP-1559 is not activated")
+        })?;
+        let base_fee = serde_json::from_value(base_fee)?;
+        metrics::histogram!("eth_client.direct.get_base_fee_per_gas", start.elapsed());
+        Ok(base_fee)
+    }
+
     pub async fn sign_prepared_tx(
         &self,
         data: Vec<u8>,
@@ -132,13 +178,45 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         data: Vec<u8>,
         contract_addr: H160,
         options: Options,
+    ) -> Result<SignedCallResult, anyhow::Error> {
+        self.sign_tx(data, contract_addr, options, None).await
+    }
+
+    pub async fn sign_prepared_eip1559_tx(
+        &self,
+        data: Vec<u8>,
+        options: Options,
+        fees: Eip1559Fees,
+    ) -> Result<SignedCallResult, anyhow::Error> {
+        self.sign_prepared_eip1559_tx_for_addr(data, self.contract_addr, options, fees)
+            .await
+    }
+
+    pub async fn sign_prepared_eip1559_tx_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        fees: Eip1559Fees,
+    ) -> Result<SignedCallResult, anyhow::Error> {
+        self.sign_tx(data, contract_addr, options, Some(fees)).await
+    }
+
+    /// Signs the legacy transaction or, if the fees are provided, the EIP-1559 one.
+    async fn sign_tx(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        eip1559_fees: Option<Eip1559Fees>,
     ) -> Result<SignedCallResult, anyhow::Error> {
         // fetch current gas_price
         let start = Instant::now();
 
-        let gas_price = match options.gas_price {
-            Some(gas_price) => gas_price,
-            None => self.get_gas_price().await?,
+        let gas_price = match (eip1559_fees, options.gas_price) {
+            (Some(fees), _) => fees.max_fee_per_gas,
+            (None, Some(gas_price)) => gas_price,
+            (None, None) => self.get_gas_price().await?,
         };
 
         let nonce = match options.nonce {
@@ -170,6 +248,8 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
             gas_price,
             gas,
             data,
+            max_fee_per_gas: eip1559_fees.map(|fees| fees.max_fee_per_gas),
+            max_priority_fee_per_gas: eip1559_fees.map(|fees| fees.max_priority_fee_per_gas),
         };
 
         let signed_tx = self.eth_signer.sign_transaction(tx).await?;
@@ -182,6 +262,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(SignedCallResult {
             raw_tx: signed_tx,
             gas_price,
+            max_priority_fee_per_gas: eip1559_fees.map(|fees| fees.max_priority_fee_per_gas),
             nonce,
             hash,
         })
@@ -230,21 +311,8 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
             .call(call_request, receipt.block_number.map(Into::into))
             .await?;
         let revert_code = hex::encode(&encoded_revert_reason.0);
-        let revert_reason = if encoded_revert_reason.0.len() >= 4 {
-            let encoded_string_without_function_hash = &encoded_revert_reason.0[4..];
-
-            ethabi::decode(
-                &[ethabi::ParamType::String],
-                encoded_string_without_function_hash,
-            )?
-            .into_iter()
-            .next()
-            .unwrap()
-            .to_string()
-            .unwrap()
-        } else {
-            "unknown".to_string()
-        };
+        let revert_reason =
+            decode_revert_reason(&encoded_revert_reason.0).unwrap_or_else(|| "unknown".to_string());
 
         metrics::histogram!("eth_client.direct.failure_reason", start.elapsed());
         Ok(Some(FailureInfo {
@@ -255,6 +323,72 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         }))
     }
 
+    pub async fn call_with_data(
+        &self,
+        data: Vec<u8>,
+        options: Options,
+        block: BlockNumber,
+    ) -> Result<CallOutcome, anyhow::Error> {
+        self.call_with_data_for_addr(data, self.contract_addr, options, block)
+            .await
+    }
+
+    pub async fn call_with_data_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        block: BlockNumber,
+    ) -> Result<CallOutcome, anyhow::Error> {
+        let start = Instant::now();
+        let gas_limit = options.gas.unwrap_or_else(|| FALLBACK_GAS_LIMIT.into());
+        let call_request = web3::types::CallRequest {
+            from: Some(self.sender_account),
+            to: Some(contract_addr),
+            gas: Some(gas_limit),
+            gas_price: options.gas_price,
+            value: options.value,
+            data: Some(Bytes(data)),
+        };
+
+        let reverted = |revert_code: Vec<u8>, message: Option<String>| {
+            let revert_reason = decode_revert_reason(&revert_code)
+                .or(message)
+                .unwrap_or_else(|| "unknown".to_string());
+            CallOutcome::Reverted(FailureInfo {
+                revert_code: hex::encode(&revert_code),
+                revert_reason,
+                gas_used: None,
+                gas_limit,
+            })
+        };
+
+        let outcome = match self
+            .web3
+            .eth()
+            .call(call_request, Some(BlockId::Number(block)))
+            .await
+        {
+            // Some nodes return the revert data as the call result.
+            Ok(output) if decode_revert_reason(&output.0).is_some() => reverted(output.0, None),
+            Ok(output) => CallOutcome::Success(output.0),
+            // Others report the revert as an error, optionally with the revert data attached.
+            Err(web3::Error::Rpc(error)) if error.message.to_lowercase().contains("revert") => {
+                let revert_code = error
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.as_str())
+                    .and_then(|data| hex::decode(data.trim_start_matches("0x")).ok())
+                    .unwrap_or_default();
+                reverted(revert_code, Some(error.message))
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        metrics::histogram!("eth_client.direct.call_with_data", start.elapsed());
+        Ok(outcome)
+    }
+
     pub async fn eth_balance(&self, address: Address) -> Result<U256, anyhow::Error> {
         let start = Instant::now();
         let balance = self.web3.eth().balance(address, None).await?;
@@ -340,11 +474,28 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
     pub async fn get_tx_status(&self, hash: H256) -> anyhow::Result<Option<ExecutedTxStatus>> {
         let start = Instant::now();
 
-        let receipt = self.tx_receipt(hash).await?;
+        // The receipt is requested directly, since `web3` doesn't know about the
+        // `effectiveGasPrice` field introduced in the London hardfork.
+        let receipt: serde_json::Value = self
+            .web3
+            .transport()
+            .execute(
+                "eth_getTransactionReceipt",
+                vec![serde_json::to_value(hash)?],
+            )
+            .await?;
+        let effective_gas_price: Option<U256> = receipt
+            .get("effectiveGasPrice")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?;
+        let receipt: Option<TransactionReceipt> = serde_json::from_value(receipt)?;
+
         let res: Result<Option<ExecutedTxStatus>, anyhow::Error> = match receipt {
             Some(TransactionReceipt {
                 block_number: Some(tx_block_number),
                 status: Some(status),
+                gas_used,
                 ..
             }) => {
                 let confirmations = self
@@ -361,10 +512,24 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     Some(receipt.unwrap())
                 };
 
+                // Nodes not supporting the London hardfork don't report the effective gas price,
+                // but then the gas price of the executed transaction itself is paid.
+                let effective_gas_price = match effective_gas_price {
+                    Some(effective_gas_price) => Some(effective_gas_price),
+                    None => self
+                        .web3
+                        .eth()
+                        .transaction(hash.into())
+                        .await?
+                        .map(|transaction| transaction.gas_price),
+                };
+
                 Ok(Some(ExecutedTxStatus {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
+                    effective_gas_price,
                 }))
             }
             _ => Ok(None),
@@ -394,3 +559,19 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
             .expect("failed to encode parameters")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn revert_reason_decoding() {
+        let mut data = REVERT_REASON_SELECTOR.to_vec();
+        data.extend(ethabi::encode(&[ethabi::Token::String("i".into())]));
+        assert_eq!(decode_revert_reason(&data), Some("i".to_string()));
+
+        // Custom errors and the empty revert data have no reason.
+        assert_eq!(decode_revert_reason(&[0x4e, 0x48, 0x7b, 0x71]), None);
+        assert_eq!(decode_revert_reason(&[]), None);
+    }
+}
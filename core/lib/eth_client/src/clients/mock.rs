@@ -6,12 +6,12 @@ use std::collections::{HashMap, HashSet};
 use tokio::sync::RwLock;
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::Options;
-use web3::types::{BlockId, Filter, Log, U64};
+use web3::types::{BlockId, BlockNumber, Filter, Log, U64};
 
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::{
-    ethereum_gateway::{ExecutedTxStatus, FailureInfo},
+    ethereum_gateway::{CallOutcome, Eip1559Fees, ExecutedTxStatus, FailureInfo},
     SignedCallResult,
 };
 
@@ -20,8 +20,13 @@ use crate::{
 pub struct MockEthereum {
     pub block_number: u64,
     pub gas_price: U256,
+    pub base_fee_per_gas: U256,
+    pub pending_nonce: U256,
+    pub balance: U256,
     pub tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     pub sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
+    /// Revert reasons of the calls with the given data, other calls succeed.
+    pub reverted_calls: Arc<RwLock<HashMap<Vec<u8>, String>>>,
 }
 
 impl Default for MockEthereum {
@@ -29,8 +34,12 @@ impl Default for MockEthereum {
         Self {
             block_number: 1,
             gas_price: 100.into(),
+            base_fee_per_gas: 80.into(),
+            pending_nonce: 0.into(),
+            balance: 0.into(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
+            reverted_calls: Default::default(),
         }
     }
 }
@@ -72,6 +81,8 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
+            effective_gas_price: None,
         };
         self.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -84,6 +95,8 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
+            effective_gas_price: None,
         };
         self.tx_statuses.write().await.insert(*hash, status);
     }
@@ -95,10 +108,21 @@ impl MockEthereum {
         Ok(self.block_number.into())
     }
 
+    pub async fn block_hash(&self, block_number: U64) -> anyhow::Result<Option<H256>> {
+        if block_number.as_u64() > self.block_number {
+            return Ok(None);
+        }
+        Ok(Some(H256::from_low_u64_be(block_number.as_u64())))
+    }
+
     pub async fn get_gas_price(&self) -> anyhow::Result<U256> {
         Ok(self.gas_price)
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> anyhow::Result<U256> {
+        Ok(self.base_fee_per_gas)
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         // Cut hash of transaction
         let mut hash: [u8; 32] = Default::default();
@@ -113,6 +137,30 @@ impl MockEthereum {
         options: Options,
     ) -> anyhow::Result<SignedCallResult> {
         let gas_price = options.gas_price.unwrap_or(self.gas_price);
+        Ok(self.sign_mock_tx(raw_tx, options, gas_price, None))
+    }
+
+    pub async fn sign_prepared_eip1559_tx(
+        &self,
+        raw_tx: Vec<u8>,
+        options: Options,
+        fees: Eip1559Fees,
+    ) -> anyhow::Result<SignedCallResult> {
+        Ok(self.sign_mock_tx(
+            raw_tx,
+            options,
+            fees.max_fee_per_gas,
+            Some(fees.max_priority_fee_per_gas),
+        ))
+    }
+
+    fn sign_mock_tx(
+        &self,
+        raw_tx: Vec<u8>,
+        options: Options,
+        gas_price: U256,
+        max_priority_fee_per_gas: Option<U256>,
+    ) -> SignedCallResult {
         let nonce = options.nonce.expect("Nonce must be set for every tx");
 
         // Nonce and gas_price are appended to distinguish the same transactions
@@ -120,16 +168,59 @@ impl MockEthereum {
         let mut data_for_hash = raw_tx.clone();
         data_for_hash.append(&mut ethabi::encode(gas_price.into_tokens().as_ref()));
         data_for_hash.append(&mut ethabi::encode(nonce.into_tokens().as_ref()));
+        if let Some(priority_fee) = max_priority_fee_per_gas {
+            data_for_hash.append(&mut ethabi::encode(priority_fee.into_tokens().as_ref()));
+        }
         let hash = Self::fake_sha256(data_for_hash.as_ref()); // Okay for test purposes.
                                                               // Concatenate raw_tx plus hash for test purposes
         let mut new_raw_tx = hash.as_bytes().to_vec();
         new_raw_tx.extend(raw_tx);
-        Ok(SignedCallResult {
+        SignedCallResult {
             raw_tx: new_raw_tx,
             gas_price,
+            max_priority_fee_per_gas,
             nonce,
             hash,
-        })
+        }
+    }
+
+    /// Makes the calls with the given data revert with the reason,
+    /// or succeed again if the reason is `None`.
+    pub async fn set_call_revert_reason(&mut self, data: Vec<u8>, reason: Option<&str>) {
+        let mut reverted_calls = self.reverted_calls.write().await;
+        match reason {
+            Some(reason) => reverted_calls.insert(data, reason.to_string()),
+            None => reverted_calls.remove(&data),
+        };
+    }
+
+    pub async fn call_with_data(
+        &self,
+        data: Vec<u8>,
+        options: Options,
+        block: BlockNumber,
+    ) -> anyhow::Result<CallOutcome> {
+        self.call_with_data_for_addr(data, Default::default(), options, block)
+            .await
+    }
+
+    pub async fn call_with_data_for_addr(
+        &self,
+        data: Vec<u8>,
+        _contract_addr: H160,
+        options: Options,
+        _block: BlockNumber,
+    ) -> anyhow::Result<CallOutcome> {
+        let outcome = match self.reverted_calls.read().await.get(&data) {
+            Some(reason) => CallOutcome::Reverted(FailureInfo {
+                revert_code: String::new(),
+                revert_reason: reason.clone(),
+                gas_used: None,
+                gas_limit: options.gas.unwrap_or_default(),
+            }),
+            None => CallOutcome::Success(Vec::new()),
+        };
+        Ok(outcome)
     }
 
     pub async fn failure_reason(
@@ -140,7 +231,7 @@ impl MockEthereum {
     }
 
     pub async fn pending_nonce(&self) -> Result<U256, Error> {
-        unreachable!()
+        Ok(self.pending_nonce)
     }
 
     pub async fn current_nonce(&self) -> Result<U256, Error> {
@@ -148,7 +239,7 @@ impl MockEthereum {
     }
 
     pub async fn sender_eth_balance(&self) -> Result<U256, Error> {
-        unreachable!()
+        Ok(self.balance)
     }
 
     pub async fn sign_prepared_tx_for_addr(
@@ -160,6 +251,16 @@ impl MockEthereum {
         unreachable!()
     }
 
+    pub async fn sign_prepared_eip1559_tx_for_addr(
+        &self,
+        _data: Vec<u8>,
+        _contract_addr: H160,
+        _options: Options,
+        _fees: Eip1559Fees,
+    ) -> Result<SignedCallResult, Error> {
+        unreachable!()
+    }
+
     pub async fn tx_receipt(&self, _tx_hash: H256) -> Result<Option<TransactionReceipt>, Error> {
         unreachable!()
     }
@@ -1,19 +1,171 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use ethabi::Contract;
+use futures::future::join_all;
 use web3::{
     contract::tokens::{Detokenize, Tokenize},
     contract::Options,
-    types::{Address, BlockId, Filter, Log, U64},
+    types::{Address, BlockId, BlockNumber, Filter, Log, U64},
 };
 
-use zksync_eth_signer::PrivateKeySigner;
+use zksync_config::{configs::eth_client::MultiplexerStrategy, ETHClientConfig};
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
-use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
-use crate::ETHDirectClient;
+use crate::ethereum_gateway::{
+    CallOutcome, Eip1559Fees, ExecutedTxStatus, FailureInfo, SignedCallResult,
+};
+use crate::{ETHDirectClient, OperatorSigner};
+
+/// Weight of the latest request outcome in the health score of the endpoint.
+const HEALTH_SCORE_WEIGHT: f64 = 0.1;
+
+/// Options of the `MultiplexerEthereumClient`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiplexerOptions {
+    /// Strategy of choosing the endpoint to send the request to.
+    pub strategy: MultiplexerStrategy,
+    /// Amount of consecutive failures after which the endpoint is skipped until the cooldown expires.
+    pub circuit_breaker_threshold: u32,
+    /// Time during which the endpoint with the opened circuit breaker is skipped.
+    pub circuit_breaker_cooldown: Duration,
+    /// Amount of endpoints that must agree on the result of the critical reads.
+    pub read_quorum: usize,
+    /// Whether the signed transactions should be sent to all the endpoints.
+    pub broadcast_txs: bool,
+}
+
+impl Default for MultiplexerOptions {
+    fn default() -> Self {
+        Self {
+            strategy: MultiplexerStrategy::Ordered,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown: Duration::from_secs(30),
+            read_quorum: 1,
+            broadcast_txs: false,
+        }
+    }
+}
+
+impl MultiplexerOptions {
+    pub fn from_config(config: &ETHClientConfig) -> Self {
+        Self {
+            strategy: config.multiplexer_strategy,
+            circuit_breaker_threshold: config.circuit_breaker_threshold,
+            circuit_breaker_cooldown: config.circuit_breaker_cooldown(),
+            read_quorum: config.read_quorum,
+            broadcast_txs: config.broadcast_txs,
+        }
+    }
+}
+
+/// Health of the endpoint as observed by the multiplexer.
+#[derive(Debug, Clone)]
+struct EndpointHealth {
+    /// Exponential moving average of the request outcomes (`1` for success, `0` for failure).
+    score: f64,
+    /// Amount of failures since the last successful request.
+    consecutive_failures: u32,
+    /// Moment until which the endpoint is skipped (opened circuit breaker).
+    open_until: Option<Instant>,
+}
+
+impl Default for EndpointHealth {
+    fn default() -> Self {
+        Self {
+            score: 1.0,
+            consecutive_failures: 0,
+            open_until: None,
+        }
+    }
+}
+
+impl EndpointHealth {
+    /// Returns `true` if the circuit breaker of the endpoint is closed, or if its cooldown expired,
+    /// so the endpoint may be probed again.
+    fn is_available(&self, now: Instant) -> bool {
+        self.open_until.map(|until| now >= until).unwrap_or(true)
+    }
+
+    fn report_success(&mut self) {
+        self.score = self.score * (1.0 - HEALTH_SCORE_WEIGHT) + HEALTH_SCORE_WEIGHT;
+        self.consecutive_failures = 0;
+        self.open_until = None;
+    }
+
+    /// Reports the failed request. Returns `true` if the circuit breaker got opened.
+    fn report_failure(&mut self, options: &MultiplexerOptions, now: Instant) -> bool {
+        self.score *= 1.0 - HEALTH_SCORE_WEIGHT;
+        self.consecutive_failures += 1;
+
+        if self.consecutive_failures >= options.circuit_breaker_threshold && self.is_available(now)
+        {
+            self.open_until = Some(now + options.circuit_breaker_cooldown);
+            return true;
+        }
+        false
+    }
+}
+
+/// Returns the indices of the endpoints in the order they should be tried.
+///
+/// With the `HealthScored` strategy endpoints with the opened circuit breaker are moved to
+/// the end of the list (so they are only used when nothing else works) and the rest is sorted
+/// by the health score.
+fn endpoints_order(
+    health: &[EndpointHealth],
+    strategy: MultiplexerStrategy,
+    now: Instant,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..health.len()).collect();
+    if strategy == MultiplexerStrategy::HealthScored {
+        // Sorting is stable, so endpoints with the same score keep the configured order.
+        order.sort_by(|&lhs, &rhs| {
+            let (lhs, rhs) = (&health[lhs], &health[rhs]);
+            rhs.is_available(now)
+                .cmp(&lhs.is_available(now))
+                .then_with(|| rhs.score.partial_cmp(&lhs.score).unwrap())
+        });
+    }
+    order
+}
+
+/// Returns the block number reached by at least `quorum` endpoints.
+fn quorum_block_number(mut block_numbers: Vec<U64>, quorum: usize) -> Option<U64> {
+    block_numbers.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    block_numbers.get(quorum.max(1) - 1).copied()
+}
+
+/// Returns the responses from the largest group of the equal (by the `key`) responses,
+/// if the group has at least `quorum` elements.
+fn quorum_responses<T, K, F>(responses: Vec<T>, quorum: usize, key: F) -> Option<Vec<T>>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    let mut groups: Vec<(K, Vec<T>)> = Vec::new();
+    for response in responses {
+        let response_key = key(&response);
+        match groups.iter_mut().find(|(key, _)| *key == response_key) {
+            Some((_, group)) => group.push(response),
+            None => groups.push((response_key, vec![response])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, group)| group)
+        .max_by_key(|group| group.len())
+        .filter(|group| group.len() >= quorum.max(1))
+}
 
 #[derive(Debug, Clone)]
 pub struct MultiplexerEthereumClient {
-    clients: Vec<(String, ETHDirectClient<PrivateKeySigner>)>,
+    clients: Vec<(String, ETHDirectClient<OperatorSigner>)>,
+    options: MultiplexerOptions,
+    health: Arc<Mutex<Vec<EndpointHealth>>>,
 }
 
 impl Default for MultiplexerEthereumClient {
@@ -22,38 +174,130 @@ impl Default for MultiplexerEthereumClient {
     }
 }
 
+/// Sends the request to the endpoints one by one until one of them succeeds.
+/// If all of them fail, the error of the last one is returned, so it can be inspected by the caller.
 macro_rules! multiple_call {
-    ($self:expr, $func:ident($($attr:expr),+)) => {
-        for (name, client) in $self.clients.iter() {
-            match client.$func($($attr.clone()),+).await {
-                Ok(res) => return Ok(res),
-                Err(err) => vlog::error!("Error in interface: {}, {} ", name, err),
+    ($self:expr, $func:ident($($attr:expr),*)) => {
+        let mut last_error = None;
+        for idx in $self.endpoints_order() {
+            let (name, client) = &$self.clients[idx];
+            match client.$func($($attr.clone()),*).await {
+                Ok(res) => {
+                    $self.report_success(idx);
+                    return Ok(res);
+                }
+                Err(err) => {
+                    $self.report_failure(idx);
+                    vlog::error!("Error in interface: {}, {} ", name, err);
+                    last_error = Some(anyhow::Error::from(err));
+                }
             }
         }
-        anyhow::bail!("All interfaces was wrong please try again")
+        return Err(match last_error {
+            Some(err) => err.context("All interfaces failed"),
+            None => anyhow::format_err!("All interfaces was wrong please try again"),
+        });
     };
+}
+
+/// Sends the request to all the available endpoints concurrently and collects the successful responses.
+macro_rules! broadcast_call {
+    ($self:expr, $func:ident($($attr:expr),*)) => {{
+        let order = $self.available_endpoints();
+        let responses = join_all(order.iter().map(|&idx| {
+            let (_, client) = &$self.clients[idx];
+            client.$func($($attr.clone()),*)
+        }))
+        .await;
 
-    ($self:expr, $func:ident()) => {
-        for (name, client) in $self.clients.iter() {
-            match client.$func().await {
-                Ok(res) => return Ok(res),
-                Err(err) => vlog::error!("Error in interface: {}, {} ", name, err),
+        let mut successful = Vec::with_capacity(responses.len());
+        for (idx, response) in order.into_iter().zip(responses) {
+            match response {
+                Ok(res) => {
+                    $self.report_success(idx);
+                    successful.push(res);
+                }
+                Err(err) => {
+                    $self.report_failure(idx);
+                    vlog::error!("Error in interface: {}, {} ", $self.clients[idx].0, err);
+                }
             }
         }
-        anyhow::bail!("All interfaces was wrong please try again")
-    };
+        successful
+    }};
 }
 
 impl MultiplexerEthereumClient {
     pub fn new() -> Self {
-        Self { clients: vec![] }
+        Self {
+            clients: vec![],
+            options: MultiplexerOptions::default(),
+            health: Default::default(),
+        }
     }
 
-    pub fn add_client(mut self, name: String, client: ETHDirectClient<PrivateKeySigner>) -> Self {
+    pub fn with_options(self, options: MultiplexerOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    pub fn add_client(mut self, name: String, client: ETHDirectClient<OperatorSigner>) -> Self {
         self.clients.push((name, client));
+        self.health.lock().unwrap().push(EndpointHealth::default());
         self
     }
 
+    /// Returns the indices of the endpoints in the order they should be tried.
+    fn endpoints_order(&self) -> Vec<usize> {
+        let health = self.health.lock().unwrap();
+        endpoints_order(&health, self.options.strategy, Instant::now())
+    }
+
+    /// Returns the indices of the endpoints to be used for the requests sent to several endpoints
+    /// at once. Endpoints with the opened circuit breaker are excluded unless there are no other ones.
+    fn available_endpoints(&self) -> Vec<usize> {
+        let health = self.health.lock().unwrap();
+        let now = Instant::now();
+        let available: Vec<usize> = (0..health.len())
+            .filter(|&idx| {
+                self.options.strategy == MultiplexerStrategy::Ordered
+                    || health[idx].is_available(now)
+            })
+            .collect();
+
+        if available.is_empty() {
+            (0..health.len()).collect()
+        } else {
+            available
+        }
+    }
+
+    fn report_success(&self, idx: usize) {
+        self.health.lock().unwrap()[idx].report_success();
+    }
+
+    fn report_failure(&self, idx: usize) {
+        let breaker_opened =
+            self.health.lock().unwrap()[idx].report_failure(&self.options, Instant::now());
+        if breaker_opened && self.options.strategy == MultiplexerStrategy::HealthScored {
+            vlog::warn!(
+                "Interface {} is failing, it won't be used for {}s",
+                self.clients[idx].0,
+                self.options.circuit_breaker_cooldown.as_secs()
+            );
+        }
+    }
+
+    /// Returns an error describing that the read quorum is not reached.
+    fn quorum_error(&self, method: &str, responses: usize) -> anyhow::Error {
+        anyhow::format_err!(
+            "Quorum of {} is not reached for `{}`: {} of {} interfaces responded successfully or agreed",
+            self.options.read_quorum,
+            method,
+            responses,
+            self.clients.len()
+        )
+    }
+
     pub async fn pending_nonce(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, pending_nonce());
     }
@@ -62,14 +306,31 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, current_nonce());
     }
 
+    /// Returns the block number reached by at least `read_quorum` endpoints, so a single
+    /// lagging endpoint can't make the chain head look stale.
     pub async fn block_number(&self) -> Result<U64, anyhow::Error> {
-        multiple_call!(self, block_number());
+        if self.options.read_quorum <= 1 {
+            multiple_call!(self, block_number());
+        }
+
+        let block_numbers = broadcast_call!(self, block_number());
+        let responses = block_numbers.len();
+        quorum_block_number(block_numbers, self.options.read_quorum)
+            .ok_or_else(|| self.quorum_error("block_number", responses))
+    }
+
+    pub async fn block_hash(&self, block_number: U64) -> Result<Option<H256>, anyhow::Error> {
+        multiple_call!(self, block_hash(block_number));
     }
 
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, get_gas_price());
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> Result<U256, anyhow::Error> {
+        multiple_call!(self, get_base_fee_per_gas());
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, sender_eth_balance());
     }
@@ -94,8 +355,40 @@ impl MultiplexerEthereumClient {
         );
     }
 
+    pub async fn sign_prepared_eip1559_tx(
+        &self,
+        data: Vec<u8>,
+        options: Options,
+        fees: Eip1559Fees,
+    ) -> Result<SignedCallResult, anyhow::Error> {
+        multiple_call!(self, sign_prepared_eip1559_tx(data, options, fees));
+    }
+
+    pub async fn sign_prepared_eip1559_tx_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        fees: Eip1559Fees,
+    ) -> Result<SignedCallResult, anyhow::Error> {
+        multiple_call!(
+            self,
+            sign_prepared_eip1559_tx_for_addr(data, contract_addr, options, fees)
+        );
+    }
+
+    /// Sends the transaction to the first healthy endpoint or, if `broadcast_txs` is set,
+    /// to all the endpoints. In the latter case it's enough for one endpoint to accept the transaction.
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
-        multiple_call!(self, send_raw_tx(tx));
+        if !self.options.broadcast_txs {
+            multiple_call!(self, send_raw_tx(tx));
+        }
+
+        let hashes = broadcast_call!(self, send_raw_tx(tx));
+        match hashes.into_iter().next() {
+            Some(hash) => Ok(hash),
+            None => anyhow::bail!("Transaction was not accepted by any of the interfaces"),
+        }
     }
 
     pub async fn tx_receipt(
@@ -112,6 +405,28 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, failure_reason(tx_hash));
     }
 
+    pub async fn call_with_data(
+        &self,
+        data: Vec<u8>,
+        options: Options,
+        block: BlockNumber,
+    ) -> Result<CallOutcome, anyhow::Error> {
+        multiple_call!(self, call_with_data(data, options, block));
+    }
+
+    pub async fn call_with_data_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        block: BlockNumber,
+    ) -> Result<CallOutcome, anyhow::Error> {
+        multiple_call!(
+            self,
+            call_with_data_for_addr(data, contract_addr, options, block)
+        );
+    }
+
     pub async fn eth_balance(&self, address: Address) -> Result<U256, anyhow::Error> {
         multiple_call!(self, eth_balance(address));
     }
@@ -167,15 +482,41 @@ impl MultiplexerEthereumClient {
         );
     }
 
+    /// Returns the transaction status on which at least `read_quorum` endpoints agree. Endpoints
+    /// agree if they report the same execution outcome, while the number of confirmations may vary,
+    /// so the lowest one is returned.
     pub async fn get_tx_status(
         &self,
         hash: H256,
     ) -> Result<Option<ExecutedTxStatus>, anyhow::Error> {
-        multiple_call!(self, get_tx_status(hash));
+        if self.options.read_quorum <= 1 {
+            multiple_call!(self, get_tx_status(hash));
+        }
+
+        let statuses = broadcast_call!(self, get_tx_status(hash));
+        let responses = statuses.len();
+        let agreed = quorum_responses(statuses, self.options.read_quorum, |status| {
+            status.as_ref().map(|status| status.success)
+        })
+        .ok_or_else(|| self.quorum_error("get_tx_status", responses))?;
+
+        Ok(agreed
+            .into_iter()
+            .min_by_key(|status| status.as_ref().map(|status| status.confirmations))
+            .flatten())
     }
 
+    /// Returns the logs on which at least `read_quorum` endpoints agree.
     pub async fn logs(&self, filter: Filter) -> anyhow::Result<Vec<Log>> {
-        multiple_call!(self, logs(filter));
+        if self.options.read_quorum <= 1 {
+            multiple_call!(self, logs(filter));
+        }
+
+        let logs = broadcast_call!(self, logs(filter));
+        let responses = logs.len();
+        quorum_responses(logs, self.options.read_quorum, Clone::clone)
+            .and_then(|agreed| agreed.into_iter().next())
+            .ok_or_else(|| self.quorum_error("logs", responses))
     }
 
     pub fn encode_tx_data<P: Tokenize + Clone>(&self, func: &str, params: P) -> Vec<u8> {
@@ -183,3 +524,139 @@ impl MultiplexerEthereumClient {
         client.encode_tx_data(func, params)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn health_scored_order() {
+        let options = MultiplexerOptions {
+            strategy: MultiplexerStrategy::HealthScored,
+            circuit_breaker_threshold: 2,
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let mut health = vec![EndpointHealth::default(); 3];
+
+        // The first endpoint fails once: its score decreases, but the breaker is still closed.
+        assert!(!health[0].report_failure(&options, now));
+        assert_eq!(
+            endpoints_order(&health, MultiplexerStrategy::HealthScored, now),
+            vec![1, 2, 0]
+        );
+        assert_eq!(
+            endpoints_order(&health, MultiplexerStrategy::Ordered, now),
+            vec![0, 1, 2]
+        );
+
+        // The second endpoint fails twice and goes to the end despite the score being close to the first one.
+        assert!(!health[1].report_failure(&options, now));
+        assert!(health[1].report_failure(&options, now));
+        assert_eq!(
+            endpoints_order(&health, MultiplexerStrategy::HealthScored, now),
+            vec![2, 0, 1]
+        );
+
+        // After the cooldown the endpoint is probed again, and a success closes the breaker.
+        let after_cooldown = now + options.circuit_breaker_cooldown;
+        assert!(health[1].is_available(after_cooldown));
+        health[1].report_success();
+        assert_eq!(health[1].consecutive_failures, 0);
+        assert!(health[1].open_until.is_none());
+    }
+
+    #[test]
+    fn quorum() {
+        let block_numbers = vec![U64::from(10), U64::from(12), U64::from(11)];
+        assert_eq!(
+            quorum_block_number(block_numbers.clone(), 1),
+            Some(U64::from(12))
+        );
+        assert_eq!(
+            quorum_block_number(block_numbers.clone(), 2),
+            Some(U64::from(11))
+        );
+        assert_eq!(quorum_block_number(block_numbers, 4), None);
+
+        let responses = vec![Some(true), None, Some(true)];
+        assert_eq!(
+            quorum_responses(responses.clone(), 2, Clone::clone),
+            Some(vec![Some(true), Some(true)])
+        );
+        assert_eq!(quorum_responses(responses, 3, Clone::clone), None);
+    }
+
+    /// Starts the HTTP server responding with the JSON-RPC error to every request.
+    async fn spawn_failing_node(error_message: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = vec![0u8; 4096];
+                let len = socket.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..len]);
+                let request_id = request
+                    .split("\r\n\r\n")
+                    .nth(1)
+                    .and_then(|body| serde_json::from_str::<serde_json::Value>(body).ok())
+                    .map(|body| body["id"].clone())
+                    .unwrap_or_default();
+
+                let body = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request_id,
+                    "error": { "code": -32005, "message": error_message },
+                })
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        url
+    }
+
+    /// Checks that the error of the node reaches the caller when all the endpoints fail,
+    /// so the range and rate limit errors of the logs requests are recognized.
+    #[tokio::test]
+    async fn node_error_is_returned_when_all_endpoints_fail() {
+        const ERROR_MESSAGE: &str = "query returned more than 10000 results";
+
+        let contract = Contract::load(&b"[]"[..]).unwrap();
+        let mut client = MultiplexerEthereumClient::new();
+        for _ in 0..2 {
+            let url = spawn_failing_node(ERROR_MESSAGE).await;
+            let transport = web3::transports::Http::new(&url).unwrap();
+            client = client.add_client(
+                url,
+                ETHDirectClient::new(
+                    transport,
+                    contract.clone(),
+                    Address::zero(),
+                    OperatorSigner::PrivateKey(zksync_eth_signer::PrivateKeySigner::new(
+                        H256::repeat_byte(1),
+                    )),
+                    Address::zero(),
+                    9,
+                    1.0,
+                ),
+            );
+        }
+
+        let error = client
+            .logs(web3::types::FilterBuilder::default().build())
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains(ERROR_MESSAGE));
+        assert!(crate::logs::is_range_limit_error(&error));
+        assert!(!crate::logs::is_rate_limit_error(&error));
+    }
+}
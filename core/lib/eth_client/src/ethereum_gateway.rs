@@ -1,25 +1,36 @@
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::Options;
-use web3::types::{Address, BlockId, Filter, Log, U64};
+use web3::types::{Address, BlockId, BlockNumber, Filter, Log, U64};
 
 use std::fmt::Debug;
 use zksync_config::ZkSyncConfig;
 use zksync_contracts::zksync_contract;
-use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::clients::mock::MockEthereum;
-use crate::clients::multiplexer::MultiplexerEthereumClient;
-use crate::ETHDirectClient;
+use crate::clients::multiplexer::{MultiplexerEthereumClient, MultiplexerOptions};
+use crate::{ETHDirectClient, OperatorSigner};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SignedCallResult {
     pub raw_tx: Vec<u8>,
+    /// Gas price of the legacy transaction or max fee per gas of the EIP-1559 transaction.
     pub gas_price: U256,
+    /// Max priority fee per gas, set only for the EIP-1559 transactions.
+    pub max_priority_fee_per_gas: Option<U256>,
     pub nonce: U256,
     pub hash: H256,
 }
 
+/// Fee parameters of the EIP-1559 (type 2) transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip1559Fees {
+    /// Maximum total fee per gas the sender is willing to pay (base fee included).
+    pub max_fee_per_gas: U256,
+    /// Maximum tip per gas for the block producer.
+    pub max_priority_fee_per_gas: U256,
+}
+
 /// State of the executed Ethereum transaction.
 #[derive(Debug, Clone)]
 pub struct ExecutedTxStatus {
@@ -30,6 +41,11 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Amount of gas used by the transaction.
+    pub gas_used: Option<U256>,
+    /// Price paid per unit of gas. For the nodes that don't support the London hardfork
+    /// it's the gas price of the executed transaction.
+    pub effective_gas_price: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -40,29 +56,61 @@ pub struct FailureInfo {
     pub gas_limit: U256,
 }
 
+/// Outcome of the call executed without creating a transaction.
+#[derive(Debug, Clone)]
+pub enum CallOutcome {
+    /// Call succeeded and returned the data.
+    Success(Vec<u8>),
+    /// Call reverted, so the transaction with the same data would fail.
+    Reverted(FailureInfo),
+}
+
 #[derive(Debug, Clone)]
 pub enum EthereumGateway {
-    Direct(ETHDirectClient<PrivateKeySigner>),
+    Direct(ETHDirectClient<OperatorSigner>),
     Multiplexed(MultiplexerEthereumClient),
     Mock(MockEthereum),
 }
 
 impl EthereumGateway {
     pub fn from_config(config: &ZkSyncConfig) -> Self {
+        Self::for_operator(
+            config,
+            config.eth_sender.sender.operator_commit_eth_addr,
+            config.eth_sender.sender.operator_private_key,
+        )
+    }
+
+    /// Creates a gateway sending the transactions from the given operator account.
+    /// `operator_private_key` is required only for the `PrivateKey` signer.
+    pub fn for_operator(
+        config: &ZkSyncConfig,
+        operator_eth_addr: Address,
+        operator_private_key: Option<H256>,
+    ) -> Self {
+        let signer = || {
+            OperatorSigner::for_account(&config.eth_sender, operator_eth_addr, operator_private_key)
+        };
+
         if config.eth_client.web3_url.len() == 1 {
             let transport = web3::transports::Http::new(&config.eth_client.web3_url()).unwrap();
 
             EthereumGateway::Direct(ETHDirectClient::new(
                 transport,
                 zksync_contract(),
-                config.eth_sender.sender.operator_commit_eth_addr,
-                PrivateKeySigner::new(config.eth_sender.sender.operator_private_key),
+                operator_eth_addr,
+                signer(),
                 config.contracts.contract_addr,
                 config.eth_client.chain_id,
                 config.eth_client.gas_price_factor,
             ))
         } else {
-            let mut client = MultiplexerEthereumClient::new();
+            assert!(
+                config.eth_client.read_quorum <= config.eth_client.web3_url.len(),
+                "Read quorum can't be greater than the amount of Ethereum endpoints"
+            );
+            let mut client = MultiplexerEthereumClient::new()
+                .with_options(MultiplexerOptions::from_config(&config.eth_client));
 
             let contract = zksync_contract();
             for web3_url in config.eth_client.web3_url.iter() {
@@ -72,8 +120,8 @@ impl EthereumGateway {
                     ETHDirectClient::new(
                         transport,
                         contract.clone(),
-                        config.eth_sender.sender.operator_commit_eth_addr,
-                        PrivateKeySigner::new(config.eth_sender.sender.operator_private_key),
+                        operator_eth_addr,
+                        signer(),
                         config.contracts.contract_addr,
                         config.eth_client.chain_id,
                         config.eth_client.gas_price_factor,
@@ -126,9 +174,20 @@ impl EthereumGateway {
         delegate_call!(self.block_number())
     }
 
+    /// Returns the hash of the block with the given number, or `None` if there is no such block yet.
+    pub async fn block_hash(&self, block_number: U64) -> Result<Option<H256>, anyhow::Error> {
+        delegate_call!(self.block_hash(block_number))
+    }
+
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.get_gas_price())
     }
+
+    /// Returns the base fee per gas of the latest block.
+    /// Fails if the network has not activated EIP-1559 yet.
+    pub async fn get_base_fee_per_gas(&self) -> Result<U256, anyhow::Error> {
+        delegate_call!(self.get_base_fee_per_gas())
+    }
     /// Returns the account balance.
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.sender_eth_balance())
@@ -155,6 +214,53 @@ impl EthereumGateway {
         delegate_call!(self.sign_prepared_tx_for_addr(data, contract_addr, options))
     }
 
+    /// Signs the EIP-1559 transaction given the previously encoded data.
+    /// Fills in gas/nonce if not supplied inside options, the gas price from options is ignored.
+    pub async fn sign_prepared_eip1559_tx(
+        &self,
+        data: Vec<u8>,
+        options: Options,
+        fees: Eip1559Fees,
+    ) -> Result<SignedCallResult, anyhow::Error> {
+        delegate_call!(self.sign_prepared_eip1559_tx(data, options, fees))
+    }
+
+    /// Signs the EIP-1559 transaction given the previously encoded data.
+    /// Fills in gas/nonce if not supplied inside options, the gas price from options is ignored.
+    pub async fn sign_prepared_eip1559_tx_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        fees: Eip1559Fees,
+    ) -> Result<SignedCallResult, anyhow::Error> {
+        delegate_call!(self.sign_prepared_eip1559_tx_for_addr(data, contract_addr, options, fees))
+    }
+
+    /// Executes the call to the main contract with the previously encoded data on behalf of
+    /// the sender account in the state of the given block, without creating a transaction.
+    /// Allows to check whether the transaction with the same data would succeed.
+    pub async fn call_with_data(
+        &self,
+        data: Vec<u8>,
+        options: Options,
+        block: BlockNumber,
+    ) -> Result<CallOutcome, anyhow::Error> {
+        delegate_call!(self.call_with_data(data, options, block))
+    }
+
+    /// Executes the call to the contract with the previously encoded data on behalf of
+    /// the sender account in the state of the given block, without creating a transaction.
+    pub async fn call_with_data_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        block: BlockNumber,
+    ) -> Result<CallOutcome, anyhow::Error> {
+        delegate_call!(self.call_with_data_for_addr(data, contract_addr, options, block))
+    }
+
     /// Sends the transaction to the Ethereum blockchain.
     /// Transaction is expected to be encoded as the byte sequence.
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
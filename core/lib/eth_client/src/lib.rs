@@ -1,5 +1,9 @@
 pub mod clients;
 pub mod ethereum_gateway;
+pub mod logs;
+pub mod operator_signer;
 pub use clients::http_client::ETHDirectClient;
-pub use clients::multiplexer::MultiplexerEthereumClient;
-pub use ethereum_gateway::{EthereumGateway, SignedCallResult};
+pub use clients::multiplexer::{MultiplexerEthereumClient, MultiplexerOptions};
+pub use ethereum_gateway::{CallOutcome, Eip1559Fees, EthereumGateway, SignedCallResult};
+pub use logs::{get_logs_chunked, is_rate_limit_error, ChunkedLogsRequest, LogsRequestOptions};
+pub use operator_signer::OperatorSigner;
@@ -0,0 +1,311 @@
+//! Requesting the logs from the large block ranges.
+//!
+//! Ethereum nodes (especially the hosted ones) limit either the block range of `eth_getLogs`
+//! or the amount of the returned logs. `ChunkedLogsRequest` splits the requested range into chunks,
+//! halving the chunk when the node rejects it and growing it back after the successful requests.
+//! Rate limit errors are returned right away, so the caller can back off,
+//! and other errors are retried with the exponential backoff.
+
+// Built-in deps
+use std::{future::Future, time::Duration};
+// External uses
+use web3::types::{BlockNumber, Filter, FilterBuilder, Log};
+
+/// Parts of the error messages returned by the Ethereum nodes when the requested
+/// block range or the amount of the logs is too large.
+const RANGE_LIMIT_ERRORS: &[&str] = &[
+    "query returned more than",
+    "response size exceeded",
+    "too many results",
+    "block range",
+    "range is too",
+    "is limited to",
+];
+
+/// Parts of the error messages returned by the Ethereum nodes when the rate limit is reached.
+const RATE_LIMIT_ERRORS: &[&str] = &["429 too many requests", "rate limit"];
+
+/// Options of the `ChunkedLogsRequest`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogsRequestOptions {
+    /// Maximum amount of blocks requested at once.
+    pub max_blocks_range: u64,
+    /// Amount of retries of the failed request. Range limit errors are not counted,
+    /// since they are handled by splitting the range, and rate limit errors are never retried.
+    pub max_retries: usize,
+    /// Delay before the first retry, doubled for each next one.
+    pub retry_delay: Duration,
+}
+
+impl Default for LogsRequestOptions {
+    fn default() -> Self {
+        Self {
+            max_blocks_range: 10_000,
+            max_retries: 5,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Returns `true` if the error means that the requested range must be reduced.
+pub fn is_range_limit_error(error: &anyhow::Error) -> bool {
+    // The whole chain is checked, since the error of the node may be wrapped with a context.
+    let message = format!("{:#}", error).to_lowercase();
+    RANGE_LIMIT_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Returns `true` if the error means that the rate limit of the Ethereum node is reached.
+pub fn is_rate_limit_error(error: &anyhow::Error) -> bool {
+    // The whole chain is checked, since the error of the node may be wrapped with a context.
+    let message = format!("{:#}", error).to_lowercase();
+    RATE_LIMIT_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Request of the logs matching the filter from the blocks `from_block..=to_block`,
+/// which are returned chunk by chunk in the block order.
+#[derive(Debug)]
+pub struct ChunkedLogsRequest<F> {
+    fetch: F,
+    filter: FilterBuilder,
+    next_block: u64,
+    to_block: u64,
+    blocks_range: u64,
+    options: LogsRequestOptions,
+}
+
+impl<F, Fut> ChunkedLogsRequest<F>
+where
+    F: Fn(Filter) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<Log>>>,
+{
+    /// Creates the request. `fetch` performs the single `eth_getLogs` call, and `filter`
+    /// is completed with the block range of each chunk.
+    pub fn new(
+        fetch: F,
+        filter: FilterBuilder,
+        from_block: u64,
+        to_block: u64,
+        options: LogsRequestOptions,
+    ) -> Self {
+        assert!(
+            options.max_blocks_range > 0,
+            "Blocks range of the logs request must be positive"
+        );
+
+        Self {
+            fetch,
+            filter,
+            next_block: from_block,
+            to_block,
+            blocks_range: options.max_blocks_range,
+            options,
+        }
+    }
+
+    /// Returns the logs from the next chunk of blocks, or `None` if all the blocks are processed.
+    pub async fn next_chunk(&mut self) -> anyhow::Result<Option<Vec<Log>>> {
+        if self.next_block > self.to_block {
+            return Ok(None);
+        }
+
+        let mut retries = 0;
+        loop {
+            let chunk_end = self
+                .next_block
+                .saturating_add(self.blocks_range - 1)
+                .min(self.to_block);
+            let filter = self
+                .filter
+                .clone()
+                .from_block(BlockNumber::Number(self.next_block.into()))
+                .to_block(BlockNumber::Number(chunk_end.into()))
+                .build();
+
+            match (self.fetch)(filter).await {
+                Ok(mut logs) => {
+                    logs.sort_by_key(|log| (log.block_number, log.log_index));
+                    self.next_block = chunk_end + 1;
+                    // The previous range limit may be caused by the amount of logs in the
+                    // particular blocks, so the range is restored gradually.
+                    self.blocks_range = self
+                        .blocks_range
+                        .saturating_mul(2)
+                        .min(self.options.max_blocks_range);
+                    return Ok(Some(logs));
+                }
+                Err(error) if is_range_limit_error(&error) && chunk_end > self.next_block => {
+                    self.blocks_range = (chunk_end - self.next_block + 1) / 2;
+                    vlog::debug!(
+                        "Logs request for blocks {}..={} is rejected ({}), reducing the range to {} blocks",
+                        self.next_block,
+                        chunk_end,
+                        error,
+                        self.blocks_range
+                    );
+                }
+                Err(error) if is_rate_limit_error(&error) => return Err(error),
+                Err(error) if retries < self.options.max_retries => {
+                    let delay = self.options.retry_delay * 2u32.pow(retries as u32);
+                    vlog::warn!(
+                        "Logs request for blocks {}..={} failed: {}. Retrying in {:?}",
+                        self.next_block,
+                        chunk_end,
+                        error,
+                        delay
+                    );
+                    retries += 1;
+                    tokio::time::delay_for(delay).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Collects the logs from all the remaining blocks.
+    pub async fn collect(mut self) -> anyhow::Result<Vec<Log>> {
+        let mut logs = Vec::new();
+        while let Some(chunk) = self.next_chunk().await? {
+            logs.extend(chunk);
+        }
+        Ok(logs)
+    }
+}
+
+/// Requests the logs matching the filter from the blocks `from_block..=to_block`
+/// with the default options. See `ChunkedLogsRequest` for details.
+pub async fn get_logs_chunked<F, Fut>(
+    fetch: F,
+    filter: FilterBuilder,
+    from_block: u64,
+    to_block: u64,
+) -> anyhow::Result<Vec<Log>>
+where
+    F: Fn(Filter) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<Log>>>,
+{
+    ChunkedLogsRequest::new(
+        fetch,
+        filter,
+        from_block,
+        to_block,
+        LogsRequestOptions::default(),
+    )
+    .collect()
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::sync::Mutex;
+
+    fn block_range(filter: &Filter) -> (u64, u64) {
+        let filter = serde_json::to_value(filter).unwrap();
+        let parse = |value: &Value| {
+            u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+        };
+        (parse(&filter["fromBlock"]), parse(&filter["toBlock"]))
+    }
+
+    fn log(block_number: u64) -> Log {
+        serde_json::from_value(json!({
+            "address": "0x0000000000000000000000000000000000000000",
+            "topics": [],
+            "data": "0x",
+            "blockNumber": format!("{:#x}", block_number),
+            "logIndex": "0x0",
+        }))
+        .unwrap()
+    }
+
+    /// Checks that the range is split when the node rejects it, and the transient errors are retried.
+    #[tokio::test]
+    async fn chunked_logs_request() {
+        let requests = Mutex::new(Vec::new());
+        let failed_once = Mutex::new(false);
+        let fetch = |filter: Filter| {
+            let (from, to) = block_range(&filter);
+            requests.lock().unwrap().push((from, to));
+            let result = if to - from + 1 > 4 {
+                Err(anyhow::format_err!(
+                    "query returned more than 10000 results"
+                ))
+            } else if from == 6 && !std::mem::replace(&mut *failed_once.lock().unwrap(), true) {
+                Err(anyhow::format_err!("connection reset"))
+            } else {
+                // Every block has a log, returned in the reverse order.
+                Ok((from..=to).rev().map(log).collect())
+            };
+            async move { result }
+        };
+
+        let options = LogsRequestOptions {
+            max_blocks_range: 10,
+            max_retries: 1,
+            retry_delay: Duration::from_millis(1),
+        };
+        let logs = ChunkedLogsRequest::new(fetch, FilterBuilder::default(), 0, 11, options)
+            .collect()
+            .await
+            .unwrap();
+
+        let blocks: Vec<_> = logs
+            .iter()
+            .map(|log| log.block_number.unwrap().as_u64())
+            .collect();
+        assert_eq!(blocks, (0..=11).collect::<Vec<_>>());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                (0, 9),
+                (0, 4),
+                (0, 1),
+                (2, 5),
+                (6, 11),
+                (6, 8),
+                (6, 8),
+                (9, 11)
+            ]
+        );
+    }
+
+    /// Checks that the rate limit errors are returned without retries.
+    #[tokio::test]
+    async fn rate_limit_error_is_not_retried() {
+        let requests = Mutex::new(0);
+        let fetch = |_filter: Filter| {
+            *requests.lock().unwrap() += 1;
+            async { Err(anyhow::format_err!("429 Too Many Requests")) }
+        };
+
+        let options = LogsRequestOptions {
+            max_blocks_range: 10,
+            max_retries: 5,
+            retry_delay: Duration::from_millis(1),
+        };
+        let result = ChunkedLogsRequest::new(fetch, FilterBuilder::default(), 0, 11, options)
+            .collect()
+            .await;
+
+        assert!(is_rate_limit_error(&result.unwrap_err()));
+        assert_eq!(*requests.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn range_limit_errors() {
+        assert!(is_range_limit_error(&anyhow::format_err!(
+            "Rpc error: query returned more than 10000 results"
+        )));
+        assert!(is_range_limit_error(&anyhow::format_err!(
+            "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range"
+        )));
+        assert!(!is_range_limit_error(&anyhow::format_err!(
+            "429 Too Many Requests"
+        )));
+    }
+}
@@ -0,0 +1,100 @@
+// Built-in deps
+use std::time::Duration;
+// External uses
+use async_trait::async_trait;
+// Workspace uses
+use zksync_config::{configs::eth_sender::OperatorSignerType, ETHSenderConfig};
+use zksync_eth_signer::{
+    error::SignerError, EthereumSigner, PrivateKeySigner, RawTransaction, RemoteSigner,
+};
+use zksync_types::{tx::TxEthSignature, Address, H256};
+
+/// Signer of the operator transactions, selected by the `signer_type` option
+/// of the Ethereum sender config.
+#[derive(Debug, Clone)]
+pub enum OperatorSigner {
+    /// Signs with the private key from the config.
+    PrivateKey(PrivateKeySigner),
+    /// Delegates signing to the remote signing service, so the key is never exposed to the server.
+    Remote(RemoteSigner),
+}
+
+impl OperatorSigner {
+    /// Creates a signer for the main operator account.
+    pub fn from_config(config: &ETHSenderConfig) -> Self {
+        Self::for_account(
+            config,
+            config.sender.operator_commit_eth_addr,
+            config.sender.operator_private_key,
+        )
+    }
+
+    /// Creates a signer for an arbitrary operator account (e.g. one of the additional
+    /// operator accounts). `private_key` is required only for the `PrivateKey` signer.
+    pub fn for_account(
+        config: &ETHSenderConfig,
+        address: Address,
+        private_key: Option<H256>,
+    ) -> Self {
+        let sender = &config.sender;
+        match sender.signer_type {
+            OperatorSignerType::PrivateKey => {
+                let private_key = private_key.unwrap_or_else(|| {
+                    panic!(
+                        "Private key of the operator account {:?} is required for the `PrivateKey` signer",
+                        address
+                    )
+                });
+                Self::PrivateKey(PrivateKeySigner::new(private_key))
+            }
+            OperatorSignerType::Remote => {
+                let url = sender
+                    .remote_signer_url
+                    .clone()
+                    .expect("Remote signer URL is required for the `Remote` signer");
+                Self::Remote(RemoteSigner::new(
+                    url,
+                    address,
+                    sender.remote_signer_auth_token.clone(),
+                    Duration::from_secs(sender.remote_signer_timeout),
+                ))
+            }
+        }
+    }
+}
+
+impl From<PrivateKeySigner> for OperatorSigner {
+    fn from(signer: PrivateKeySigner) -> Self {
+        Self::PrivateKey(signer)
+    }
+}
+
+impl From<RemoteSigner> for OperatorSigner {
+    fn from(signer: RemoteSigner) -> Self {
+        Self::Remote(signer)
+    }
+}
+
+#[async_trait]
+impl EthereumSigner for OperatorSigner {
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_message(message).await,
+            Self::Remote(signer) => signer.sign_message(message).await,
+        }
+    }
+
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_transaction(raw_tx).await,
+            Self::Remote(signer) => signer.sign_transaction(raw_tx).await,
+        }
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.get_address().await,
+            Self::Remote(signer) => signer.get_address().await,
+        }
+    }
+}
@@ -288,7 +288,7 @@ impl JsonRpcSigner {
 }
 
 mod messages {
-    use crate::RawTransaction;
+    use crate::{raw_ethereum_tx::EIP1559_TX_TYPE, RawTransaction};
     use hex::encode;
     use zksync_types::Address;
 
@@ -341,27 +341,29 @@ mod messages {
         pub fn sign_transaction(from: Address, tx_data: RawTransaction) -> Self {
             let mut params = Vec::new();
 
+            let mut tx = serde_json::json!({
+                "from": serde_json::to_value(from).expect("serialization fail"),
+                "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
+                "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
+                "data": serde_json::to_value(format!("0x{}", encode(&tx_data.data))).expect("serialization fail"),
+                "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
+            });
             // Parameter `To` is optional, so we add it only if it is not None
-            let tx = if let Some(to) = tx_data.to {
-                serde_json::json!({
-                    "from": serde_json::to_value(from).expect("serialization fail"),
-                    "to": serde_json::to_value(to).expect("serialization fail"),
-                    "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
-                    "gasPrice": serde_json::to_value(tx_data.gas_price).expect("serialization fail"),
-                    "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
-                    "data": serde_json::to_value(format!("0x{}", encode(tx_data.data))).expect("serialization fail"),
-                    "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
-                })
+            if let Some(to) = tx_data.to {
+                tx["to"] = serde_json::to_value(to).expect("serialization fail");
+            }
+            // EIP-1559 transactions are signed as type-2 transactions with the fee caps
+            // instead of the gas price.
+            if let Some((max_fee_per_gas, max_priority_fee_per_gas)) = tx_data.eip1559_fees() {
+                tx["type"] = format!("{:#x}", EIP1559_TX_TYPE).into();
+                tx["maxFeePerGas"] =
+                    serde_json::to_value(max_fee_per_gas).expect("serialization fail");
+                tx["maxPriorityFeePerGas"] =
+                    serde_json::to_value(max_priority_fee_per_gas).expect("serialization fail");
             } else {
-                serde_json::json!({
-                    "from": serde_json::to_value(from).expect("serialization fail"),
-                    "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
-                    "gasPrice": serde_json::to_value(tx_data.gas_price).expect("serialization fail"),
-                    "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
-                    "data": serde_json::to_value(format!("0x{}", encode(tx_data.data))).expect("serialization fail"),
-                    "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
-                })
-            };
+                tx["gasPrice"] =
+                    serde_json::to_value(tx_data.gas_price).expect("serialization fail");
+            }
             params.push(tx);
             Self::create("eth_signTransaction", params)
         }
@@ -488,6 +490,8 @@ mod tests {
                 gas_price: Default::default(),
                 gas: Default::default(),
                 data: vec![],
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
             })
             .await
             .unwrap();
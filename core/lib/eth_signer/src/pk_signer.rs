@@ -51,7 +51,8 @@ impl EthereumSigner for PrivateKeySigner {
 mod test {
     use super::PrivateKeySigner;
     use super::RawTransaction;
-    use crate::EthereumSigner;
+    use crate::{raw_ethereum_tx::EIP1559_TX_TYPE, EthereumSigner};
+    use parity_crypto::publickey::{public_to_address, recover, Signature};
     use zksync_types::{H160, H256, U256};
 
     #[tokio::test]
@@ -66,6 +67,8 @@ mod test {
             gas_price: U256::from(1),
             gas: U256::from(2),
             data: vec![1, 2, 3],
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
         };
         let signature = signer
             .sign_transaction(raw_transaction.clone())
@@ -82,4 +85,48 @@ mod test {
         ];
         assert_eq!(signature, precalculated_signature);
     }
+
+    #[tokio::test]
+    async fn test_generating_eip1559_signature() {
+        let private_key = H256::from([5; 32]);
+        let signer = PrivateKeySigner::new(private_key);
+        let raw_transaction = RawTransaction {
+            chain_id: 1,
+            nonce: U256::from(1),
+            to: Some(H160::zero()),
+            value: U256::from(10),
+            gas_price: U256::zero(),
+            gas: U256::from(2),
+            data: vec![1, 2, 3],
+            max_fee_per_gas: Some(U256::from(4)),
+            max_priority_fee_per_gas: Some(U256::from(3)),
+        };
+        let signed_tx = signer
+            .sign_transaction(raw_transaction.clone())
+            .await
+            .unwrap();
+
+        // Typed transaction envelope: type byte followed by the RLP list.
+        assert_eq!(signed_tx[0], EIP1559_TX_TYPE);
+        let rlp = rlp::Rlp::new(&signed_tx[1..]);
+        assert_eq!(rlp.item_count().unwrap(), 12);
+        assert_eq!(rlp.val_at::<u8>(0).unwrap(), 1);
+        assert_eq!(rlp.val_at::<U256>(2).unwrap(), U256::from(3));
+        assert_eq!(rlp.val_at::<U256>(3).unwrap(), U256::from(4));
+        assert_eq!(rlp.at(8).unwrap().item_count().unwrap(), 0);
+
+        // Signature must be made by the signer key over the typed transaction hash.
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        let r_bytes = rlp.val_at::<Vec<u8>>(10).unwrap();
+        let s_bytes = rlp.val_at::<Vec<u8>>(11).unwrap();
+        r[32 - r_bytes.len()..].copy_from_slice(&r_bytes);
+        s[32 - s_bytes.len()..].copy_from_slice(&s_bytes);
+        let signature = Signature::from_rsv(&r.into(), &s.into(), rlp.val_at(9).unwrap());
+        let public_key = recover(&signature, &raw_transaction.hash().into()).unwrap();
+        assert_eq!(
+            public_to_address(&public_key).as_bytes(),
+            signer.get_address().await.unwrap().as_bytes()
+        );
+    }
 }
@@ -21,8 +21,25 @@ pub struct RawTransaction {
     pub gas: U256,
     /// Input data
     pub data: Vec<u8>,
+    /// Max fee per gas (EIP-1559 transactions only)
+    #[serde(
+        rename = "maxFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas (EIP-1559 transactions only)
+    #[serde(
+        rename = "maxPriorityFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_priority_fee_per_gas: Option<U256>,
 }
 
+/// Type of the EIP-1559 transactions as defined in EIP-2718.
+pub const EIP1559_TX_TYPE: u8 = 2;
+
 fn find_first_nonzero(vector: &[u8]) -> usize {
     let mut result: usize = 0;
     for el in vector {
@@ -37,7 +54,22 @@ fn find_first_nonzero(vector: &[u8]) -> usize {
 }
 
 impl RawTransaction {
+    /// Returns the fee parameters `(max_fee_per_gas, max_priority_fee_per_gas)`
+    /// if the transaction is an EIP-1559 one. Otherwise `gas_price` is used.
+    pub fn eip1559_fees(&self) -> Option<(U256, U256)> {
+        match (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+            (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
+                Some((max_fee_per_gas, max_priority_fee_per_gas))
+            }
+            _ => None,
+        }
+    }
+
     pub fn rlp_encode_tx(&self, sig: Signature) -> Vec<u8> {
+        if self.eip1559_fees().is_some() {
+            return self.rlp_encode_eip1559_tx(sig);
+        }
+
         let signature = to_ecdsa(sig, self.chain_id);
         let mut tx = RlpStream::new();
         tx.begin_unbounded_list();
@@ -53,7 +85,35 @@ impl RawTransaction {
         tx.out()
     }
 
+    /// Encodes the signed EIP-1559 transaction as `0x02 || rlp([fields..., y_parity, r, s])`.
+    fn rlp_encode_eip1559_tx(&self, sig: Signature) -> Vec<u8> {
+        let mut tx = RlpStream::new();
+        tx.begin_unbounded_list();
+        self.encode_eip1559(&mut tx);
+        tx.append(&sig.v());
+        let r_start = find_first_nonzero(sig.r());
+        tx.append(&&sig.r()[r_start..]);
+        let s_start = find_first_nonzero(sig.s());
+        tx.append(&&sig.s()[s_start..]);
+        tx.finalize_unbounded_list();
+
+        let mut encoded = vec![EIP1559_TX_TYPE];
+        encoded.extend(tx.out());
+        encoded
+    }
+
     pub fn hash(&self) -> [u8; 32] {
+        if self.eip1559_fees().is_some() {
+            let mut hash = RlpStream::new();
+            hash.begin_unbounded_list();
+            self.encode_eip1559(&mut hash);
+            hash.finalize_unbounded_list();
+
+            let mut payload = vec![EIP1559_TX_TYPE];
+            payload.extend(hash.out());
+            return payload.keccak256();
+        }
+
         let mut hash = RlpStream::new();
         hash.begin_unbounded_list();
         self.encode(&mut hash);
@@ -68,6 +128,25 @@ impl RawTransaction {
         s.append(&self.nonce);
         s.append(&self.gas_price);
         s.append(&self.gas);
+        self.encode_call(s);
+    }
+
+    /// Encodes the unsigned fields of the EIP-1559 transaction with an empty access list.
+    pub fn encode_eip1559(&self, s: &mut RlpStream) {
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self
+            .eip1559_fees()
+            .expect("EIP-1559 fees must be set for the EIP-1559 transaction");
+
+        s.append(&self.chain_id);
+        s.append(&self.nonce);
+        s.append(&max_priority_fee_per_gas);
+        s.append(&max_fee_per_gas);
+        s.append(&self.gas);
+        self.encode_call(s);
+        s.begin_list(0);
+    }
+
+    fn encode_call(&self, s: &mut RlpStream) {
         if let Some(ref t) = self.to {
             s.append(t);
         } else {
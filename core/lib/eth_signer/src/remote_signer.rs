@@ -0,0 +1,442 @@
+//! Signer delegating signing to the remote signing service (e.g. a proxy in front of
+//! the KMS or HSM), so the private key is never exposed to the server.
+//!
+//! Service is expected to speak the following JSON protocol:
+//!
+//! - `POST /sign/message`: `{ "address": "0x..", "message": "0x.." }`.
+//!   The service signs the message with the Ethereum prefix (same as `eth_sign`).
+//! - `POST /sign/transaction`: `{ "address": "0x..", "transaction": { "chainId": 1, "nonce": "0x..",
+//!   "to": "0x..", "value": "0x..", "gas": "0x..", "gasPrice": "0x..", "data": "0x.." } }`.
+//!   EIP-1559 transactions have `maxFeePerGas` and `maxPriorityFeePerGas` instead of `gasPrice`.
+//!   The service signs the transaction hash.
+//!
+//! Both endpoints respond with `{ "signature": "0x.." }`, where signature is 65 bytes `r || s || v`.
+//! If the auth token is configured, it's sent in the `Authorization: Bearer <token>` header.
+//!
+//! Signer verifies that every received signature was made by the expected address,
+//! and RLP-encodes the signed transactions by itself.
+
+// Built-in deps
+use std::{fmt, time::Duration};
+// External deps
+use parity_crypto::publickey::{public_to_address, recover, Signature};
+use zksync_types::{
+    tx::{PackedEthSignature, TxEthSignature},
+    Address, H256,
+};
+// Local deps
+use self::messages::{SignMessageRequest, SignTransactionRequest, SignatureResponse};
+use crate::{
+    error::SignerError, json_rpc_signer::is_signature_from_address, EthereumSigner, RawTransaction,
+};
+
+#[derive(Clone)]
+pub struct RemoteSigner {
+    url: String,
+    address: Address,
+    auth_token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // We do not want to have an auth token in the debug representation.
+        f.debug_struct("RemoteSigner")
+            .field("url", &self.url)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the `address` account managed by the signing service at `url`.
+    pub fn new(
+        url: impl Into<String>,
+        address: Address,
+        auth_token: Option<String>,
+        timeout: Duration,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Unable to create the HTTP client");
+
+        Self {
+            url: url.into().trim_end_matches('/').to_owned(),
+            address,
+            auth_token,
+            client,
+        }
+    }
+
+    /// Performs a POST query to the signing service endpoint and returns the received signature.
+    async fn request_signature(
+        &self,
+        endpoint: &str,
+        message: impl serde::Serialize,
+    ) -> Result<[u8; 65], SignerError> {
+        let mut request = self
+            .client
+            .post(&format!("{}/{}", self.url, endpoint))
+            .json(&message);
+        if let Some(auth_token) = &self.auth_token {
+            request = request.bearer_auth(auth_token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if response.status() != reqwest::StatusCode::OK {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(SignerError::SigningFailed(format!(
+                "Remote signer responded with a non-OK response: {}: {}",
+                status, body
+            )));
+        }
+
+        let response: SignatureResponse = response
+            .json()
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        response.signature()
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    /// The sign method calculates an Ethereum specific signature with:
+    /// sign(keccak256("\x19Ethereum Signed Message:\n" + len(message) + message))).
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let request = SignMessageRequest::new(self.address, message);
+        let signature = self.request_signature("sign/message", request).await?;
+
+        let signature = PackedEthSignature::deserialize_packed(&signature)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if is_signature_from_address(&signature, message, self.address)? {
+            Ok(TxEthSignature::EthereumSignature(signature))
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ))
+        }
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let request = SignTransactionRequest::new(self.address, &raw_tx);
+        let signature = self.request_signature("sign/transaction", request).await?;
+
+        // Both the legacy (27/28) and the raw (0/1) recovery ids are accepted.
+        let (r, s, v) = (&signature[..32], &signature[32..64], signature[64]);
+        let v = if v >= 27 { v - 27 } else { v };
+        let signature = Signature::from_rsv(&H256::from_slice(r), &H256::from_slice(s), v);
+
+        let signer = recover(&signature, &raw_tx.hash().into())
+            .map(|public| public_to_address(&public))
+            .map_err(|err| SignerError::RecoverAddress(err.to_string()))?;
+        if signer.as_bytes() != self.address.as_bytes() {
+            return Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ));
+        }
+
+        Ok(raw_tx.rlp_encode_tx(signature))
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+mod messages {
+    use crate::{error::SignerError, RawTransaction};
+    use hex::encode;
+    use zksync_types::{Address, H160, U256};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct SignMessageRequest {
+        pub address: Address,
+        pub message: String,
+    }
+
+    impl SignMessageRequest {
+        pub fn new(address: Address, message: &[u8]) -> Self {
+            Self {
+                address,
+                message: format!("0x{}", encode(message)),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct TransactionData {
+        pub chain_id: u8,
+        pub nonce: U256,
+        pub to: Option<H160>,
+        pub value: U256,
+        pub gas: U256,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub gas_price: Option<U256>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_fee_per_gas: Option<U256>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_priority_fee_per_gas: Option<U256>,
+        pub data: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct SignTransactionRequest {
+        pub address: Address,
+        pub transaction: TransactionData,
+    }
+
+    impl SignTransactionRequest {
+        pub fn new(address: Address, tx: &RawTransaction) -> Self {
+            let (gas_price, max_fee_per_gas, max_priority_fee_per_gas) = match tx.eip1559_fees() {
+                Some((max_fee, priority_fee)) => (None, Some(max_fee), Some(priority_fee)),
+                None => (Some(tx.gas_price), None, None),
+            };
+
+            Self {
+                address,
+                transaction: TransactionData {
+                    chain_id: tx.chain_id,
+                    nonce: tx.nonce,
+                    to: tx.to,
+                    value: tx.value,
+                    gas: tx.gas,
+                    gas_price,
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                    data: format!("0x{}", encode(&tx.data)),
+                },
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct SignatureResponse {
+        pub signature: String,
+    }
+
+    impl SignatureResponse {
+        /// Decodes the 65-byte `r || s || v` signature.
+        pub fn signature(&self) -> Result<[u8; 65], SignerError> {
+            let bytes = hex::decode(self.signature.trim_start_matches("0x"))
+                .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+            if bytes.len() != 65 {
+                return Err(SignerError::SigningFailed(format!(
+                    "Signature must be 65 bytes long, got {}",
+                    bytes.len()
+                )));
+            }
+
+            let mut signature = [0u8; 65];
+            signature.copy_from_slice(&bytes);
+            Ok(signature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use actix_web::{post, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+    use futures::future::{AbortHandle, Abortable};
+    use parity_crypto::publickey::{sign, Generator, KeyPair, Random};
+    use serde_json::json;
+
+    use zksync_types::{tx::TxEthSignature, H256};
+
+    use super::messages::{SignMessageRequest, SignTransactionRequest};
+    use crate::{
+        json_rpc_signer::is_signature_from_address, raw_ethereum_tx::EIP1559_TX_TYPE,
+        EthereumSigner, PrivateKeySigner, RawTransaction, RemoteSigner,
+    };
+
+    const AUTH_TOKEN: &str = "secret";
+
+    /// Local stand-in for the signing service, which signs with the key from the state.
+    #[derive(Clone)]
+    struct State {
+        key_pair: KeyPair,
+    }
+
+    impl State {
+        fn signer(&self) -> PrivateKeySigner {
+            PrivateKeySigner::new(H256::from_slice(self.key_pair.secret().as_bytes()))
+        }
+    }
+
+    fn is_authorized(req: &HttpRequest) -> bool {
+        req.headers()
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            == Some(format!("Bearer {}", AUTH_TOKEN).as_str())
+    }
+
+    #[post("/sign/message")]
+    async fn sign_message(
+        req: HttpRequest,
+        body: web::Json<SignMessageRequest>,
+        state: web::Data<State>,
+    ) -> impl Responder {
+        if !is_authorized(&req) {
+            return HttpResponse::Unauthorized().finish();
+        }
+
+        let message = hex::decode(&body.message[2..]).unwrap();
+        let signature = match state.signer().sign_message(&message).await.unwrap() {
+            TxEthSignature::EthereumSignature(signature) => signature,
+            _ => unreachable!(),
+        };
+        HttpResponse::Ok().json(json!({
+            "signature": format!("0x{}", hex::encode(signature.serialize_packed().as_ref()))
+        }))
+    }
+
+    #[post("/sign/transaction")]
+    async fn sign_transaction(
+        req: HttpRequest,
+        body: web::Json<SignTransactionRequest>,
+        state: web::Data<State>,
+    ) -> impl Responder {
+        if !is_authorized(&req) {
+            return HttpResponse::Unauthorized().finish();
+        }
+
+        let tx = &body.transaction;
+        let raw_tx = RawTransaction {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            to: tx.to,
+            value: tx.value,
+            gas_price: tx.gas_price.unwrap_or_default(),
+            gas: tx.gas,
+            data: hex::decode(&tx.data[2..]).unwrap(),
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+        };
+        let signature = sign(state.key_pair.secret(), &raw_tx.hash().into()).unwrap();
+        HttpResponse::Ok().json(json!({
+            "signature": format!("0x{}", hex::encode(&signature[..]))
+        }))
+    }
+
+    fn run_server(state: State) -> (String, AbortHandle) {
+        let mut url = None;
+        let mut server = None;
+        for i in 9000..9999 {
+            let new_url = format!("127.0.0.1:{}", i);
+            // Try to bind to some port, hope that 999 variants will be enough
+            let tmp_state = state.clone();
+            if let Ok(ser) = HttpServer::new(move || {
+                App::new()
+                    .data(tmp_state.clone())
+                    .service(sign_message)
+                    .service(sign_transaction)
+            })
+            .bind(new_url.clone())
+            {
+                server = Some(ser);
+                url = Some(new_url);
+                break;
+            }
+        }
+
+        let server = server.expect("Could not bind to port from 9000 to 9999");
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let future = Abortable::new(server.run(), abort_registration);
+        tokio::spawn(future);
+        let address = format!("http://{}/", &url.unwrap());
+        (address, abort_handle)
+    }
+
+    fn raw_transaction() -> RawTransaction {
+        RawTransaction {
+            chain_id: 9,
+            nonce: 1.into(),
+            to: Some([3; 20].into()),
+            value: 0.into(),
+            gas_price: 10.into(),
+            gas: 100_000.into(),
+            data: vec![1, 2, 3],
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        }
+    }
+
+    /// Checks that messages and both legacy and EIP-1559 transactions signed by the remote
+    /// service are the same as the ones signed with the private key directly.
+    #[actix_rt::test]
+    async fn remote_signer() {
+        let state = State {
+            key_pair: Random.generate(),
+        };
+        let pk_signer = state.signer();
+        let address = state.key_pair.address();
+        let (url, abort_handle) = run_server(state);
+
+        let signer = RemoteSigner::new(
+            url,
+            address,
+            Some(AUTH_TOKEN.to_owned()),
+            Duration::from_secs(5),
+        );
+        assert_eq!(signer.get_address().await.unwrap(), address);
+
+        let msg = b"some_text_message";
+        if let TxEthSignature::EthereumSignature(signature) =
+            signer.sign_message(msg).await.unwrap()
+        {
+            assert!(is_signature_from_address(&signature, msg, address).unwrap())
+        } else {
+            panic!("Wrong signature type")
+        }
+
+        let legacy_tx = raw_transaction();
+        let mut eip1559_tx = raw_transaction();
+        eip1559_tx.max_fee_per_gas = Some(20.into());
+        eip1559_tx.max_priority_fee_per_gas = Some(2.into());
+
+        for tx in vec![legacy_tx, eip1559_tx.clone()] {
+            assert_eq!(
+                signer.sign_transaction(tx.clone()).await.unwrap(),
+                pk_signer.sign_transaction(tx).await.unwrap()
+            );
+        }
+        let signed_eip1559_tx = signer.sign_transaction(eip1559_tx).await.unwrap();
+        assert_eq!(signed_eip1559_tx[0], EIP1559_TX_TYPE);
+
+        abort_handle.abort();
+    }
+
+    /// Checks that signatures made by another account and unauthorized requests are rejected.
+    #[actix_rt::test]
+    async fn remote_signer_errors() {
+        let (url, abort_handle) = run_server(State {
+            key_pair: Random.generate(),
+        });
+
+        // Signing service signs with a key that doesn't match the expected address.
+        let other_address = Random.generate().address();
+        let signer = RemoteSigner::new(
+            url.clone(),
+            other_address,
+            Some(AUTH_TOKEN.to_owned()),
+            Duration::from_secs(5),
+        );
+        assert!(signer.sign_message(b"message").await.is_err());
+        assert!(signer.sign_transaction(raw_transaction()).await.is_err());
+
+        // Auth token is missing.
+        let signer = RemoteSigner::new(url, other_address, None, Duration::from_secs(5));
+        assert!(signer.sign_transaction(raw_transaction()).await.is_err());
+
+        abort_handle.abort();
+    }
+}
@@ -1,10 +1,19 @@
-//! Generate exit proof for exodus mode given account and token
-//! correct verified state should be present in the db (could be restored using `data-restore` module)
+//! Generate exit proof for exodus mode given account and token, along with the calldata of
+//! the zkSync contract exodus function.
+//!
+//! The state is restored from the db (could be restored using `data-restore` module), or from
+//! the state snapshot file, which can be exported from the db with the `--export_snapshot` option,
+//! so the proofs can be generated without the db access.
 
 use serde::Serialize;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Instant;
 use structopt::StructOpt;
 use zksync_crypto::proof::EncodedSingleProof;
+use zksync_prover_utils::exit_proof::{
+    create_exit_proof, perform_exodus_calldata, ExodusStateSnapshot,
+};
 use zksync_storage::ConnectionPool;
 use zksync_types::{AccountId, Address, TokenId, TokenLike};
 use zksync_utils::BigUintSerdeWrapper;
@@ -16,6 +25,8 @@ struct ExitProofData {
     account_address: Address,
     amount: BigUintSerdeWrapper,
     proof: EncodedSingleProof,
+    /// Calldata of the `performExodus` function of the zkSync contract.
+    calldata: String,
 }
 
 #[derive(StructOpt)]
@@ -27,61 +38,129 @@ struct ExitProofData {
 struct Opt {
     /// Account id of the account
     #[structopt(long)]
-    account_id: String,
+    account_id: Option<String>,
 
-    /// Token to withdraw - "ETH" or address of the ERC20 token
+    /// Address of the account, can be used instead of the account id
     #[structopt(long)]
-    token: String,
-}
+    address: Option<String>,
 
-#[tokio::main]
-async fn main() {
-    vlog::init();
+    /// Token to withdraw - "ETH", symbol, id or address of the ERC20 token
+    #[structopt(long)]
+    token: Option<String>,
 
-    let opt = Opt::from_args();
+    /// Path to the state snapshot file to restore the state from instead of the db
+    #[structopt(long, parse(from_os_str))]
+    snapshot: Option<PathBuf>,
 
-    let account_id = opt.account_id.parse::<AccountId>().unwrap();
-    let token = TokenLike::parse(&opt.token);
+    /// Path to export the state snapshot restored from the db to
+    #[structopt(long, parse(from_os_str))]
+    export_snapshot: Option<PathBuf>,
+}
 
-    let timer = Instant::now();
-    vlog::info!("Restoring state from db");
+async fn load_snapshot_from_db() -> ExodusStateSnapshot {
     let connection_pool = ConnectionPool::new(Some(1));
     let mut storage = connection_pool
         .access_storage()
         .await
         .expect("Storage access failed");
 
-    let token_id = storage
+    let (block_number, accounts) = storage
+        .chain()
+        .state_schema()
+        .load_verified_state()
+        .await
+        .expect("Failed to load verified state");
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await
+        .expect("DB access fail")
+        .expect("Last verified block not found in the db");
+    let tokens = storage
         .tokens_schema()
-        .get_token(token)
+        .load_tokens()
         .await
-        .expect("Db access fail")
+        .expect("DB access fail")
+        .into_iter()
+        .map(|(_, token)| token)
+        .collect();
+
+    ExodusStateSnapshot {
+        block,
+        accounts: accounts.into_iter().collect(),
+        tokens,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    vlog::init();
+
+    let opt = Opt::from_args();
+
+    let timer = Instant::now();
+    let snapshot = match &opt.snapshot {
+        Some(path) => {
+            vlog::info!("Restoring state from snapshot");
+            ExodusStateSnapshot::load(path).expect("Failed to load state snapshot")
+        }
+        None => {
+            vlog::info!("Restoring state from db");
+            load_snapshot_from_db().await
+        }
+    };
+    vlog::info!(
+        "Restored state after the block {}: {} s",
+        snapshot.block.block_number,
+        timer.elapsed().as_secs()
+    );
+
+    if let Some(path) = &opt.export_snapshot {
+        snapshot.save(path).expect("Failed to save state snapshot");
+        vlog::info!("State snapshot saved to {}", path.display());
+        if opt.token.is_none() {
+            return;
+        }
+    }
+
+    let account_id = opt
+        .account_id
+        .as_ref()
+        .map(|id| id.parse::<AccountId>().expect("Invalid account id"));
+    let address = opt.address.as_ref().map(|address| {
+        let address = address.strip_prefix("0x").unwrap_or(address);
+        Address::from_str(address).expect("Invalid account address")
+    });
+    if account_id.is_none() && address.is_none() {
+        panic!("Either account id or address of the account should be provided");
+    }
+    let (account_id, address) = snapshot
+        .find_account(account_id, address)
+        .map(|(id, account)| (id, account.address))
+        .expect("Account not found in the state");
+
+    let token = TokenLike::parse(opt.token.as_ref().expect("Token should be provided"));
+    let token_id = snapshot
+        .find_token(&token)
         .expect(
             "Token not found. If you're addressing an ERC-20 token by it's symbol, \
                   it may not be available after data restore. Try using token address in that case",
         )
         .id;
-    let address = storage
-        .chain()
-        .account_schema()
-        .last_verified_state_for_account(account_id)
-        .await
-        .expect("DB access fail")
-        .expect("Account not found in the db")
-        .address;
-    let accounts = storage
-        .chain()
-        .state_schema()
-        .load_verified_state()
-        .await
-        .expect("Failed to load verified state")
-        .1;
-
-    vlog::info!("Restored state from db: {} s", timer.elapsed().as_secs());
 
-    let (proof, amount) =
-        zksync_prover_utils::exit_proof::create_exit_proof(accounts, account_id, address, token_id)
-            .expect("Failed to generate exit proof");
+    // The proof is verified with the exodus verification key once created.
+    let (proof, amount) = create_exit_proof(snapshot.account_map(), account_id, address, token_id)
+        .expect("Failed to generate exit proof");
+    let calldata = perform_exodus_calldata(
+        &snapshot.block,
+        address,
+        account_id,
+        token_id,
+        &amount,
+        &proof,
+    )
+    .expect("Failed to encode exodus calldata");
 
     let proof_data = ExitProofData {
         token_id,
@@ -89,12 +168,14 @@ async fn main() {
         account_address: address,
         amount: amount.into(),
         proof,
+        calldata: format!("0x{}", hex::encode(calldata)),
     };
 
     println!("\n\n");
     println!("==========================");
     println!("Generating proof completed");
     println!("Below you can see the input data for the exit transaction on zkSync contract");
+    println!("The calldata can be sent to the zkSync contract as is, it's valid as long as the block {} is the last executed one", snapshot.block.block_number);
     println!("Look up the manuals of your desired smart wallet in order to know how to sign and send this transaction to the Ethereum");
     println!("==========================");
 
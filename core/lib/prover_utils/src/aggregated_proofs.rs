@@ -47,6 +47,46 @@ pub fn prepare_proof_data(
     (all_vks, single_proof_data)
 }
 
+/// Verifies the aggregated proof: inputs and verification key indexes of the aggregated proofs are expected
+/// to match the given single proofs (the rest are the padding ones), and the recursive proof is expected
+/// to be valid for the recursive circuit of the aggregated proof size.
+pub fn verify_aggregated_proof(
+    proof: &AggregatedProof,
+    single_proofs: &[(SingleProof, usize)],
+    available_chunks: &[usize],
+) -> anyhow::Result<bool> {
+    let aggregated_proof_size = proof.individual_vk_inputs.len();
+    if aggregated_proof_size < single_proofs.len()
+        || proof.individual_vk_idxs.len() != aggregated_proof_size
+    {
+        return Ok(false);
+    }
+
+    let individual_inputs = proof
+        .individual_vk_inputs
+        .iter()
+        .zip(proof.individual_vk_idxs.iter());
+    for ((single_proof, block_size), (input, vk_idx)) in single_proofs.iter().zip(individual_inputs)
+    {
+        let expected_vk_idx = available_chunks
+            .iter()
+            .position(|chunks| chunks == block_size);
+        if single_proof.0.input_values != [*input] || expected_vk_idx != Some(*vk_idx) {
+            return Ok(false);
+        }
+    }
+
+    let vk_for_recursive_circuit = VkAggregate::read(File::open(
+        get_recursive_verification_key_path(aggregated_proof_size),
+    )?)?;
+    let valid = verify::<_, _, RollingKeccakTranscript<<Engine as ScalarEngine>::Fr>>(
+        &vk_for_recursive_circuit,
+        &proof.proof,
+        None,
+    )?;
+    Ok(valid)
+}
+
 pub fn gen_aggregate_proof(
     single_vks: Vec<Vk>,
     proofs: Vec<SingleProofData>,
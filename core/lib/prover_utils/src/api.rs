@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::BlockNumber;
 use zksync_circuit::serialization::ProverData;
@@ -13,6 +14,24 @@ pub struct ProverInputRequest {
 pub struct ProverInputRequestAuxData {
     pub prefer_aggregated_proof: Option<bool>,
     pub preferred_block_size: Option<usize>,
+    /// Version of the prover application.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Jobs the prover is able to perform. Provers without capabilities are given any job.
+    #[serde(default)]
+    pub capabilities: Option<ProverCapabilities>,
+}
+
+/// Hard capabilities of the prover. Unlike the preferences, the jobs not matching
+/// them are never given to the prover.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProverCapabilities {
+    /// Block chunk sizes the prover has setups for.
+    pub block_sizes: Vec<usize>,
+    /// Aggregated proof sizes the prover has setups for.
+    pub aggregated_proof_sizes: Vec<usize>,
+    /// Available RAM in bytes, used to skip the jobs with too large setups.
+    pub available_ram: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -21,6 +40,9 @@ pub struct ProverInputResponse {
     pub first_block: BlockNumber,
     pub last_block: BlockNumber,
     pub data: Option<JobRequestData>,
+    /// Time by which the proof for the job is expected to be published.
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
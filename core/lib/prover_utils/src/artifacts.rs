@@ -0,0 +1,78 @@
+//! Self-contained artifacts of the block proving, used to reproduce the prover crashes and
+//! the proof mismatches offline, without the database access.
+
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use zksync_circuit::serialization::ProverData;
+use zksync_crypto::proof::SingleProof;
+use zksync_crypto::serialization::FrSerde;
+use zksync_crypto::Fr;
+use zksync_types::BlockNumber;
+
+use crate::api::JobRequestData;
+use crate::verify_single_proof;
+
+/// Witness of the block along with the proof stored for it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockProofArtifact {
+    pub block_number: BlockNumber,
+    /// Block chunks size, which determines the circuit used to prove the block.
+    pub block_size: usize,
+    /// Public data commitment of the block, the only public input of the block proof.
+    #[serde(with = "FrSerde")]
+    pub public_data_commitment: Fr,
+    pub witness: ProverData,
+    /// Proof stored for the block, if any.
+    pub proof: Option<SingleProof>,
+}
+
+impl BlockProofArtifact {
+    pub fn new(
+        block_number: BlockNumber,
+        block_size: usize,
+        witness: ProverData,
+        proof: Option<SingleProof>,
+    ) -> Self {
+        Self {
+            block_number,
+            block_size,
+            public_data_commitment: witness.public_data_commitment,
+            witness,
+            proof,
+        }
+    }
+
+    /// Loads the artifact from the JSON file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path)?;
+        let artifact = serde_json::from_reader(BufReader::new(file))?;
+        Ok(artifact)
+    }
+
+    /// Saves the artifact to the JSON file.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer(BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    /// Returns the prover job proving the block.
+    pub fn job_data(&self) -> JobRequestData {
+        JobRequestData::BlockProof(self.witness.clone(), self.block_size)
+    }
+
+    /// Verifies the proof stored in the artifact against its public input.
+    /// Returns `None` if there is no proof in the artifact.
+    pub fn verify_proof(&self) -> anyhow::Result<Option<bool>> {
+        if self.witness.public_data_commitment != self.public_data_commitment {
+            anyhow::bail!("Public data commitment doesn't match the witness");
+        }
+
+        self.proof
+            .as_ref()
+            .map(|proof| verify_single_proof(proof, self.public_data_commitment, self.block_size))
+            .transpose()
+    }
+}
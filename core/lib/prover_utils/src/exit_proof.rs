@@ -2,13 +2,103 @@
 
 use crate::gen_verified_proof_for_exit_circuit;
 use anyhow::format_err;
+use ethabi::Token as AbiToken;
 use num::BigUint;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
 use std::time::Instant;
+use zksync_basic_types::U256;
 use zksync_circuit::exit_circuit::create_exit_circuit_with_public_input;
+use zksync_contracts::zksync_contract;
 use zksync_crypto::circuit::account::CircuitAccount;
 use zksync_crypto::circuit::CircuitAccountTree;
 use zksync_crypto::proof::EncodedSingleProof;
-use zksync_types::{AccountId, AccountMap, Address, TokenId};
+use zksync_types::aggregated_operations::stored_block_info;
+use zksync_types::block::Block;
+use zksync_types::{Account, AccountId, AccountMap, Address, Token, TokenId, TokenLike};
+
+/// State of the accounts after the last executed block, which is sufficient to create
+/// the exit proofs without access to the database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExodusStateSnapshot {
+    /// Last executed block, stored info of which is expected by the contract.
+    pub block: Block,
+    pub accounts: Vec<(AccountId, Account)>,
+    pub tokens: Vec<Token>,
+}
+
+impl ExodusStateSnapshot {
+    /// Loads the snapshot from the JSON file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path)?;
+        let snapshot = serde_json::from_reader(BufReader::new(file))?;
+        Ok(snapshot)
+    }
+
+    /// Saves the snapshot to the JSON file.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer(BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    pub fn account_map(&self) -> AccountMap {
+        self.accounts.iter().cloned().collect()
+    }
+
+    /// Finds the account by its id or address.
+    pub fn find_account(
+        &self,
+        account_id: Option<AccountId>,
+        address: Option<Address>,
+    ) -> Option<(AccountId, &Account)> {
+        self.accounts
+            .iter()
+            .find(|(id, account)| {
+                account_id.map_or(true, |account_id| *id == account_id)
+                    && address.map_or(true, |address| account.address == address)
+            })
+            .map(|(id, account)| (*id, account))
+    }
+
+    /// Finds the token by its id, address or symbol.
+    pub fn find_token(&self, token: &TokenLike) -> Option<&Token> {
+        self.tokens.iter().find(|known_token| match token {
+            TokenLike::Id(id) => known_token.id == *id,
+            TokenLike::Address(address) => known_token.address == *address,
+            TokenLike::Symbol(symbol) => known_token.symbol.eq_ignore_ascii_case(symbol),
+        })
+    }
+}
+
+/// Encodes the call of the zkSync contract function withdrawing the funds of the account
+/// in exodus mode. `block` is expected to be the last executed block.
+pub fn perform_exodus_calldata(
+    block: &Block,
+    owner: Address,
+    account_id: AccountId,
+    token_id: TokenId,
+    amount: &BigUint,
+    proof: &EncodedSingleProof,
+) -> anyhow::Result<Vec<u8>> {
+    let amount = U256::from_dec_str(&amount.to_string())
+        .map_err(|e| format_err!("Amount doesn't fit into U256: {:?}", e))?;
+    let calldata = zksync_contract()
+        .function("performExodus")
+        .map_err(|e| format_err!("Failed to get performExodus function: {}", e))?
+        .encode_input(&[
+            stored_block_info(block),
+            AbiToken::Address(owner),
+            AbiToken::Uint(U256::from(*account_id)),
+            AbiToken::Uint(U256::from(*token_id)),
+            AbiToken::Uint(amount),
+            AbiToken::Array(proof.proof.iter().copied().map(AbiToken::Uint).collect()),
+        ])
+        .map_err(|e| format_err!("Failed to encode performExodus calldata: {}", e))?;
+    Ok(calldata)
+}
 
 pub fn create_exit_proof(
     accounts: AccountMap,
@@ -51,3 +141,167 @@ pub fn create_exit_proof(
     vlog::info!("Exit proof created: {} s", timer.elapsed().as_secs());
     Ok((proof.serialize_single_proof(), balance))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_crypto::Fr;
+    use zksync_types::{BlockNumber, H256};
+
+    fn get_test_snapshot() -> ExodusStateSnapshot {
+        let block = Block::new(
+            BlockNumber(5),
+            Fr::default(),
+            AccountId(0),
+            vec![],
+            (0, 0),
+            10,
+            U256::default(),
+            U256::default(),
+            H256::repeat_byte(7),
+            0,
+        );
+        let accounts = vec![
+            (
+                AccountId(0),
+                Account::default_with_address(&Address::repeat_byte(1)),
+            ),
+            (
+                AccountId(3),
+                Account::default_with_address(&Address::repeat_byte(2)),
+            ),
+        ];
+        let tokens = vec![
+            Token::new(TokenId(0), Address::zero(), "ETH", 18),
+            Token::new(TokenId(1), Address::repeat_byte(9), "DAI", 18),
+        ];
+
+        ExodusStateSnapshot {
+            block,
+            accounts,
+            tokens,
+        }
+    }
+
+    #[test]
+    fn find_account() {
+        let snapshot = get_test_snapshot();
+        let owner = Address::repeat_byte(2);
+
+        let (id, account) = snapshot.find_account(Some(AccountId(3)), None).unwrap();
+        assert_eq!(id, AccountId(3));
+        assert_eq!(account.address, owner);
+        let (id, _) = snapshot.find_account(None, Some(owner)).unwrap();
+        assert_eq!(id, AccountId(3));
+        let (id, _) = snapshot
+            .find_account(Some(AccountId(3)), Some(owner))
+            .unwrap();
+        assert_eq!(id, AccountId(3));
+
+        // Both the id and the address should match.
+        assert!(snapshot
+            .find_account(Some(AccountId(0)), Some(owner))
+            .is_none());
+        assert!(snapshot.find_account(Some(AccountId(1)), None).is_none());
+        assert!(snapshot
+            .find_account(None, Some(Address::repeat_byte(3)))
+            .is_none());
+    }
+
+    #[test]
+    fn find_token() {
+        let snapshot = get_test_snapshot();
+
+        let token = snapshot.find_token(&TokenLike::Id(TokenId(1))).unwrap();
+        assert_eq!(token.symbol, "DAI");
+        let token = snapshot
+            .find_token(&TokenLike::Address(Address::repeat_byte(9)))
+            .unwrap();
+        assert_eq!(token.id, TokenId(1));
+        let token = snapshot
+            .find_token(&TokenLike::Symbol("eth".to_string()))
+            .unwrap();
+        assert_eq!(token.id, TokenId(0));
+
+        assert!(snapshot.find_token(&TokenLike::Id(TokenId(2))).is_none());
+        assert!(snapshot
+            .find_token(&TokenLike::Symbol("USDC".to_string()))
+            .is_none());
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let snapshot = get_test_snapshot();
+        let path = std::env::temp_dir().join(format!(
+            "zksync_exodus_snapshot_{}.json",
+            std::process::id()
+        ));
+
+        snapshot.save(&path).unwrap();
+        let loaded = ExodusStateSnapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&snapshot).unwrap()
+        );
+        assert_eq!(loaded.account_map(), snapshot.account_map());
+    }
+
+    #[test]
+    fn perform_exodus_calldata_encoding() {
+        let snapshot = get_test_snapshot();
+        let owner = Address::repeat_byte(2);
+        let amount = BigUint::from(1_000_000u32);
+        let proof = EncodedSingleProof {
+            inputs: vec![U256::from(1)],
+            proof: vec![U256::from(2), U256::from(3)],
+        };
+
+        let calldata = perform_exodus_calldata(
+            &snapshot.block,
+            owner,
+            AccountId(3),
+            TokenId(1),
+            &amount,
+            &proof,
+        )
+        .unwrap();
+
+        let contract = zksync_contract();
+        let function = contract.function("performExodus").unwrap();
+        assert_eq!(calldata[..4], function.short_signature());
+        let param_types: Vec<_> = function
+            .inputs
+            .iter()
+            .map(|param| param.kind.clone())
+            .collect();
+        let args = ethabi::decode(&param_types, &calldata[4..]).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                stored_block_info(&snapshot.block),
+                AbiToken::Address(owner),
+                AbiToken::Uint(U256::from(3)),
+                AbiToken::Uint(U256::from(1)),
+                AbiToken::Uint(U256::from(1_000_000)),
+                AbiToken::Array(vec![
+                    AbiToken::Uint(U256::from(2)),
+                    AbiToken::Uint(U256::from(3))
+                ]),
+            ]
+        );
+
+        // Amount doesn't fit into the contract argument.
+        let amount = BigUint::from(1u32) << 256;
+        assert!(perform_exodus_calldata(
+            &snapshot.block,
+            owner,
+            AccountId(3),
+            TokenId(1),
+            &amount,
+            &proof,
+        )
+        .is_err());
+    }
+}
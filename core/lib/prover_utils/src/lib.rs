@@ -24,6 +24,7 @@ use zksync_crypto::{Engine, Fr};
 
 pub mod aggregated_proofs;
 pub mod api;
+pub mod artifacts;
 pub mod exit_proof;
 pub mod fs_utils;
 pub mod network_utils;
@@ -129,6 +130,28 @@ impl Drop for SetupForStepByStepProver {
     }
 }
 
+/// Verifies the single block proof: the proof is expected to have the block public data commitment
+/// as its only input and to be valid for the main circuit of the given block size.
+pub fn verify_single_proof(
+    proof: &SingleProof,
+    public_data_commitment: Fr,
+    block_chunks: usize,
+) -> Result<bool, anyhow::Error> {
+    if proof.0.input_values != [public_data_commitment] {
+        return Ok(false);
+    }
+
+    let vk = PlonkVerificationKey::read_verification_key_for_main_circuit(block_chunks)?;
+    let rns_params =
+        RnsParameters::<Engine, <Engine as EngineTrait>::Fq>::new_for_field(68, 110, 4);
+    let rescue_params = Bn256RescueParams::new_checked_2_into_1();
+    let transcript_params = (&rescue_params, &rns_params);
+
+    let valid =
+        verify::<_, _, RescueTranscriptForRNS<Engine>>(&proof.0, &vk.0, Some(transcript_params))?;
+    Ok(valid)
+}
+
 /// Generates proof for exit given circuit using step-by-step algorithm.
 pub fn gen_verified_proof_for_exit_circuit<C: Circuit<Engine> + Clone>(
     circuit: C,
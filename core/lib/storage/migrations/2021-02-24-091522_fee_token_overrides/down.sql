@@ -0,0 +1,2 @@
+DROP TABLE fee_token_overrides_history;
+DROP TABLE fee_token_overrides;
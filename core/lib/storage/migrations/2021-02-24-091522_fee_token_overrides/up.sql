@@ -0,0 +1,22 @@
+CREATE TABLE fee_token_overrides (
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    eligibility TEXT,
+    subsidized BOOLEAN,
+    fixed_usd_price NUMERIC,
+    updated_by TEXT NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL,
+    PRIMARY KEY (token_id)
+);
+
+CREATE TABLE fee_token_overrides_history (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    removed BOOLEAN NOT NULL,
+    eligibility TEXT,
+    subsidized BOOLEAN,
+    fixed_usd_price NUMERIC,
+    changed_by TEXT NOT NULL,
+    changed_at TIMESTAMP with time zone NOT NULL
+);
+
+CREATE INDEX fee_token_overrides_history_token_id_idx ON fee_token_overrides_history (token_id);
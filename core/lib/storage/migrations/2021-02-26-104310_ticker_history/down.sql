@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS ticker_fee_history;
+DROP TABLE IF EXISTS ticker_price_history;
@@ -0,0 +1,22 @@
+-- Every price received by the fee ticker, unlike `ticker_price` which stores only the latest one.
+CREATE TABLE ticker_price_history (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    usd_price NUMERIC NOT NULL,
+    last_updated TIMESTAMP with time zone NOT NULL
+);
+CREATE INDEX ticker_price_history_token_id_last_updated_idx ON ticker_price_history (token_id, last_updated);
+
+-- Fees computed by the fee ticker, sampled periodically.
+CREATE TABLE ticker_fee_history (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    fee_type jsonb NOT NULL,
+    gas_tx_amount NUMERIC NOT NULL,
+    gas_price_wei NUMERIC NOT NULL,
+    gas_fee NUMERIC NOT NULL,
+    zkp_fee NUMERIC NOT NULL,
+    total_fee NUMERIC NOT NULL,
+    sampled_at TIMESTAMP with time zone NOT NULL
+);
+CREATE INDEX ticker_fee_history_token_id_sampled_at_idx ON ticker_fee_history (token_id, sampled_at);
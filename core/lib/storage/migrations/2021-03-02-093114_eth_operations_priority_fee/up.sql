@@ -0,0 +1,3 @@
+-- Priority fee of the last sent EIP-1559 transaction. For such transactions
+-- `last_used_gas_price` stores the max fee per gas.
+ALTER TABLE eth_operations ADD COLUMN last_used_priority_fee NUMERIC;
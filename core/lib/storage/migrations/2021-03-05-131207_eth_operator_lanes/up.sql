@@ -0,0 +1,9 @@
+-- Operator account that sent the transaction, NULL for the main operator account.
+ALTER TABLE eth_operations ADD COLUMN sender BYTEA;
+
+-- Nonces of the additional operator accounts. The nonce of the main
+-- operator account is still stored in the `eth_parameters` table.
+CREATE TABLE eth_operator_nonces (
+    address BYTEA PRIMARY KEY,
+    nonce BIGINT NOT NULL
+);
@@ -0,0 +1,21 @@
+-- State of the Ethereum watcher, so it doesn't have to scan the Ethereum blocks from scratch on restart.
+CREATE TABLE eth_watch_state (
+    -- enforce single record
+    id bool PRIMARY KEY NOT NULL DEFAULT true,
+    CONSTRAINT single_eth_watch_state CHECK (id),
+    -- Last Ethereum block processed by the watcher.
+    last_ethereum_block BIGINT NOT NULL,
+    -- Hashes of the recently processed blocks used to detect the chain reorganizations.
+    block_hashes jsonb NOT NULL,
+    -- Priority operations without enough confirmations yet.
+    unconfirmed_ops jsonb NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL
+);
+
+-- Confirmed priority operations received by the Ethereum watcher.
+CREATE TABLE eth_watch_priority_ops (
+    serial_id BIGINT PRIMARY KEY,
+    eth_block BIGINT NOT NULL,
+    op jsonb NOT NULL,
+    received_at TIMESTAMP with time zone NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS eth_tx_costs_confirmed_at_idx;
+DROP TABLE IF EXISTS eth_tx_costs;
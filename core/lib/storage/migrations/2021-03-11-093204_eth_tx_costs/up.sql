@@ -0,0 +1,12 @@
+-- Costs of the confirmed Ethereum transactions sent by the operator.
+CREATE TABLE eth_tx_costs (
+    eth_op_id BIGINT PRIMARY KEY REFERENCES eth_operations (id) ON DELETE CASCADE,
+    -- Hash of the confirmed transaction.
+    tx_hash BYTEA NOT NULL,
+    gas_used NUMERIC NOT NULL,
+    -- Price paid per unit of gas (in wei).
+    effective_gas_price NUMERIC NOT NULL,
+    confirmed_at TIMESTAMP with time zone NOT NULL
+);
+
+CREATE INDEX eth_tx_costs_confirmed_at_idx ON eth_tx_costs (confirmed_at);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS eth_sender_admin_actions_pending_idx;
+DROP TABLE IF EXISTS eth_sender_admin_actions;
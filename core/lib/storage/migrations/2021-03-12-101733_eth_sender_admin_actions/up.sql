@@ -0,0 +1,15 @@
+-- Admin actions with the Ethereum sender. Pause and resume are applied once requested,
+-- and the latest of them determines whether sending is paused. The operation actions are
+-- performed by the sender, which marks them as processed and records the result.
+CREATE TABLE eth_sender_admin_actions (
+    id BIGSERIAL PRIMARY KEY,
+    action TEXT NOT NULL,
+    eth_op_id BIGINT REFERENCES eth_operations (id) ON DELETE CASCADE,
+    gas_price NUMERIC,
+    requested_by TEXT NOT NULL,
+    requested_at TIMESTAMP with time zone NOT NULL,
+    processed_at TIMESTAMP with time zone,
+    result TEXT
+);
+
+CREATE INDEX eth_sender_admin_actions_pending_idx ON eth_sender_admin_actions (id) WHERE processed_at IS NULL;
@@ -0,0 +1 @@
+ALTER TABLE prover_job_queue DROP COLUMN IF EXISTS attempts;
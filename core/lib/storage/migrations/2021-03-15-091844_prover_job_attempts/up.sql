@@ -0,0 +1,3 @@
+-- Amount of times the job was given to the provers. Jobs which are not completed
+-- after the maximum amount of attempts are considered failed and are not given anymore.
+ALTER TABLE prover_job_queue ADD COLUMN attempts INT NOT NULL DEFAULT 0;
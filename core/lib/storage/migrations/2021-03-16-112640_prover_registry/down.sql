@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS prover_registry_current_job_idx;
+DROP TABLE IF EXISTS prover_registry;
@@ -0,0 +1,19 @@
+-- Registry of the provers requesting jobs from the server. The capabilities are the ones
+-- advertised by the prover with its latest job request. The job currently given to the prover
+-- is tracked to attribute the completed and failed jobs to it.
+CREATE TABLE prover_registry (
+    name TEXT PRIMARY KEY,
+    version TEXT,
+    preferred_block_size BIGINT,
+    prefer_aggregated_proof BOOLEAN,
+    registered_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    last_heartbeat TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    stopped_at TIMESTAMP with time zone,
+    current_job_id INT,
+    job_started_at TIMESTAMP with time zone,
+    jobs_completed BIGINT NOT NULL DEFAULT 0,
+    jobs_failed BIGINT NOT NULL DEFAULT 0,
+    total_proof_time_ms BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX prover_registry_current_job_idx ON prover_registry (current_job_id);
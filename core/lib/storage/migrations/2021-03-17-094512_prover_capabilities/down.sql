@@ -0,0 +1,5 @@
+ALTER TABLE prover_registry DROP COLUMN IF EXISTS available_ram;
+ALTER TABLE prover_registry DROP COLUMN IF EXISTS aggregated_proof_sizes;
+ALTER TABLE prover_registry DROP COLUMN IF EXISTS block_sizes;
+
+ALTER TABLE prover_job_queue DROP COLUMN IF EXISTS block_size;
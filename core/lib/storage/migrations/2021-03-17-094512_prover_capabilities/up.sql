@@ -0,0 +1,8 @@
+-- Block chunk size of the single block proof jobs, used to give the jobs only
+-- to the provers capable of proving blocks of such size.
+ALTER TABLE prover_job_queue ADD COLUMN block_size BIGINT;
+
+-- Hard capabilities advertised by the provers.
+ALTER TABLE prover_registry ADD COLUMN block_sizes BIGINT[];
+ALTER TABLE prover_registry ADD COLUMN aggregated_proof_sizes BIGINT[];
+ALTER TABLE prover_registry ADD COLUMN available_ram BIGINT;
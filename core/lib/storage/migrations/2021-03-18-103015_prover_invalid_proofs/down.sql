@@ -0,0 +1 @@
+ALTER TABLE prover_registry DROP COLUMN IF EXISTS invalid_proofs;
@@ -0,0 +1,2 @@
+-- Amount of the proofs submitted by the prover that failed the verification.
+ALTER TABLE prover_registry ADD COLUMN invalid_proofs BIGINT NOT NULL DEFAULT 0;
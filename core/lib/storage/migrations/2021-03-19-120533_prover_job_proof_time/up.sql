@@ -0,0 +1,3 @@
+-- Time spent by the prover on the completed job, used to estimate
+-- the amount of provers required to prove the pending jobs in time.
+ALTER TABLE prover_job_queue ADD COLUMN proof_time_ms BIGINT;
@@ -0,0 +1,8 @@
+ALTER TABLE aggregated_proofs ALTER COLUMN proof SET NOT NULL;
+ALTER TABLE aggregated_proofs DROP COLUMN proof_ref;
+
+ALTER TABLE proofs ALTER COLUMN proof SET NOT NULL;
+ALTER TABLE proofs DROP COLUMN proof_ref;
+
+ALTER TABLE block_witness ALTER COLUMN witness SET NOT NULL;
+ALTER TABLE block_witness DROP COLUMN witness_ref;
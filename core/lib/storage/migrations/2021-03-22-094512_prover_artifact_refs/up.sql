@@ -0,0 +1,10 @@
+-- Witnesses and proofs can be kept in the artifact store outside of the database,
+-- in which case only the references to them are stored.
+ALTER TABLE block_witness ADD COLUMN witness_ref TEXT;
+ALTER TABLE block_witness ALTER COLUMN witness DROP NOT NULL;
+
+ALTER TABLE proofs ADD COLUMN proof_ref TEXT;
+ALTER TABLE proofs ALTER COLUMN proof DROP NOT NULL;
+
+ALTER TABLE aggregated_proofs ADD COLUMN proof_ref TEXT;
+ALTER TABLE aggregated_proofs ALTER COLUMN proof DROP NOT NULL;
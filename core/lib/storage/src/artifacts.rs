@@ -0,0 +1,117 @@
+//! Storage for the large prover artifacts, such as the block witnesses and proofs.
+//!
+//! Artifacts are kept outside of the database, which only stores the references to them.
+//! The artifact store is configured with the `artifacts_dir` option of the database config,
+//! if it's not set, the artifacts are stored in the database itself.
+
+// Built-in deps
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+// External imports
+use anyhow::{ensure, format_err};
+use async_trait::async_trait;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+// Workspace imports
+use zksync_config::DBConfig;
+
+/// Store of the prover artifacts, referenced from the database.
+#[async_trait]
+pub trait ArtifactStore: fmt::Debug + Send + Sync {
+    /// Stores the artifact, returning the reference to load it with.
+    async fn put(&self, data: Vec<u8>) -> anyhow::Result<String>;
+
+    /// Loads the artifact by the reference returned from `put`.
+    async fn get(&self, reference: &str) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Creates the artifact store configured with the `artifacts_dir` option of the database config.
+pub fn artifact_store_from_config(config: &DBConfig) -> Option<Arc<dyn ArtifactStore>> {
+    config
+        .artifacts_dir
+        .as_ref()
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| Arc::new(FilesystemArtifactStore::new(dir)) as Arc<dyn ArtifactStore>)
+}
+
+/// Content-addressed artifact store on the filesystem.
+///
+/// Artifacts are compressed with gzip and stored under the SHA-256 hash of their content,
+/// thus the same artifact is stored only once. The reference of the artifact is the hex
+/// encoded hash, and the file is placed at `<root>/<hash[0..2]>/<hash[2..4]>/<hash>.gz`.
+#[derive(Debug, Clone)]
+pub struct FilesystemArtifactStore {
+    root: PathBuf,
+}
+
+impl FilesystemArtifactStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn artifact_path(&self, reference: &str) -> anyhow::Result<PathBuf> {
+        ensure!(
+            reference.len() == 64 && reference.chars().all(|c| c.is_ascii_hexdigit()),
+            "Invalid artifact reference: {}",
+            reference
+        );
+
+        Ok(self
+            .root
+            .join(&reference[0..2])
+            .join(&reference[2..4])
+            .join(format!("{}.gz", reference)))
+    }
+
+    fn write_artifact(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+        if path.exists() {
+            return Ok(());
+        }
+        let dir = path
+            .parent()
+            .ok_or_else(|| format_err!("Artifact path has no parent directory"))?;
+        fs::create_dir_all(dir)?;
+
+        // The artifact is written to the temporary file first, so the partially written
+        // artifacts are never visible by their reference.
+        let mut encoder = GzEncoder::new(NamedTempFile::new_in(dir)?, Compression::default());
+        encoder.write_all(data)?;
+        let file = encoder.finish()?;
+        file.as_file().sync_all()?;
+        file.persist(path)?;
+        Ok(())
+    }
+
+    fn read_artifact(path: &Path, reference: &str) -> anyhow::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        GzDecoder::new(File::open(path)?).read_to_end(&mut data)?;
+        ensure!(
+            hex::encode(Sha256::digest(&data)) == reference,
+            "Artifact {} is corrupted",
+            reference
+        );
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for FilesystemArtifactStore {
+    async fn put(&self, data: Vec<u8>) -> anyhow::Result<String> {
+        let reference = hex::encode(Sha256::digest(&data));
+        let path = self.artifact_path(&reference)?;
+        tokio::task::spawn_blocking(move || Self::write_artifact(&path, &data)).await??;
+        Ok(reference)
+    }
+
+    async fn get(&self, reference: &str) -> anyhow::Result<Vec<u8>> {
+        let path = self.artifact_path(reference)?;
+        let reference = reference.to_string();
+        tokio::task::spawn_blocking(move || Self::read_artifact(&path, &reference)).await?
+    }
+}
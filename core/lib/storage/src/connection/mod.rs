@@ -1,12 +1,14 @@
 // Built-in deps
-use std::{env, fmt, time::Instant};
+use std::{env, fmt, sync::Arc, time::Instant};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
 use sqlx::{Connection, Error as SqlxError, PgConnection};
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
+use crate::artifacts::{artifact_store_from_config, ArtifactStore};
 use crate::StorageProcessor;
+use zksync_config::DBConfig;
 use zksync_utils::parse_env;
 
 pub mod holder;
@@ -44,10 +46,12 @@ impl Manager<PgConnection, SqlxError> for DbPool {
 /// the fixed size pool of connection to the database.
 ///
 /// The size of the pool and the database URL are configured via environment
-/// variables `DATABASE_POOL_SIZE` and `DATABASE_URL` respectively.
+/// variables `DATABASE_POOL_SIZE` and `DATABASE_URL` respectively. The prover
+/// artifacts store is created from the `artifacts_dir` option of the database config.
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    artifact_store: Option<Arc<dyn ArtifactStore>>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -66,7 +70,16 @@ impl ConnectionPool {
 
         let pool = DbPool::create(database_url, max_size as usize);
 
-        Self { pool }
+        Self {
+            pool,
+            artifact_store: artifact_store_from_config(&DBConfig::from_env()),
+        }
+    }
+
+    /// Replaces the store of the prover artifacts used by the storage processors of the pool.
+    pub fn with_artifact_store(mut self, artifact_store: Arc<dyn ArtifactStore>) -> Self {
+        self.artifact_store = Some(artifact_store);
+        self
     }
 
     /// Creates a `StorageProcessor` entity over a recoverable connection.
@@ -82,7 +95,10 @@ impl ConnectionPool {
         let connection = self.pool.get().await.unwrap();
         metrics::histogram!("sql.connection_acquire", start.elapsed());
 
-        Ok(StorageProcessor::from_pool(connection))
+        Ok(
+            StorageProcessor::from_pool(connection)
+                .with_artifact_store(self.artifact_store.clone()),
+        )
     }
 
     /// Obtains the database URL from the environment variable.
@@ -0,0 +1,113 @@
+// Built-in deps
+use std::{collections::BTreeMap, time::Instant};
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{PriorityOp, SerialId, H256};
+// Local imports
+use self::records::{StoredEthWatchState, StoredReceivedPriorityOp};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Ethereum watch schema stores the state of the Ethereum watcher,
+/// so it can be restored without scanning the Ethereum blocks from scratch.
+#[derive(Debug)]
+pub struct EthWatchSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> EthWatchSchema<'a, 'c> {
+    /// Loads the stored state of the Ethereum watcher, if any.
+    pub async fn load_state(&mut self) -> QueryResult<Option<StoredEthWatchState>> {
+        let start = Instant::now();
+        let state = sqlx::query_as!(
+            StoredEthWatchState,
+            "SELECT last_ethereum_block, block_hashes, unconfirmed_ops FROM eth_watch_state WHERE id = true",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.eth_watch.load_state", start.elapsed());
+        Ok(state)
+    }
+
+    /// Loads the stored confirmed priority operations ordered by the serial ID.
+    pub async fn load_priority_ops(&mut self) -> QueryResult<Vec<StoredReceivedPriorityOp>> {
+        let start = Instant::now();
+        let ops = sqlx::query_as!(
+            StoredReceivedPriorityOp,
+            "SELECT serial_id, eth_block, op, received_at FROM eth_watch_priority_ops ORDER BY serial_id",
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.eth_watch.load_priority_ops", start.elapsed());
+        Ok(ops)
+    }
+
+    /// Saves the state of the Ethereum watcher.
+    ///
+    /// Since the confirmed priority operations queue is changed incrementally, only the new
+    /// (or replaced) operations are stored, and the operations with serial IDs not
+    /// mentioned in `retained_serial_ids` are removed.
+    pub async fn save_state(
+        &mut self,
+        last_ethereum_block: u64,
+        block_hashes: &BTreeMap<u64, H256>,
+        unconfirmed_ops: &[PriorityOp],
+        new_priority_ops: &[(PriorityOp, DateTime<Utc>)],
+        retained_serial_ids: &[SerialId],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let block_hashes = serde_json::to_value(block_hashes)?;
+        let unconfirmed_ops = serde_json::to_value(unconfirmed_ops)?;
+        let retained_serial_ids: Vec<i64> = retained_serial_ids
+            .iter()
+            .map(|&serial_id| serial_id as i64)
+            .collect();
+
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO eth_watch_state ( last_ethereum_block, block_hashes, unconfirmed_ops, updated_at )
+            VALUES ( $1, $2, $3, now() )
+            ON CONFLICT (id)
+            DO
+              UPDATE SET last_ethereum_block = $1, block_hashes = $2, unconfirmed_ops = $3, updated_at = now()
+            "#,
+            last_ethereum_block as i64,
+            block_hashes,
+            unconfirmed_ops
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM eth_watch_priority_ops WHERE serial_id <> ALL($1)",
+            &retained_serial_ids
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        for (op, received_at) in new_priority_ops {
+            sqlx::query!(
+                r#"
+                INSERT INTO eth_watch_priority_ops ( serial_id, eth_block, op, received_at )
+                VALUES ( $1, $2, $3, $4 )
+                ON CONFLICT (serial_id)
+                DO
+                  UPDATE SET eth_block = $2, op = $3, received_at = $4
+                "#,
+                op.serial_id as i64,
+                op.eth_block as i64,
+                serde_json::to_value(op)?,
+                received_at
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.eth_watch.save_state", start.elapsed());
+        Ok(())
+    }
+}
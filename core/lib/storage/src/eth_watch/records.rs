@@ -0,0 +1,45 @@
+// Built-in deps
+use std::collections::BTreeMap;
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::{PriorityOp, H256};
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredEthWatchState {
+    pub last_ethereum_block: i64,
+    pub block_hashes: Value,
+    pub unconfirmed_ops: Value,
+}
+
+impl StoredEthWatchState {
+    pub fn last_ethereum_block(&self) -> u64 {
+        self.last_ethereum_block as u64
+    }
+
+    pub fn block_hashes(&self) -> BTreeMap<u64, H256> {
+        serde_json::from_value(self.block_hashes.clone()).expect("Unparsable block hashes in db")
+    }
+
+    pub fn unconfirmed_ops(&self) -> Vec<PriorityOp> {
+        serde_json::from_value(self.unconfirmed_ops.clone()).expect("Unparsable PriorityOp in db")
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredReceivedPriorityOp {
+    pub serial_id: i64,
+    pub eth_block: i64,
+    pub op: Value,
+    pub received_at: DateTime<Utc>,
+}
+
+impl StoredReceivedPriorityOp {
+    pub fn into_priority_op(self) -> (PriorityOp, DateTime<Utc>) {
+        let op = serde_json::from_value(self.op).expect("Unparsable PriorityOp in db");
+        (op, self.received_at)
+    }
+}
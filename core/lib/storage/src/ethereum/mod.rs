@@ -2,15 +2,22 @@
 use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
 // External imports
 use anyhow::format_err;
+use chrono::{DateTime, Utc};
 use num::{BigInt, BigUint};
 use sqlx::types::BigDecimal;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace imports
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
-use zksync_types::ethereum::{ETHOperation, InsertedOperationResponse};
+use zksync_types::ethereum::{
+    ETHOperation, ETHSenderAdminAction, ETHSenderAdminActionType, ETHSenderState, EthOpId,
+    InsertedOperationResponse,
+};
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
+use self::records::{
+    decimal_to_u256, ETHCosts, ETHOperatorNonce, ETHParams, ETHStats, ETHTxHash,
+    StorageETHOperation, StoredETHCosts, StoredETHSenderAdminAction,
+};
 use crate::{chain::operations::records::StoredAggregatedOperation, QueryResult, StorageProcessor};
 
 pub mod records;
@@ -93,19 +100,25 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
                 .expect("Stored operation type must have a valid value");
             let last_used_gas_price =
                 U256::from_str(&eth_op.last_used_gas_price.to_string()).unwrap();
+            let last_used_priority_fee = eth_op
+                .last_used_priority_fee
+                .map(|fee| U256::from_str(&fee.to_string()).unwrap());
             let used_tx_hashes = eth_tx_hashes
                 .iter()
                 .map(|entry| H256::from_slice(&entry.tx_hash))
                 .collect();
             let final_hash = eth_op.final_hash.map(|hash| H256::from_slice(&hash));
+            let sender = eth_op.sender.map(|sender| Address::from_slice(&sender));
 
             let eth_op = ETHOperation {
                 id: eth_op.id,
                 op_type,
                 op,
+                sender,
                 nonce: eth_op.nonce.into(),
                 last_deadline_block: eth_op.last_deadline_block as u64,
                 last_used_gas_price,
+                last_used_priority_fee,
                 used_tx_hashes,
                 encoded_tx_data: eth_op.raw_tx,
                 confirmed: eth_op.confirmed,
@@ -210,32 +223,49 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
     /// Stores the sent (but not confirmed yet) Ethereum transaction in the database.
     /// Returns the `ETHOperation` object containing the assigned nonce and operation ID.
+    ///
+    /// `last_used_priority_fee` is provided only for EIP-1559 transactions.
+    /// `sender` is the additional operator account sending the transaction, or `None`
+    /// for the main operator account. Each account has its own nonce sequence.
+    #[allow(clippy::too_many_arguments)]
     pub async fn save_new_eth_tx(
         &mut self,
         op_type: AggregatedActionType,
         operation: Option<(i64, AggregatedOperation)>,
         last_deadline_block: i64,
         last_used_gas_price: BigUint,
+        last_used_priority_fee: Option<BigUint>,
         raw_tx: Vec<u8>,
+        sender: Option<Address>,
     ) -> QueryResult<InsertedOperationResponse> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
         // It's important to assign nonce within the same db transaction
         // as saving the operation to avoid the state divergence.
-        let nonce = EthereumSchema(&mut transaction).get_next_nonce().await?;
+        let nonce = match sender {
+            Some(address) => {
+                EthereumSchema(&mut transaction)
+                    .get_next_operator_nonce(address)
+                    .await?
+            }
+            None => EthereumSchema(&mut transaction).get_next_nonce().await?,
+        };
+        let sender = sender.map(|address| address.as_bytes().to_vec());
 
         // Create and insert the operation.
 
         // Obtain the operation ID for the follow-up queried.
         let last_used_gas_price = BigDecimal::from(BigInt::from(last_used_gas_price));
+        let last_used_priority_fee =
+            last_used_priority_fee.map(|fee| BigDecimal::from(BigInt::from(fee)));
         let eth_op_id = sqlx::query!(
             "
-                INSERT INTO eth_operations (op_type, nonce, last_deadline_block, last_used_gas_price, raw_tx)
-                VALUES ($1, $2, $3, $4, $5)
+                INSERT INTO eth_operations (op_type, nonce, last_deadline_block, last_used_gas_price, last_used_priority_fee, raw_tx, sender)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
                 RETURNING id
             ",
-            op_type.to_string(), nonce, last_deadline_block, last_used_gas_price, raw_tx,
+            op_type.to_string(), nonce, last_deadline_block, last_used_gas_price, last_used_priority_fee, raw_tx, sender,
         )
         .fetch_one(transaction.conn())
         .await?
@@ -287,6 +317,35 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(confirmed)
     }
 
+    /// Returns whether the latest operation sent from the same operator account before the
+    /// operation with the given id was confirmed. `sender` is `None` for the main operator account.
+    /// If there are no such operations, then it returns Ok(true).
+    pub async fn is_previous_sender_op_confirmed(
+        &mut self,
+        id: i64,
+        sender: Option<Address>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let sender = sender.map(|address| address.as_bytes().to_vec());
+        let confirmed = sqlx::query!(
+            "SELECT confirmed FROM eth_operations
+            WHERE id < $1 AND sender IS NOT DISTINCT FROM $2
+            ORDER BY id DESC LIMIT 1",
+            id,
+            sender
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|op| op.confirmed)
+        .unwrap_or(true);
+
+        metrics::histogram!(
+            "sql.ethereum.is_previous_sender_op_confirmed",
+            start.elapsed()
+        );
+        Ok(confirmed)
+    }
+
     /// Retrieves the Ethereum operation ID given the tx hash.
     async fn get_eth_op_id(&mut self, hash: &H256) -> QueryResult<i64> {
         let start = Instant::now();
@@ -318,22 +377,25 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
     }
 
     /// Updates the Ethereum operation by adding a new tx data.
-    /// The new deadline block / gas value are placed instead of old values to the main entry.
+    /// The new deadline block / gas value / priority fee are placed instead of old values to the main entry.
     pub async fn update_eth_tx(
         &mut self,
         eth_op_id: i64,
         new_deadline_block: i64,
         new_gas_value: BigUint,
+        new_priority_fee: Option<BigUint>,
     ) -> QueryResult<()> {
         let start = Instant::now();
         // Update the stored tx.
         let new_gas_price = BigDecimal::from(BigInt::from(new_gas_value));
+        let new_priority_fee = new_priority_fee.map(|fee| BigDecimal::from(BigInt::from(fee)));
         sqlx::query!(
             "UPDATE eth_operations 
-            SET last_used_gas_price = $1, last_deadline_block = $2
-            WHERE id = $3",
+            SET last_used_gas_price = $1, last_deadline_block = $2, last_used_priority_fee = $3
+            WHERE id = $4",
             new_gas_price,
             new_deadline_block,
+            new_priority_fee,
             eth_op_id
         )
         .execute(self.0.conn())
@@ -463,13 +525,256 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(average_gas_price)
     }
 
-    /// Loads the stored Ethereum operations stats.
+    /// Loads the stored Ethereum operations stats, including the costs
+    /// of the confirmed transactions for every type of the operations.
     pub async fn load_stats(&mut self) -> QueryResult<ETHStats> {
         let start = Instant::now();
         let params = self.load_eth_params().await?;
 
+        // Every aggregated operation is sent in a single transaction,
+        // so the blocks are counted for the operations bound to the transactions.
+        let costs = sqlx::query_as!(
+            StoredETHCosts,
+            r#"SELECT eth_operations.op_type,
+                COUNT(*) AS "txs_count!",
+                COALESCE(SUM(aggregate_operations.to_block - aggregate_operations.from_block + 1), 0)::BIGINT AS "blocks_count!",
+                SUM(eth_tx_costs.gas_used) AS "gas_used!",
+                SUM(eth_tx_costs.gas_used * eth_tx_costs.effective_gas_price) AS "fee!"
+            FROM eth_tx_costs
+            INNER JOIN eth_operations ON eth_operations.id = eth_tx_costs.eth_op_id
+            LEFT JOIN eth_aggregated_ops_binding ON eth_aggregated_ops_binding.eth_op_id = eth_operations.id
+            LEFT JOIN aggregate_operations ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+            GROUP BY eth_operations.op_type
+            ORDER BY eth_operations.op_type"#,
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(ETHCosts::from)
+        .collect();
+
         metrics::histogram!("sql.ethereum.load_stats", start.elapsed());
-        Ok(params.into())
+        Ok(ETHStats {
+            last_committed_block: params.last_committed_block,
+            last_verified_block: params.last_verified_block,
+            last_executed_block: params.last_executed_block,
+            costs,
+        })
+    }
+
+    /// Stores the cost of the confirmed Ethereum transaction.
+    /// Does nothing if the cost of the operation is already stored.
+    pub async fn save_eth_tx_cost(
+        &mut self,
+        hash: &H256,
+        gas_used: BigUint,
+        effective_gas_price: BigUint,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let eth_op_id = EthereumSchema(&mut transaction).get_eth_op_id(hash).await?;
+        let gas_used = BigDecimal::from(BigInt::from(gas_used));
+        let effective_gas_price = BigDecimal::from(BigInt::from(effective_gas_price));
+        sqlx::query!(
+            "INSERT INTO eth_tx_costs (eth_op_id, tx_hash, gas_used, effective_gas_price, confirmed_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (eth_op_id) DO NOTHING",
+            eth_op_id,
+            hash.as_bytes(),
+            gas_used,
+            effective_gas_price,
+            Utc::now(),
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.save_eth_tx_cost", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the total fee in wei paid for the transactions sent from the operator account
+    /// (`None` for the main operator account) and confirmed since the given moment.
+    pub async fn load_spent_fee(
+        &mut self,
+        sender: Option<Address>,
+        since: DateTime<Utc>,
+    ) -> QueryResult<U256> {
+        let start = Instant::now();
+        let sender = sender.map(|address| address.as_bytes().to_vec());
+        let fee = sqlx::query!(
+            r#"SELECT COALESCE(SUM(eth_tx_costs.gas_used * eth_tx_costs.effective_gas_price), 0) AS "fee!"
+            FROM eth_tx_costs
+            INNER JOIN eth_operations ON eth_operations.id = eth_tx_costs.eth_op_id
+            WHERE eth_tx_costs.confirmed_at >= $1 AND eth_operations.sender IS NOT DISTINCT FROM $2"#,
+            since,
+            sender
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .fee;
+
+        metrics::histogram!("sql.ethereum.load_spent_fee", start.elapsed());
+        Ok(decimal_to_u256(&fee))
+    }
+
+    /// Returns whether the Ethereum operation is confirmed, `None` if there is no such operation.
+    pub async fn is_eth_op_confirmed(&mut self, eth_op_id: EthOpId) -> QueryResult<Option<bool>> {
+        let start = Instant::now();
+        let confirmed = sqlx::query!(
+            "SELECT confirmed FROM eth_operations WHERE id = $1",
+            eth_op_id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|op| op.confirmed);
+
+        metrics::histogram!("sql.ethereum.is_eth_op_confirmed", start.elapsed());
+        Ok(confirmed)
+    }
+
+    /// Stores the admin action with the Ethereum sender. Pause and resume are applied
+    /// right away, while the operation actions are left pending for the sender to perform.
+    pub async fn add_sender_admin_action(
+        &mut self,
+        action: ETHSenderAdminActionType,
+        eth_op_id: Option<EthOpId>,
+        gas_price: Option<U256>,
+        requested_by: &str,
+    ) -> QueryResult<ETHSenderAdminAction> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let (processed_at, result) = match action {
+            ETHSenderAdminActionType::Pause | ETHSenderAdminActionType::Resume => {
+                let was_paused = EthereumSchema(&mut transaction).is_sender_paused().await?;
+                let result = match (action, was_paused) {
+                    (ETHSenderAdminActionType::Pause, false) => "Sending is paused",
+                    (ETHSenderAdminActionType::Pause, true) => "Sending is already paused",
+                    (_, true) => "Sending is resumed",
+                    (_, false) => "Sending is not paused",
+                };
+                (Some(Utc::now()), Some(result.to_owned()))
+            }
+            _ => (None, None),
+        };
+        let gas_price = gas_price.map(|price| BigDecimal::from_str(&price.to_string()).unwrap());
+
+        let stored = sqlx::query_as!(
+            StoredETHSenderAdminAction,
+            "INSERT INTO eth_sender_admin_actions (action, eth_op_id, gas_price, requested_by, requested_at, processed_at, result)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *",
+            action.to_string(),
+            eth_op_id,
+            gas_price,
+            requested_by,
+            Utc::now(),
+            processed_at,
+            result,
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.add_sender_admin_action", start.elapsed());
+        Ok(stored.into())
+    }
+
+    /// Marks the admin action as performed and records its result.
+    pub async fn complete_sender_admin_action(&mut self, id: i64, result: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE eth_sender_admin_actions
+            SET processed_at = $1, result = $2
+            WHERE id = $3",
+            Utc::now(),
+            result,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.complete_sender_admin_action", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the runtime state of the Ethereum sender: whether it's paused,
+    /// and the admin actions waiting to be performed.
+    pub async fn load_sender_state(&mut self) -> QueryResult<ETHSenderState> {
+        let start = Instant::now();
+        let last_change = sqlx::query_as!(
+            StoredETHSenderAdminAction,
+            "SELECT * FROM eth_sender_admin_actions
+            WHERE action IN ('Pause', 'Resume')
+            ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(ETHSenderAdminAction::from);
+
+        let pending_actions = sqlx::query_as!(
+            StoredETHSenderAdminAction,
+            "SELECT * FROM eth_sender_admin_actions
+            WHERE processed_at IS NULL
+            ORDER BY id ASC",
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(ETHSenderAdminAction::from)
+        .collect();
+
+        let paused = last_change
+            .as_ref()
+            .map(|change| change.action == ETHSenderAdminActionType::Pause)
+            .unwrap_or(false);
+
+        metrics::histogram!("sql.ethereum.load_sender_state", start.elapsed());
+        Ok(ETHSenderState {
+            paused,
+            last_change,
+            pending_actions,
+        })
+    }
+
+    /// Loads the latest admin actions with the Ethereum sender, latest first.
+    pub async fn load_sender_admin_actions(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<ETHSenderAdminAction>> {
+        let start = Instant::now();
+        let actions = sqlx::query_as!(
+            StoredETHSenderAdminAction,
+            "SELECT * FROM eth_sender_admin_actions
+            ORDER BY id DESC LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(ETHSenderAdminAction::from)
+        .collect();
+
+        metrics::histogram!("sql.ethereum.load_sender_admin_actions", start.elapsed());
+        Ok(actions)
+    }
+
+    async fn is_sender_paused(&mut self) -> QueryResult<bool> {
+        let last_change = sqlx::query!(
+            "SELECT action FROM eth_sender_admin_actions
+            WHERE action IN ('Pause', 'Resume')
+            ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        Ok(last_change
+            .map(|change| change.action == ETHSenderAdminActionType::Pause.to_string())
+            .unwrap_or(false))
     }
 
     async fn load_eth_params(&mut self) -> QueryResult<ETHParams> {
@@ -550,6 +855,68 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(old_nonce_value)
     }
 
+    /// Obtains the next nonce to use for the additional operator account and updates
+    /// the corresponding entry in the database for the next invocation.
+    ///
+    /// The nonce of the account is expected to be initialized with `initialize_operator_nonce`.
+    pub(crate) async fn get_next_operator_nonce(&mut self, address: Address) -> QueryResult<i64> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let old_nonce = sqlx::query_as!(
+            ETHOperatorNonce,
+            "SELECT * FROM eth_operator_nonces WHERE address = $1 FOR UPDATE",
+            address.as_bytes()
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .ok_or_else(|| {
+            format_err!(
+                "Nonce of the operator account {:?} is not initialized",
+                address
+            )
+        })?;
+
+        sqlx::query!(
+            "UPDATE eth_operator_nonces
+            SET nonce = $1
+            WHERE address = $2",
+            old_nonce.nonce + 1,
+            address.as_bytes()
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.get_next_operator_nonce", start.elapsed());
+        Ok(old_nonce.nonce)
+    }
+
+    /// Stores the initial nonce of the additional operator account, usually obtained from
+    /// the Ethereum node. Does nothing if the nonce of the account is already stored,
+    /// since the stored value accounts for the transactions that are not mined yet.
+    pub async fn initialize_operator_nonce(
+        &mut self,
+        address: Address,
+        nonce: i64,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "INSERT INTO eth_operator_nonces (address, nonce)
+            VALUES ($1, $2)
+            ON CONFLICT (address) DO NOTHING",
+            address.as_bytes(),
+            nonce
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.initialize_operator_nonce", start.elapsed());
+        Ok(())
+    }
+
     /// Method that internally initializes the `eth_parameters` table.
     /// Since in db tests the database is empty, we must provide a possibility
     /// to initialize required db fields.
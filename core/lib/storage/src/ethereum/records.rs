@@ -1,6 +1,9 @@
 // External imports
+use chrono::{DateTime, Utc};
 use sqlx::{types::BigDecimal, FromRow};
 // Workspace imports
+use zksync_basic_types::U256;
+use zksync_types::{aggregated_operations::AggregatedActionType, ethereum::ETHSenderAdminAction};
 // Local imports
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -13,6 +16,8 @@ pub struct StorageETHOperation {
     pub final_hash: Option<Vec<u8>>,
     pub last_deadline_block: i64,
     pub last_used_gas_price: BigDecimal,
+    pub last_used_priority_fee: Option<BigDecimal>,
+    pub sender: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -22,6 +27,12 @@ pub struct ETHTxHash {
     pub tx_hash: Vec<u8>,
 }
 
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct ETHOperatorNonce {
+    pub address: Vec<u8>,
+    pub nonce: i64,
+}
+
 #[derive(Debug, FromRow, PartialEq)]
 pub struct ETHParams {
     pub id: bool,
@@ -33,20 +44,117 @@ pub struct ETHParams {
     pub last_executed_block: i64,
 }
 
-/// A slice of `ETHParams` structure with only stats part in it.
+/// Total costs of the confirmed Ethereum transactions of the same type, as stored in the database.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredETHCosts {
+    pub op_type: String,
+    pub txs_count: i64,
+    pub blocks_count: i64,
+    pub gas_used: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+/// Total costs of the confirmed Ethereum transactions of the same type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ETHCosts {
+    pub op_type: AggregatedActionType,
+    /// Amount of the confirmed transactions.
+    pub txs_count: u64,
+    /// Amount of the zkSync blocks processed by the transactions.
+    pub blocks_count: u64,
+    /// Total amount of gas used by the transactions.
+    pub gas_used: U256,
+    /// Total fee paid for the transactions in wei.
+    pub fee: U256,
+}
+
+impl ETHCosts {
+    /// Average amount of gas used per zkSync block, `None` if no blocks were processed.
+    pub fn gas_per_block(&self) -> Option<U256> {
+        self.per_block(self.gas_used)
+    }
+
+    /// Average fee paid per zkSync block, `None` if no blocks were processed.
+    pub fn fee_per_block(&self) -> Option<U256> {
+        self.per_block(self.fee)
+    }
+
+    fn per_block(&self, value: U256) -> Option<U256> {
+        if self.blocks_count == 0 {
+            None
+        } else {
+            Some(value / self.blocks_count)
+        }
+    }
+}
+
+impl From<StoredETHCosts> for ETHCosts {
+    fn from(stored: StoredETHCosts) -> Self {
+        Self {
+            op_type: stored
+                .op_type
+                .parse()
+                .expect("Incorrect aggregated action type stored in DB"),
+            txs_count: stored.txs_count as u64,
+            blocks_count: stored.blocks_count as u64,
+            gas_used: decimal_to_u256(&stored.gas_used),
+            fee: decimal_to_u256(&stored.fee),
+        }
+    }
+}
+
+/// Converts the stored `NUMERIC` value into `U256`.
+pub(crate) fn decimal_to_u256(value: &BigDecimal) -> U256 {
+    U256::from_dec_str(&value.with_scale(0).to_string())
+        .expect("Incorrect amount of wei stored in DB")
+}
+
+/// A slice of `ETHParams` structure with only stats part in it,
+/// along with the costs of the confirmed transactions.
 #[derive(Debug)]
 pub struct ETHStats {
     pub last_committed_block: i64,
     pub last_verified_block: i64,
     pub last_executed_block: i64,
+    /// Costs of the confirmed transactions for every type of the operations sent.
+    pub costs: Vec<ETHCosts>,
+}
+
+impl ETHStats {
+    /// Total fee paid for all the confirmed transactions in wei.
+    pub fn total_fee(&self) -> U256 {
+        self.costs
+            .iter()
+            .fold(U256::zero(), |total, costs| total + costs.fee)
+    }
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredETHSenderAdminAction {
+    pub id: i64,
+    pub action: String,
+    pub eth_op_id: Option<i64>,
+    pub gas_price: Option<BigDecimal>,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+    pub result: Option<String>,
 }
 
-impl From<ETHParams> for ETHStats {
-    fn from(params: ETHParams) -> Self {
+impl From<StoredETHSenderAdminAction> for ETHSenderAdminAction {
+    fn from(stored: StoredETHSenderAdminAction) -> Self {
         Self {
-            last_committed_block: params.last_committed_block,
-            last_verified_block: params.last_verified_block,
-            last_executed_block: params.last_executed_block,
+            id: stored.id,
+            action: stored
+                .action
+                .parse()
+                .expect("Incorrect Ethereum sender admin action stored in DB"),
+            eth_op_id: stored.eth_op_id,
+            gas_price: stored.gas_price.as_ref().map(decimal_to_u256),
+            requested_by: stored.requested_by,
+            requested_at: stored.requested_at,
+            processed_at: stored.processed_at,
+            result: stored.result,
         }
     }
 }
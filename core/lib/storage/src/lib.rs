@@ -10,7 +10,8 @@
 //!
 //! This crate can be divided into three logical parts:
 //! - Connection utilities. Tools to establish connections to the database,
-//!   stored in the `connection` module.
+//!   stored in the `connection` module, and the store of the large prover
+//!   artifacts kept outside of the database, stored in the `artifacts` module.
 //! - `Schema`s. Schema is a logically extracted access to the part of
 //!   the database, e.g. `ethereum` (which contains methods to store the
 //!   information about interaction with the Ethereum blockchain).
@@ -73,24 +74,28 @@
 #![allow(clippy::toplevel_ref_arg, clippy::suspicious_else_formatting)]
 
 // Built-in deps
-// use std::env;
+use std::sync::Arc;
 // External imports
 use sqlx::{postgres::Postgres, Connection, PgConnection, Transaction};
 // Workspace imports
 use zksync_basic_types::BlockNumber;
+use zksync_config::DBConfig;
 use zksync_types::ActionType;
 // Local imports
+use crate::artifacts::ArtifactStore;
 use crate::connection::{holder::ConnectionHolder, PooledConnection};
 
 // mod schema;
 #[cfg(test)]
 mod tests;
 
+pub mod artifacts;
 pub mod chain;
 pub mod config;
 pub mod connection;
 pub mod data_restore;
 pub mod diff;
+pub mod eth_watch;
 pub mod ethereum;
 pub mod prover;
 pub mod test_data;
@@ -111,6 +116,7 @@ pub const MAX_BLOCK_INDEX: u32 = i32::MAX as u32;
 pub struct StorageProcessor<'a> {
     conn: ConnectionHolder<'a>,
     in_transaction: bool,
+    artifact_store: Option<Arc<dyn ArtifactStore>>,
 }
 
 #[derive(sqlx::Type, Debug, Clone, PartialEq, Eq)]
@@ -137,15 +143,18 @@ impl<'a> StorageProcessor<'a> {
         Ok(StorageProcessor {
             conn: ConnectionHolder::Direct(connection),
             in_transaction: false,
+            artifact_store: artifacts::artifact_store_from_config(&DBConfig::from_env()),
         })
     }
 
     pub async fn start_transaction<'c: 'b, 'b>(
         &'c mut self,
     ) -> Result<StorageProcessor<'b>, anyhow::Error> {
+        let artifact_store = self.artifact_store.clone();
         let transaction = self.conn().begin().await?;
 
-        let mut processor = StorageProcessor::from_transaction(transaction);
+        let mut processor =
+            StorageProcessor::from_transaction(transaction).with_artifact_store(artifact_store);
         processor.in_transaction = true;
 
         Ok(processor)
@@ -160,6 +169,7 @@ impl<'a> StorageProcessor<'a> {
         StorageProcessor {
             conn: ConnectionHolder::Transaction(conn),
             in_transaction: true,
+            artifact_store: None,
         }
     }
 
@@ -179,9 +189,22 @@ impl<'a> StorageProcessor<'a> {
         Self {
             conn: ConnectionHolder::Pooled(conn),
             in_transaction: false,
+            artifact_store: None,
         }
     }
 
+    /// Sets the store of the prover artifacts kept outside of the database.
+    /// If no store is set, the artifacts are stored in the database itself.
+    pub fn with_artifact_store(mut self, artifact_store: Option<Arc<dyn ArtifactStore>>) -> Self {
+        self.artifact_store = artifact_store;
+        self
+    }
+
+    /// Returns the store of the prover artifacts, if any.
+    pub fn artifact_store(&self) -> Option<Arc<dyn ArtifactStore>> {
+        self.artifact_store.clone()
+    }
+
     /// Gains access to the `Chain` schemas.
     pub fn chain(&mut self) -> chain::ChainIntermediator<'_, 'a> {
         chain::ChainIntermediator(self)
@@ -197,6 +220,11 @@ impl<'a> StorageProcessor<'a> {
         data_restore::DataRestoreSchema(self)
     }
 
+    /// Gains access to the `EthWatch` schema.
+    pub fn eth_watch_schema(&mut self) -> eth_watch::EthWatchSchema<'_, 'a> {
+        eth_watch::EthWatchSchema(self)
+    }
+
     /// Gains access to the `Ethereum` schema.
     pub fn ethereum_schema(&mut self) -> ethereum::EthereumSchema<'_, 'a> {
         ethereum::EthereumSchema(self)
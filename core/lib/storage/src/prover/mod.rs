@@ -2,17 +2,22 @@
 use std::time::Instant;
 // External imports
 use anyhow::format_err;
+use chrono::{DateTime, Utc};
 use sqlx::Done;
 // Workspace imports
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{
+    StoragePendingJobs, StorageProofTime, StorageProverInfo, StorageProverJobQueue,
+    StoredAggregatedProof, StoredProof,
+};
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
 use crate::{QueryResult, StorageProcessor};
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
+use zksync_prover_utils::api::ProverInputRequestAuxData;
 use zksync_types::aggregated_operations::AggregatedActionType;
-use zksync_types::prover::{ProverJob, ProverJobStatus, ProverJobType};
+use zksync_types::prover::{ProverJob, ProverJobFilter, ProverJobStatus, ProverJobType};
 
 pub mod records;
 
@@ -27,8 +32,9 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
     pub async fn pending_jobs_count(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
         let pending_jobs_count = sqlx::query!(
-            "SELECT COUNT(*) FROM prover_job_queue WHERE job_status != $1",
-            ProverJobStatus::Done.to_number()
+            "SELECT COUNT(*) FROM prover_job_queue WHERE job_status IN ($1, $2)",
+            ProverJobStatus::Idle.to_number(),
+            ProverJobStatus::InProgress.to_number()
         )
         .fetch_one(self.0.conn())
         .await?
@@ -38,6 +44,54 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(pending_jobs_count)
     }
 
+    /// Returns the pending jobs grouped by the job type and block chunk size.
+    pub async fn load_pending_jobs(&mut self) -> QueryResult<Vec<StoragePendingJobs>> {
+        let start = Instant::now();
+        let pending_jobs = sqlx::query_as!(
+            StoragePendingJobs,
+            r#"SELECT job_type, block_size,
+                COUNT(*) as "jobs_count!", MIN(created_at) as "oldest_job_created_at!"
+            FROM prover_job_queue
+            WHERE job_status IN ($1, $2)
+            GROUP BY job_type, block_size
+            ORDER BY job_type, block_size"#,
+            ProverJobStatus::Idle.to_number(),
+            ProverJobStatus::InProgress.to_number()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_pending_jobs");
+        Ok(pending_jobs)
+    }
+
+    /// Returns the average time spent by the provers on the jobs completed since the given moment,
+    /// grouped by the job type and block chunk size.
+    pub async fn load_average_proof_times(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageProofTime>> {
+        let start = Instant::now();
+        let proof_times = sqlx::query_as!(
+            StorageProofTime,
+            r#"SELECT job_type, block_size,
+                AVG(proof_time_ms)::BIGINT as "average_proof_time_ms!"
+            FROM prover_job_queue
+            WHERE job_status = $1 AND proof_time_ms IS NOT NULL AND updated_at >= $2
+            GROUP BY job_type, block_size
+            ORDER BY job_type, block_size"#,
+            ProverJobStatus::Done.to_number(),
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_average_proof_times");
+        Ok(proof_times)
+    }
+
+    /// Adds the job to the queue unless there is already a job of the same type for these blocks.
+    /// The block chunk size is expected for the single block proofs.
     pub async fn add_prover_job_to_job_queue(
         &mut self,
         first_block: BlockNumber,
@@ -45,14 +99,15 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        block_size: Option<usize>,
     ) -> QueryResult<()> {
         sqlx::query!(
         "
           WITH job_values as (
-            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb
+            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb, $7::int8
             WHERE NOT EXISTS (SELECT * FROM prover_job_queue WHERE first_block = $4 and last_block = $5 and job_type = $3 LIMIT 1)
           )
-          INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data)
+          INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data, block_size)
           SELECT * from job_values
         ",
             ProverJobStatus::Idle.to_number(),
@@ -61,23 +116,84 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             i64::from(*first_block),
             i64::from(*last_block),
             job_data,
+            block_size.map(|size| size as i64),
         ).execute(self.0.conn()).await?;
         Ok(())
     }
 
-    pub async fn mark_stale_jobs_as_idle(&mut self) -> QueryResult<()> {
-        sqlx::query!(
+    /// Returns the amount of jobs which were not completed after the maximum amount of attempts.
+    pub async fn failed_jobs_count(&mut self) -> QueryResult<u32> {
+        let start = Instant::now();
+        let failed_jobs_count = sqlx::query!(
+            "SELECT COUNT(*) FROM prover_job_queue WHERE job_status = $1",
+            ProverJobStatus::Failed.to_number()
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count
+        .unwrap_or(0) as u32;
+        metrics::histogram!("sql", start.elapsed(), "prover" => "failed_jobs_count");
+        Ok(failed_jobs_count)
+    }
+
+    /// Returns the jobs for which no heartbeats were received for a while back to the queue.
+    /// Jobs which were given to the provers `max_attempts` times already are marked as failed
+    /// instead, so a job crashing the provers doesn't cycle through them indefinitely.
+    /// Returns the IDs of the jobs marked as failed.
+    ///
+    /// Stale jobs are counted as failed ones for the provers they were given to.
+    pub async fn mark_stale_jobs_as_idle(&mut self, max_attempts: u32) -> QueryResult<Vec<i32>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let failed_jobs: Vec<i32> = sqlx::query!(
+            "UPDATE prover_job_queue SET (job_status, updated_at, updated_by) = ($1, now(), 'server_fail_job')
+            WHERE job_status = $2 and (now() - updated_at) >= interval '120 seconds' and attempts >= $3
+            RETURNING id",
+            ProverJobStatus::Failed.to_number(),
+            ProverJobStatus::InProgress.to_number(),
+            max_attempts as i32,
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|job| job.id)
+        .collect();
+
+        let returned_jobs: Vec<i32> = sqlx::query!(
             "UPDATE prover_job_queue SET (job_status, updated_at, updated_by) = ($1, now(), 'server_clean_idle')
-            WHERE job_status = $2 and (now() - updated_at) >= interval '120 seconds'",
+            WHERE job_status = $2 and (now() - updated_at) >= interval '120 seconds'
+            RETURNING id",
             ProverJobStatus::Idle.to_number(),
             ProverJobStatus::InProgress.to_number(),
         )
-        .execute(self.0.conn())
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|job| job.id)
+        .collect();
+
+        let stale_jobs: Vec<i32> = failed_jobs.iter().chain(&returned_jobs).copied().collect();
+        sqlx::query!(
+            "UPDATE prover_registry
+            SET (jobs_failed, current_job_id, job_started_at) = (jobs_failed + 1, NULL, NULL)
+            WHERE current_job_id = ANY($1)",
+            &stale_jobs
+        )
+        .execute(transaction.conn())
         .await?;
-        Ok(())
+        transaction.commit().await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "mark_stale_jobs_as_idle");
+        Ok(failed_jobs)
     }
 
-    pub async fn get_idle_prover_job_from_job_queue(&mut self) -> QueryResult<Option<ProverJob>> {
+    /// Gives the next idle job to the prover: jobs with the higher priority (i.e. the lower priority value)
+    /// go first, and the jobs with the same priority are given in the order of blocks.
+    /// Jobs not satisfying the filter are skipped.
+    pub async fn get_idle_prover_job_from_job_queue(
+        &mut self,
+        filter: &ProverJobFilter,
+    ) -> QueryResult<Option<ProverJob>> {
         let start = Instant::now();
         // Select the block to prove.
         let mut transaction = self.0.start_transaction().await?;
@@ -85,15 +201,26 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             .execute(transaction.conn())
             .await?;
 
+        let block_sizes: Option<Vec<i64>> = filter
+            .block_sizes
+            .as_ref()
+            .map(|sizes| sizes.iter().map(|&size| size as i64).collect());
+        let max_aggregated_blocks = filter.max_aggregated_blocks.map(|blocks| blocks as i64);
         let prover_job_queue = sqlx::query_as!(
             StorageProverJobQueue,
             r#"
                 SELECT * FROM prover_job_queue
                 WHERE job_status = $1
-                ORDER BY (job_priority, id, first_block)
+                    AND (job_type != $2 OR $3::int8[] IS NULL OR block_size IS NULL OR block_size = ANY($3))
+                    AND (job_type != $4 OR $5::int8 IS NULL OR last_block - first_block + 1 <= $5)
+                ORDER BY job_priority, first_block, id
                 LIMIT 1
             "#,
-            ProverJobStatus::Idle.to_number()
+            ProverJobStatus::Idle.to_number(),
+            ProverJobType::SingleProof.to_string(),
+            block_sizes,
+            ProverJobType::AggregatedProof.to_string(),
+            max_aggregated_blocks,
         )
         .fetch_optional(transaction.conn())
         .await?;
@@ -102,7 +229,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             sqlx::query!(
                 r#"
                 UPDATE prover_job_queue
-                SET (job_status, updated_at, updated_by) = ($1, now(), 'server_give_job')
+                SET (job_status, updated_at, updated_by, attempts) = ($1, now(), 'server_give_job', attempts + 1)
                 WHERE id = $2;
             "#,
                 ProverJobStatus::InProgress.to_number(),
@@ -116,6 +243,8 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
                 BlockNumber(job.first_block as u32),
                 BlockNumber(job.last_block as u32),
                 job.job_data,
+                job.attempts + 1,
+                job.created_at,
             ))
         } else {
             None
@@ -125,6 +254,93 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(prover_job)
     }
 
+    /// Loads the job with the given ID.
+    pub async fn get_prover_job_by_id(&mut self, job_id: i32) -> QueryResult<Option<ProverJob>> {
+        let start = Instant::now();
+        let job = sqlx::query_as!(
+            StorageProverJobQueue,
+            "SELECT * FROM prover_job_queue WHERE id = $1",
+            job_id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|job| {
+            ProverJob::new(
+                job.id,
+                BlockNumber(job.first_block as u32),
+                BlockNumber(job.last_block as u32),
+                job.job_data,
+                job.attempts,
+                job.created_at,
+            )
+        });
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "get_prover_job_by_id");
+        Ok(job)
+    }
+
+    /// Returns the job for which an invalid proof was submitted back to the queue, or marks it
+    /// as failed if it was given to the provers `max_attempts` times already. The submitted proof
+    /// is counted as invalid for the prover the job was given to.
+    /// Returns `true` if the job was marked as failed.
+    pub async fn record_invalid_proof(
+        &mut self,
+        job_id: i32,
+        max_attempts: u32,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let job_status = sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (job_status, updated_at, updated_by) = (
+                CASE WHEN attempts >= $1 THEN $2::int4 ELSE $3::int4 END, now(), 'server_reject_proof'
+            )
+            WHERE id = $4 AND job_status = $5
+            RETURNING job_status",
+            max_attempts as i32,
+            ProverJobStatus::Failed.to_number(),
+            ProverJobStatus::Idle.to_number(),
+            job_id,
+            ProverJobStatus::InProgress.to_number(),
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .map(|job| job.job_status);
+
+        sqlx::query!(
+            "UPDATE prover_registry
+            SET (jobs_failed, invalid_proofs, current_job_id, job_started_at)
+                = (jobs_failed + 1, invalid_proofs + 1, NULL, NULL)
+            WHERE current_job_id = $1",
+            job_id
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "record_invalid_proof");
+        Ok(job_status == Some(ProverJobStatus::Failed.to_number()))
+    }
+
+    /// Returns the failed job back to the queue and resets its attempts counter.
+    /// Returns `false` if there is no failed job with the given ID.
+    pub async fn requeue_failed_job(&mut self, job_id: i32) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (job_status, updated_at, updated_by, attempts) = ($1, now(), 'server_requeue_job', 0)
+            WHERE id = $2 AND job_status = $3",
+            ProverJobStatus::Idle.to_number(),
+            job_id,
+            ProverJobStatus::Failed.to_number(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "requeue_failed_job");
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Updates the state of ongoing prover job.
     pub async fn record_prover_is_working(
         &mut self,
@@ -132,6 +348,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         prover_name: &str,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             "UPDATE prover_job_queue
             SET (updated_at, updated_by) = (now(), $1)
@@ -139,8 +356,15 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             prover_name.to_string(),
             job_id,
         )
-        .execute(self.0.conn())
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "UPDATE prover_registry SET last_heartbeat = now() WHERE name = $1",
+            prover_name
+        )
+        .execute(transaction.conn())
         .await?;
+        transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_is_working");
         Ok(())
@@ -149,6 +373,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
     /// Marks the prover as stopped.
     pub async fn record_prover_stop(&mut self, prover_name: &str) -> QueryResult<()> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             "UPDATE prover_job_queue
             SET (updated_at, job_status) = (now(), $1)
@@ -157,8 +382,17 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             prover_name,
             ProverJobStatus::InProgress.to_number()
         )
-        .execute(self.0.conn())
+        .execute(transaction.conn())
         .await?;
+        sqlx::query!(
+            "UPDATE prover_registry
+            SET (stopped_at, current_job_id, job_started_at) = (now(), NULL, NULL)
+            WHERE name = $1",
+            prover_name
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_stop");
         Ok(())
@@ -172,6 +406,10 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         proof: &SingleProof,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        let (proof, proof_ref) = match self.0.artifact_store() {
+            Some(store) => (None, Some(store.put(serde_json::to_vec(proof)?).await?)),
+            None => (Some(serde_json::to_value(proof).unwrap()), None),
+        };
         let mut transaction = self.0.start_transaction().await?;
         let updated_rows = sqlx::query!(
             "UPDATE prover_job_queue
@@ -190,13 +428,18 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         }
 
         sqlx::query!(
-            "INSERT INTO proofs (block_number, proof)
-            VALUES ($1, $2)",
+            "INSERT INTO proofs (block_number, proof, proof_ref)
+            VALUES ($1, $2, $3)",
             i64::from(*block_number),
-            serde_json::to_value(proof).unwrap()
+            proof,
+            proof_ref
         )
         .execute(transaction.conn())
         .await?;
+        transaction
+            .prover_schema()
+            .record_prover_job_completed(job_id)
+            .await?;
         transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "store_proof");
@@ -212,6 +455,10 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         proof: &AggregatedProof,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        let (proof, proof_ref) = match self.0.artifact_store() {
+            Some(store) => (None, Some(store.put(serde_json::to_vec(proof)?).await?)),
+            None => (Some(serde_json::to_value(proof).unwrap()), None),
+        };
         let mut transaction = self.0.start_transaction().await?;
         let updated_rows = sqlx::query!(
             "UPDATE prover_job_queue
@@ -230,34 +477,171 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         }
 
         sqlx::query!(
-            "INSERT INTO aggregated_proofs (first_block, last_block, proof)
-            VALUES ($1, $2, $3)",
+            "INSERT INTO aggregated_proofs (first_block, last_block, proof, proof_ref)
+            VALUES ($1, $2, $3, $4)",
             i64::from(*first_block),
             i64::from(*last_block),
-            serde_json::to_value(proof).unwrap()
+            proof,
+            proof_ref
         )
         .execute(transaction.conn())
         .await?;
+        transaction
+            .prover_schema()
+            .record_prover_job_completed(job_id)
+            .await?;
         transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "store_aggregated_proof");
         Ok(())
     }
 
+    /// Registers the prover requesting a job, or updates the capabilities it advertised
+    /// if it's already known. The prover is considered running again if it was stopped.
+    pub async fn record_prover_request(
+        &mut self,
+        prover_name: &str,
+        aux_data: &ProverInputRequestAuxData,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let capabilities = aux_data.capabilities.as_ref();
+        let block_sizes: Option<Vec<i64>> = capabilities.map(|capabilities| {
+            capabilities
+                .block_sizes
+                .iter()
+                .map(|&size| size as i64)
+                .collect()
+        });
+        let aggregated_proof_sizes: Option<Vec<i64>> = capabilities.map(|capabilities| {
+            capabilities
+                .aggregated_proof_sizes
+                .iter()
+                .map(|&size| size as i64)
+                .collect()
+        });
+        let available_ram = capabilities
+            .and_then(|capabilities| capabilities.available_ram)
+            .map(|ram| ram as i64);
+        sqlx::query!(
+            "INSERT INTO prover_registry (
+                name, version, preferred_block_size, prefer_aggregated_proof,
+                block_sizes, aggregated_proof_sizes, available_ram
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (name)
+            DO UPDATE
+            SET (
+                version, preferred_block_size, prefer_aggregated_proof,
+                block_sizes, aggregated_proof_sizes, available_ram, last_heartbeat, stopped_at
+            ) = ($2, $3, $4, $5, $6, $7, now(), NULL)",
+            prover_name,
+            aux_data.version,
+            aux_data.preferred_block_size.map(|size| size as i64),
+            aux_data.prefer_aggregated_proof,
+            block_sizes,
+            aggregated_proof_sizes,
+            available_ram,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_request");
+        Ok(())
+    }
+
+    /// Records the job given to the prover, so the result of the job can be attributed to it.
+    pub async fn record_prover_job_assigned(
+        &mut self,
+        prover_name: &str,
+        job_id: i32,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE prover_registry
+            SET (current_job_id, job_started_at) = ($1, now())
+            WHERE name = $2",
+            job_id,
+            prover_name,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_job_assigned");
+        Ok(())
+    }
+
+    /// Counts the job as completed for the prover it was given to and stores the time spent on it.
+    async fn record_prover_job_completed(&mut self, job_id: i32) -> QueryResult<()> {
+        sqlx::query!(
+            "UPDATE prover_job_queue
+            SET proof_time_ms = (
+                SELECT (EXTRACT(EPOCH FROM now() - job_started_at) * 1000)::BIGINT
+                FROM prover_registry
+                WHERE current_job_id = $1
+                LIMIT 1
+            )
+            WHERE id = $1",
+            job_id,
+        )
+        .execute(self.0.conn())
+        .await?;
+        sqlx::query!(
+            "UPDATE prover_registry
+            SET (jobs_completed, total_proof_time_ms, current_job_id, job_started_at) = (
+                jobs_completed + 1,
+                total_proof_time_ms + (EXTRACT(EPOCH FROM now() - job_started_at) * 1000)::BIGINT,
+                NULL,
+                NULL
+            )
+            WHERE current_job_id = $1",
+            job_id,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Loads all the registered provers.
+    pub async fn load_provers(&mut self) -> QueryResult<Vec<StorageProverInfo>> {
+        let start = Instant::now();
+        let provers = sqlx::query_as!(
+            StorageProverInfo,
+            "SELECT * FROM prover_registry ORDER BY name",
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_provers");
+        Ok(provers)
+    }
+
     /// Gets the stored proof for a block.
     pub async fn load_proof(
         &mut self,
         block_number: BlockNumber,
     ) -> QueryResult<Option<SingleProof>> {
         let start = Instant::now();
-        let proof = sqlx::query_as!(
+        let stored = sqlx::query_as!(
             StoredProof,
             "SELECT * FROM proofs WHERE block_number = $1",
             i64::from(*block_number),
         )
         .fetch_optional(self.0.conn())
-        .await?
-        .map(|stored| serde_json::from_value(stored.proof).unwrap());
+        .await?;
+        let proof = match stored {
+            Some(StoredProof {
+                proof: Some(proof), ..
+            }) => Some(serde_json::from_value(proof)?),
+            Some(StoredProof {
+                proof_ref: Some(reference),
+                ..
+            }) => Some(serde_json::from_slice(
+                &self.load_artifact(&reference).await?,
+            )?),
+            Some(_) => return Err(format_err!("Proof for block {} is missing", block_number)),
+            None => None,
+        };
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "load_proof");
         Ok(proof)
@@ -270,15 +654,33 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         last_block: BlockNumber,
     ) -> QueryResult<Option<AggregatedProof>> {
         let start = Instant::now();
-        let proof = sqlx::query_as!(
+        let stored = sqlx::query_as!(
             StoredAggregatedProof,
             "SELECT * FROM aggregated_proofs WHERE first_block = $1 and last_block = $2",
             i64::from(*first_block),
             i64::from(*last_block)
         )
         .fetch_optional(self.0.conn())
-        .await?
-        .map(|stored| serde_json::from_value(stored.proof).unwrap());
+        .await?;
+        let proof = match stored {
+            Some(StoredAggregatedProof {
+                proof: Some(proof), ..
+            }) => Some(serde_json::from_value(proof)?),
+            Some(StoredAggregatedProof {
+                proof_ref: Some(reference),
+                ..
+            }) => Some(serde_json::from_slice(
+                &self.load_artifact(&reference).await?,
+            )?),
+            Some(_) => {
+                return Err(format_err!(
+                    "Aggregated proof for blocks {}-{} is missing",
+                    first_block,
+                    last_block
+                ))
+            }
+            None => None,
+        };
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "load_aggregated_proof");
         Ok(proof)
@@ -292,13 +694,18 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
     ) -> QueryResult<()> {
         let start = Instant::now();
         let witness_str = serde_json::to_string(&witness).expect("Failed to serialize witness");
+        let (witness, witness_ref) = match self.0.artifact_store() {
+            Some(store) => (None, Some(store.put(witness_str.into_bytes()).await?)),
+            None => (Some(witness_str), None),
+        };
         sqlx::query!(
-            "INSERT INTO block_witness (block, witness)
-            VALUES ($1, $2)
+            "INSERT INTO block_witness (block, witness, witness_ref)
+            VALUES ($1, $2, $3)
             ON CONFLICT (block)
             DO NOTHING",
             i64::from(*block),
-            witness_str
+            witness,
+            witness_ref
         )
         .execute(self.0.conn())
         .await?;
@@ -320,10 +727,139 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
+        let witness = match block_witness {
+            Some(StorageBlockWitness {
+                witness: Some(witness),
+                ..
+            }) => Some(serde_json::from_str(&witness).expect("Failed to deserialize witness")),
+            Some(StorageBlockWitness {
+                witness_ref: Some(reference),
+                ..
+            }) => Some(
+                serde_json::from_slice(&self.load_artifact(&reference).await?)
+                    .expect("Failed to deserialize witness"),
+            ),
+            Some(_) => return Err(format_err!("Witness for block {} is missing", block_number)),
+            None => None,
+        };
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "get_witness");
-        Ok(block_witness
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness")))
+        Ok(witness)
+    }
+
+    /// Checks whether the witness of the block is stored, without loading it.
+    pub async fn witness_exists(&mut self, block_number: BlockNumber) -> QueryResult<bool> {
+        let start = Instant::now();
+        let exists = sqlx::query!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM block_witness
+                WHERE block = $1 AND (witness IS NOT NULL OR witness_ref IS NOT NULL)
+            ) AS "exists!""#,
+            i64::from(*block_number),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .exists;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "witness_exists");
+        Ok(exists)
+    }
+
+    /// Loads the artifact referenced from the database from the artifact store.
+    async fn load_artifact(&self, reference: &str) -> QueryResult<Vec<u8>> {
+        let store = self.0.artifact_store().ok_or_else(|| {
+            format_err!(
+                "Artifact {} is referenced, but the artifact store is not configured",
+                reference
+            )
+        })?;
+        store.get(reference).await
+    }
+
+    /// Moves up to `limit` witnesses and up to `limit` proofs of each kind from the database
+    /// to the artifact store, keeping only the references to them in the database.
+    /// Returns the amount of the moved artifacts, which is zero once none are left.
+    pub async fn move_artifacts_to_store(&mut self, limit: u32) -> QueryResult<usize> {
+        let start = Instant::now();
+        let store = self
+            .0
+            .artifact_store()
+            .ok_or_else(|| format_err!("Artifact store is not configured"))?;
+        let mut moved = 0;
+
+        let witnesses = sqlx::query!(
+            "SELECT block, witness FROM block_witness
+            WHERE witness IS NOT NULL
+            ORDER BY block
+            LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        for row in witnesses {
+            if let Some(witness) = row.witness {
+                let reference = store.put(witness.into_bytes()).await?;
+                sqlx::query!(
+                    "UPDATE block_witness SET witness = NULL, witness_ref = $2 WHERE block = $1",
+                    row.block,
+                    reference
+                )
+                .execute(self.0.conn())
+                .await?;
+                moved += 1;
+            }
+        }
+
+        let proofs = sqlx::query!(
+            "SELECT block_number, proof FROM proofs
+            WHERE proof IS NOT NULL
+            ORDER BY block_number
+            LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        for row in proofs {
+            if let Some(proof) = row.proof {
+                let reference = store.put(serde_json::to_vec(&proof)?).await?;
+                sqlx::query!(
+                    "UPDATE proofs SET proof = NULL, proof_ref = $2 WHERE block_number = $1",
+                    row.block_number,
+                    reference
+                )
+                .execute(self.0.conn())
+                .await?;
+                moved += 1;
+            }
+        }
+
+        let aggregated_proofs = sqlx::query!(
+            "SELECT first_block, last_block, proof FROM aggregated_proofs
+            WHERE proof IS NOT NULL
+            ORDER BY first_block
+            LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        for row in aggregated_proofs {
+            if let Some(proof) = row.proof {
+                let reference = store.put(serde_json::to_vec(&proof)?).await?;
+                sqlx::query!(
+                    "UPDATE aggregated_proofs SET proof = NULL, proof_ref = $3
+                    WHERE first_block = $1 AND last_block = $2",
+                    row.first_block,
+                    row.last_block,
+                    reference
+                )
+                .execute(self.0.conn())
+                .await?;
+                moved += 1;
+            }
+        }
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "move_artifacts_to_store");
+        Ok(moved)
     }
 
     pub async fn get_last_block_prover_job_queue(
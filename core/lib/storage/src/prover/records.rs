@@ -23,16 +23,22 @@ pub struct NewProof {
 #[derive(Debug, FromRow)]
 pub struct StoredProof {
     pub block_number: i64,
-    pub proof: serde_json::Value,
+    /// Proof stored in the database, `None` if it's kept in the artifact store.
+    pub proof: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
+    /// Reference to the proof in the artifact store.
+    pub proof_ref: Option<String>,
 }
 
 #[derive(Debug, FromRow)]
 pub struct StoredAggregatedProof {
     pub first_block: i64,
     pub last_block: i64,
-    pub proof: serde_json::Value,
+    /// Proof stored in the database, `None` if it's kept in the artifact store.
+    pub proof: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
+    /// Reference to the proof in the artifact store.
+    pub proof_ref: Option<String>,
 }
 
 // Every time before a prover worker starts generating the proof, a prover run is recorded for monitoring purposes
@@ -53,7 +59,10 @@ pub struct IntegerNumber {
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct StorageBlockWitness {
     pub block: i64,
-    pub witness: String,
+    /// Witness stored in the database, `None` if it's kept in the artifact store.
+    pub witness: Option<String>,
+    /// Reference to the witness in the artifact store.
+    pub witness_ref: Option<String>,
 }
 
 #[derive(Debug, FromRow)]
@@ -68,4 +77,44 @@ pub struct StorageProverJobQueue {
     pub first_block: i64,
     pub last_block: i64,
     pub job_data: serde_json::Value,
+    pub attempts: i32,
+    pub block_size: Option<i64>,
+    pub proof_time_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageProverInfo {
+    pub name: String,
+    pub version: Option<String>,
+    pub preferred_block_size: Option<i64>,
+    pub prefer_aggregated_proof: Option<bool>,
+    pub registered_at: DateTime<Utc>,
+    pub last_heartbeat: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub current_job_id: Option<i32>,
+    pub job_started_at: Option<DateTime<Utc>>,
+    pub jobs_completed: i64,
+    pub jobs_failed: i64,
+    pub total_proof_time_ms: i64,
+    pub block_sizes: Option<Vec<i64>>,
+    pub aggregated_proof_sizes: Option<Vec<i64>>,
+    pub available_ram: Option<i64>,
+    pub invalid_proofs: i64,
+}
+
+/// Pending jobs of the same type and block chunk size.
+#[derive(Debug, Clone, FromRow)]
+pub struct StoragePendingJobs {
+    pub job_type: String,
+    pub block_size: Option<i64>,
+    pub jobs_count: i64,
+    pub oldest_job_created_at: DateTime<Utc>,
+}
+
+/// Average time spent by the provers on the jobs of the same type and block chunk size.
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageProofTime {
+    pub job_type: String,
+    pub block_size: Option<i64>,
+    pub average_proof_time_ms: i64,
 }
@@ -254,7 +254,9 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
+                None,
             )
             .await?;
 
@@ -297,7 +299,9 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
+                        None,
                     )
                     .await?;
                 EthereumSchema(&mut storage)
@@ -413,7 +417,9 @@ async fn test_block_range(mut storage: StorageProcessor<'_>) -> QueryResult<()>
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
+                None,
             )
             .await?;
         EthereumSchema(&mut storage)
@@ -446,7 +452,9 @@ async fn test_block_range(mut storage: StorageProcessor<'_>) -> QueryResult<()>
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
+                    None,
                 )
                 .await?;
             EthereumSchema(&mut storage)
@@ -532,7 +540,9 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
+                None,
             )
             .await?;
         EthereumSchema(&mut storage)
@@ -569,7 +579,9 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
+                    None,
                 )
                 .await?;
             EthereumSchema(&mut storage)
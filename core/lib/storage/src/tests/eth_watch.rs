@@ -0,0 +1,93 @@
+// Built-in deps
+use std::collections::BTreeMap;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{Deposit, PriorityOp, TokenId, ZkSyncPriorityOp, H256};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn dummy_priority_op(serial_id: u64, eth_block: u64) -> PriorityOp {
+    PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: Default::default(),
+            token: TokenId(0),
+            amount: 100u32.into(),
+            to: Default::default(),
+        }),
+        deadline_block: 0,
+        eth_hash: H256::from_low_u64_be(eth_block),
+        eth_block,
+    }
+}
+
+fn received_at(secs: i64) -> DateTime<Utc> {
+    DateTime::from_utc(chrono::NaiveDateTime::from_timestamp(secs, 0), Utc)
+}
+
+/// Checks that the Ethereum watcher state is saved and loaded correctly,
+/// and that the confirmed priority operations are updated incrementally.
+#[db_test]
+async fn eth_watch_state(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(storage.eth_watch_schema().load_state().await?.is_none());
+
+    let block_hashes: BTreeMap<_, _> = vec![(5, H256::from_low_u64_be(5))].into_iter().collect();
+    let unconfirmed_ops = vec![dummy_priority_op(2, 5)];
+    let priority_ops = vec![
+        (dummy_priority_op(0, 3), received_at(100)),
+        (dummy_priority_op(1, 4), received_at(200)),
+    ];
+    storage
+        .eth_watch_schema()
+        .save_state(5, &block_hashes, &unconfirmed_ops, &priority_ops, &[0, 1])
+        .await?;
+
+    let state = storage.eth_watch_schema().load_state().await?.unwrap();
+    assert_eq!(state.last_ethereum_block(), 5);
+    assert_eq!(state.block_hashes(), block_hashes);
+    assert_eq!(state.unconfirmed_ops().len(), 1);
+    assert_eq!(state.unconfirmed_ops()[0].serial_id, 2);
+
+    let stored_ops: Vec<_> = storage
+        .eth_watch_schema()
+        .load_priority_ops()
+        .await?
+        .into_iter()
+        .map(|op| op.into_priority_op())
+        .collect();
+    assert_eq!(stored_ops.len(), 2);
+    assert_eq!(stored_ops[1].0.eth_hash, H256::from_low_u64_be(4));
+    assert_eq!(stored_ops[1].1, received_at(200));
+
+    // The first operation is removed, the second one is replaced (e.g. by the chain reorganization),
+    // and the third one is confirmed.
+    let priority_ops = vec![
+        (dummy_priority_op(1, 6), received_at(300)),
+        (dummy_priority_op(2, 5), received_at(300)),
+    ];
+    storage
+        .eth_watch_schema()
+        .save_state(7, &BTreeMap::new(), &[], &priority_ops, &[1, 2])
+        .await?;
+
+    let state = storage.eth_watch_schema().load_state().await?.unwrap();
+    assert_eq!(state.last_ethereum_block(), 7);
+    assert!(state.block_hashes().is_empty());
+    assert!(state.unconfirmed_ops().is_empty());
+
+    let stored_ops: Vec<_> = storage
+        .eth_watch_schema()
+        .load_priority_ops()
+        .await?
+        .into_iter()
+        .map(|op| op.into_priority_op())
+        .collect();
+    assert_eq!(stored_ops.len(), 2);
+    assert_eq!(stored_ops[0].0.serial_id, 1);
+    assert_eq!(stored_ops[0].0.eth_block, 6);
+    assert_eq!(stored_ops[1].0.serial_id, 2);
+
+    Ok(())
+}
@@ -1,11 +1,11 @@
 // Built-in deps
 use std::str::FromStr;
 // External imports
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace imports
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
-    ethereum::ETHOperation,
+    ethereum::{ETHOperation, ETHSenderAdminActionType},
     BlockNumber,
 };
 // Local imports
@@ -24,7 +24,9 @@ pub struct EthereumTxParams {
     hash: H256,
     deadline_block: u64,
     gas_price: BigUint,
+    priority_fee: Option<BigUint>,
     raw_tx: Vec<u8>,
+    sender: Option<Address>,
 }
 
 impl EthereumTxParams {
@@ -37,7 +39,9 @@ impl EthereumTxParams {
             hash: H256::from_low_u64_ne(op_id as u64),
             deadline_block: 100,
             gas_price: 1000u32.into(),
+            priority_fee: None,
             raw_tx: Default::default(),
+            sender: None,
         }
     }
 
@@ -45,15 +49,21 @@ impl EthereumTxParams {
         let op_type = AggregatedActionType::from_str(self.op_type.as_ref())
             .expect("Stored operation type must have a valid value");
         let last_used_gas_price = U256::from_str(&self.gas_price.to_string()).unwrap();
+        let last_used_priority_fee = self
+            .priority_fee
+            .as_ref()
+            .map(|fee| U256::from_str(&fee.to_string()).unwrap());
         let used_tx_hashes = vec![self.hash];
 
         ETHOperation {
             id: db_id,
             op_type,
             op: self.op.clone(),
+            sender: self.sender,
             nonce: nonce.into(),
             last_deadline_block: self.deadline_block,
             last_used_gas_price,
+            last_used_priority_fee,
             used_tx_hashes,
             encoded_tx_data: self.raw_tx.clone(),
             confirmed: false,
@@ -111,7 +121,9 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            params.priority_fee.clone(),
             params.raw_tx.clone(),
+            None,
         )
         .await?;
     EthereumSchema(&mut storage)
@@ -143,15 +155,18 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
         .get_aggregated_op_that_affects_block(AggregatedActionType::CreateProofBlocks, block_number)
         .await?;
 
-    // Create one more Ethereum transaction.
-    let params_2 = EthereumTxParams::new("CommitBlocks".into(), op);
+    // Create one more Ethereum transaction, this time an EIP-1559 one.
+    let mut params_2 = EthereumTxParams::new("CommitBlocks".into(), op);
+    params_2.priority_fee = Some(10u32.into());
     let response_2 = EthereumSchema(&mut storage)
         .save_new_eth_tx(
             AggregatedActionType::CreateProofBlocks,
             params_2.op.clone(),
             params_2.deadline_block as i64,
             params_2.gas_price.clone(),
+            params_2.priority_fee.clone(),
             params_2.raw_tx.clone(),
+            None,
         )
         .await?;
     EthereumSchema(&mut storage)
@@ -170,6 +185,25 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
         params_2.to_eth_op(eth_op.id, response_2.nonce.low_u64())
     );
 
+    // Bump the fees of the EIP-1559 transaction and check that they're updated.
+    params_2.gas_price = 2000u32.into();
+    params_2.priority_fee = Some(20u32.into());
+    EthereumSchema(&mut storage)
+        .update_eth_tx(
+            response_2.id,
+            params_2.deadline_block as i64,
+            params_2.gas_price.clone(),
+            params_2.priority_fee.clone(),
+        )
+        .await?;
+    let unconfirmed_operations = EthereumSchema(&mut storage)
+        .load_unconfirmed_operations()
+        .await?;
+    assert_eq!(
+        unconfirmed_operations[1],
+        params_2.to_eth_op(response_2.id, response_2.nonce.low_u64())
+    );
+
     // Make the transaction as completed.
     EthereumSchema(&mut storage)
         .confirm_eth_tx(&params_2.hash)
@@ -205,6 +239,307 @@ async fn eth_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the additional operator accounts have their own nonce lanes:
+/// - Operator nonce is initialized only once.
+/// - Transactions sent from the additional account use its own nonce sequence.
+/// - Confirmation of the previous operation is checked within the same lane only.
+#[db_test]
+async fn eth_operator_nonce_lanes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let operator = Address::repeat_byte(0x11);
+    // Nonce of the account which was not initialized can't be obtained.
+    assert!(EthereumSchema(&mut storage)
+        .get_next_operator_nonce(operator)
+        .await
+        .is_err());
+
+    EthereumSchema(&mut storage)
+        .initialize_operator_nonce(operator, 5)
+        .await?;
+    // Repeated initialization must not reset the stored nonce.
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .get_next_operator_nonce(operator)
+            .await?,
+        5
+    );
+    EthereumSchema(&mut storage)
+        .initialize_operator_nonce(operator, 0)
+        .await?;
+
+    // Store the transactions for the main operator and the additional one.
+    let mut main_params = EthereumTxParams::new("CommitBlocks".into(), None);
+    main_params.hash = H256::repeat_byte(0x01);
+    let mut lane_params = EthereumTxParams::new("ExecuteBlocks".into(), None);
+    lane_params.hash = H256::repeat_byte(0x02);
+    lane_params.sender = Some(operator);
+
+    let mut responses = Vec::new();
+    for (op_type, params) in &[
+        (AggregatedActionType::CommitBlocks, &main_params),
+        (AggregatedActionType::ExecuteBlocks, &lane_params),
+    ] {
+        let response = EthereumSchema(&mut storage)
+            .save_new_eth_tx(
+                *op_type,
+                params.op.clone(),
+                params.deadline_block as i64,
+                params.gas_price.clone(),
+                params.priority_fee.clone(),
+                params.raw_tx.clone(),
+                params.sender,
+            )
+            .await?;
+        EthereumSchema(&mut storage)
+            .add_hash_entry(response.id, &params.hash)
+            .await?;
+        responses.push(response);
+    }
+    assert_eq!(responses[0].nonce, U256::from(0));
+    assert_eq!(responses[1].nonce, U256::from(6));
+
+    let unconfirmed_operations = EthereumSchema(&mut storage)
+        .load_unconfirmed_operations()
+        .await?;
+    assert_eq!(unconfirmed_operations.len(), 2);
+    assert_eq!(unconfirmed_operations[0].sender, None);
+    assert_eq!(unconfirmed_operations[1].sender, Some(operator));
+    assert_eq!(
+        unconfirmed_operations[1],
+        lane_params.to_eth_op(responses[1].id, 6)
+    );
+
+    // The unconfirmed main operator transaction does not block the other lane.
+    assert!(
+        EthereumSchema(&mut storage)
+            .is_previous_sender_op_confirmed(responses[1].id, Some(operator))
+            .await?
+    );
+    assert!(
+        !EthereumSchema(&mut storage)
+            .is_previous_sender_op_confirmed(responses[1].id + 1, None)
+            .await?
+    );
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&main_params.hash)
+        .await?;
+    assert!(
+        EthereumSchema(&mut storage)
+            .is_previous_sender_op_confirmed(responses[1].id + 1, None)
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Checks the accounting of the confirmed transactions costs:
+/// - Costs are aggregated by the operation type along with the amount of processed blocks.
+/// - Cost of the transaction is stored only once.
+/// - Spent fee is loaded for the particular operator account and period.
+#[db_test]
+async fn eth_tx_costs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+    assert!(EthereumSchema(&mut storage)
+        .load_stats()
+        .await?
+        .costs
+        .is_empty());
+
+    let operator = Address::repeat_byte(0x11);
+    EthereumSchema(&mut storage)
+        .initialize_operator_nonce(operator, 0)
+        .await?;
+
+    // Store the commit operation sent from the main account and the execute
+    // operation without the block sent from the additional account.
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::CommitBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let op = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+        .await?;
+    let commit_params = EthereumTxParams::new("CommitBlocks".into(), op);
+    let mut execute_params = EthereumTxParams::new("ExecuteBlocks".into(), None);
+    execute_params.hash = H256::repeat_byte(0x02);
+    execute_params.sender = Some(operator);
+
+    for (op_type, params) in &[
+        (AggregatedActionType::CommitBlocks, &commit_params),
+        (AggregatedActionType::ExecuteBlocks, &execute_params),
+    ] {
+        let response = EthereumSchema(&mut storage)
+            .save_new_eth_tx(
+                *op_type,
+                params.op.clone(),
+                params.deadline_block as i64,
+                params.gas_price.clone(),
+                params.priority_fee.clone(),
+                params.raw_tx.clone(),
+                params.sender,
+            )
+            .await?;
+        EthereumSchema(&mut storage)
+            .add_hash_entry(response.id, &params.hash)
+            .await?;
+        EthereumSchema(&mut storage)
+            .confirm_eth_tx(&params.hash)
+            .await?;
+    }
+
+    let since = chrono::Utc::now() - chrono::Duration::hours(1);
+    EthereumSchema(&mut storage)
+        .save_eth_tx_cost(&commit_params.hash, 100_000u32.into(), 30u32.into())
+        .await?;
+    EthereumSchema(&mut storage)
+        .save_eth_tx_cost(&execute_params.hash, 50_000u32.into(), 20u32.into())
+        .await?;
+    // Repeated saving doesn't change the stored cost.
+    EthereumSchema(&mut storage)
+        .save_eth_tx_cost(&execute_params.hash, 70_000u32.into(), 20u32.into())
+        .await?;
+
+    let stats = EthereumSchema(&mut storage).load_stats().await?;
+    assert_eq!(stats.costs.len(), 2);
+    let commit_costs = &stats.costs[0];
+    assert_eq!(commit_costs.op_type, AggregatedActionType::CommitBlocks);
+    assert_eq!(commit_costs.txs_count, 1);
+    assert_eq!(commit_costs.blocks_count, 1);
+    assert_eq!(commit_costs.gas_used, U256::from(100_000));
+    assert_eq!(commit_costs.fee, U256::from(3_000_000));
+    assert_eq!(commit_costs.fee_per_block(), Some(U256::from(3_000_000)));
+    let execute_costs = &stats.costs[1];
+    assert_eq!(execute_costs.op_type, AggregatedActionType::ExecuteBlocks);
+    assert_eq!(execute_costs.blocks_count, 0);
+    assert_eq!(execute_costs.fee, U256::from(1_000_000));
+    assert_eq!(execute_costs.fee_per_block(), None);
+    assert_eq!(stats.total_fee(), U256::from(4_000_000));
+
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .load_spent_fee(None, since)
+            .await?,
+        U256::from(3_000_000)
+    );
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .load_spent_fee(Some(operator), since)
+            .await?,
+        U256::from(1_000_000)
+    );
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .load_spent_fee(None, chrono::Utc::now() + chrono::Duration::hours(1))
+            .await?,
+        U256::zero()
+    );
+
+    Ok(())
+}
+
+/// Checks the admin actions with the Ethereum sender:
+/// - Pause and resume are applied right away and determine the sender state.
+/// - Operation actions are pending until completed.
+/// - All the actions are kept in the history.
+#[db_test]
+async fn eth_sender_admin_actions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let state = EthereumSchema(&mut storage).load_sender_state().await?;
+    assert!(!state.paused);
+    assert!(state.last_change.is_none());
+    assert!(state.pending_actions.is_empty());
+
+    let pause = EthereumSchema(&mut storage)
+        .add_sender_admin_action(ETHSenderAdminActionType::Pause, None, None, "admin")
+        .await?;
+    assert!(pause.processed_at.is_some());
+    assert_eq!(pause.result.as_deref(), Some("Sending is paused"));
+    let repeated_pause = EthereumSchema(&mut storage)
+        .add_sender_admin_action(ETHSenderAdminActionType::Pause, None, None, "admin")
+        .await?;
+    assert_eq!(
+        repeated_pause.result.as_deref(),
+        Some("Sending is already paused")
+    );
+
+    // Store the operation to apply the actions to.
+    let params = EthereumTxParams::new("CommitBlocks".into(), None);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::CommitBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            params.priority_fee.clone(),
+            params.raw_tx.clone(),
+            None,
+        )
+        .await?;
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .is_eth_op_confirmed(response.id)
+            .await?,
+        Some(false)
+    );
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .is_eth_op_confirmed(response.id + 1)
+            .await?,
+        None
+    );
+
+    let bump = EthereumSchema(&mut storage)
+        .add_sender_admin_action(
+            ETHSenderAdminActionType::BumpGasPrice,
+            Some(response.id),
+            Some(U256::from(5000)),
+            "on-call",
+        )
+        .await?;
+    assert_eq!(bump.gas_price, Some(U256::from(5000)));
+    assert!(bump.processed_at.is_none());
+
+    let state = EthereumSchema(&mut storage).load_sender_state().await?;
+    assert!(state.paused);
+    assert_eq!(state.last_change, Some(repeated_pause));
+    assert_eq!(state.pending_actions, vec![bump.clone()]);
+
+    EthereumSchema(&mut storage)
+        .complete_sender_admin_action(bump.id, "Done")
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_sender_admin_action(ETHSenderAdminActionType::Resume, None, None, "admin")
+        .await?;
+
+    let state = EthereumSchema(&mut storage).load_sender_state().await?;
+    assert!(!state.paused);
+    assert!(state.pending_actions.is_empty());
+
+    let history = EthereumSchema(&mut storage)
+        .load_sender_admin_actions(10)
+        .await?;
+    let actions: Vec<_> = history.iter().map(|action| action.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            ETHSenderAdminActionType::Resume,
+            ETHSenderAdminActionType::BumpGasPrice,
+            ETHSenderAdminActionType::Pause,
+            ETHSenderAdminActionType::Pause,
+        ]
+    );
+    assert_eq!(history[1].result.as_deref(), Some("Done"));
+    assert_eq!(history[1].requested_by, "on-call");
+
+    Ok(())
+}
+
 /// Here we check `unprocessed` and `unconfirmed` operations getting.
 /// If there is no `ETHOperation` for `Operation`, it must be returend by `load_unprocessed_operations`.
 /// It must **not** be returned by `load_unconfirmed_operations`.
@@ -279,7 +614,9 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            params.priority_fee.clone(),
             params.raw_tx.clone(),
+            None,
         )
         .await?;
     EthereumSchema(&mut storage)
@@ -337,7 +674,9 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
             verify_params.op,
             verify_params.deadline_block as i64,
             verify_params.gas_price.clone(),
+            verify_params.priority_fee.clone(),
             verify_params.raw_tx.clone(),
+            None,
         )
         .await?;
     EthereumSchema(&mut storage)
@@ -31,6 +31,7 @@ use zksync_crypto::rand::{SeedableRng, XorShiftRng};
 pub(crate) mod chain;
 mod config;
 mod data_restore;
+mod eth_watch;
 mod ethereum;
 mod prover;
 mod tokens;
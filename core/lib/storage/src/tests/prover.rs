@@ -1,16 +1,24 @@
+// Built-in imports
+use std::sync::Arc;
 // External imports
 use anyhow::format_err;
+use chrono::Utc;
 // Workspace imports
-use zksync_types::prover::{ProverJob, ProverJobType};
+use zksync_prover_utils::api::ProverInputRequestAuxData;
+use zksync_types::prover::{ProverJob, ProverJobFilter, ProverJobStatus, ProverJobType};
 // Local imports
 use crate::test_data::{gen_sample_block, get_sample_aggregated_proof, get_sample_single_proof};
 use crate::tests::db_test;
-use crate::{prover::ProverSchema, QueryResult, StorageProcessor};
+use crate::{
+    artifacts::{ArtifactStore, FilesystemArtifactStore},
+    prover::ProverSchema,
+    QueryResult, StorageProcessor,
+};
 use zksync_types::BlockNumber;
 
 async fn get_idle_job_from_queue(mut storage: &mut StorageProcessor<'_>) -> QueryResult<ProverJob> {
     let job = ProverSchema(&mut storage)
-        .get_idle_prover_job_from_job_queue()
+        .get_idle_prover_job_from_job_queue(&Default::default())
         .await?;
 
     job.ok_or_else(|| format_err!("expect idle job from job queue"))
@@ -20,12 +28,338 @@ async fn get_idle_job_from_queue(mut storage: &mut StorageProcessor<'_>) -> Quer
 /// `prover_job_queue` table is locked when accessed, so it cannot be accessed simultaneously.
 #[db_test]
 async fn test_prover_job_queue(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    job_attempts(&mut storage).await?;
+    prover_registry(&mut storage).await?;
+    invalid_proofs(&mut storage).await?;
+    job_filter(&mut storage).await?;
     test_store_proof(&mut storage).await?;
     pending_jobs_count(&mut storage).await?;
 
     Ok(())
 }
 
+/// Checks that the stale jobs are returned to the queue until the maximum amount
+/// of attempts is reached, after which they are marked as failed.
+async fn job_attempts(mut storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
+    const MAX_ATTEMPTS: u32 = 2;
+
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(100),
+            BlockNumber(100),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+            Some(10),
+        )
+        .await?;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let job = get_idle_job_from_queue(&mut storage).await?;
+        assert_eq!(job.first_block, BlockNumber(100));
+        assert_eq!(job.attempts, attempt as i32);
+
+        // No heartbeats from the prover for a while.
+        sqlx::query("UPDATE prover_job_queue SET updated_at = now() - interval '1 hour'")
+            .execute(storage.conn())
+            .await?;
+        let failed_jobs = ProverSchema(&mut storage)
+            .mark_stale_jobs_as_idle(MAX_ATTEMPTS)
+            .await?;
+
+        if attempt < MAX_ATTEMPTS {
+            assert!(failed_jobs.is_empty());
+        } else {
+            assert_eq!(failed_jobs, vec![job.job_id]);
+        }
+    }
+
+    // Failed job is not given anymore and is not considered pending.
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue(&Default::default())
+        .await?;
+    assert!(job.is_none());
+    assert_eq!(ProverSchema(&mut storage).pending_jobs_count().await?, 0);
+    assert_eq!(ProverSchema(&mut storage).failed_jobs_count().await?, 1);
+
+    // Only failed jobs can be re-queued.
+    let failed_job_id = get_failed_job_id(&mut storage).await?;
+    assert!(
+        !ProverSchema(&mut storage)
+            .requeue_failed_job(failed_job_id + 1)
+            .await?
+    );
+    assert!(
+        ProverSchema(&mut storage)
+            .requeue_failed_job(failed_job_id)
+            .await?
+    );
+    assert!(
+        !ProverSchema(&mut storage)
+            .requeue_failed_job(failed_job_id)
+            .await?
+    );
+    assert_eq!(ProverSchema(&mut storage).failed_jobs_count().await?, 0);
+
+    // Re-queued job is given to the provers again with the attempts counter reset.
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.job_id, failed_job_id);
+    assert_eq!(job.attempts, 1);
+
+    // Leave the job failed, so it doesn't interfere with the other checks.
+    sqlx::query("UPDATE prover_job_queue SET job_status = $1 WHERE id = $2")
+        .bind(ProverJobStatus::Failed.to_number())
+        .bind(failed_job_id)
+        .execute(storage.conn())
+        .await?;
+
+    Ok(())
+}
+
+async fn get_failed_job_id(storage: &mut StorageProcessor<'_>) -> QueryResult<i32> {
+    let job_id =
+        sqlx::query_as::<_, (i32,)>("SELECT id FROM prover_job_queue WHERE job_status = $1")
+            .bind(ProverJobStatus::Failed.to_number())
+            .fetch_one(storage.conn())
+            .await?
+            .0;
+    Ok(job_id)
+}
+
+/// Checks that the completed and failed jobs are attributed to the provers they were given to.
+async fn prover_registry(mut storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
+    const PROVER_NAME: &str = "registry_prover";
+
+    ProverSchema(&mut storage)
+        .record_prover_request(
+            PROVER_NAME,
+            &ProverInputRequestAuxData {
+                version: Some("1.0.0".to_string()),
+                preferred_block_size: Some(6),
+                prefer_aggregated_proof: Some(false),
+                capabilities: None,
+            },
+        )
+        .await?;
+    for block in 200..202 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+                Some(10),
+            )
+            .await?;
+    }
+
+    // The first job is completed.
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    ProverSchema(&mut storage)
+        .record_prover_job_assigned(PROVER_NAME, job.job_id)
+        .await?;
+    ProverSchema(&mut storage)
+        .record_prover_is_working(job.job_id, PROVER_NAME)
+        .await?;
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+
+    // The second one becomes stale and is given to the prover again.
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    ProverSchema(&mut storage)
+        .record_prover_job_assigned(PROVER_NAME, job.job_id)
+        .await?;
+    sqlx::query("UPDATE prover_job_queue SET updated_at = now() - interval '1 hour' WHERE id = $1")
+        .bind(job.job_id)
+        .execute(storage.conn())
+        .await?;
+    ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(10)
+        .await?;
+
+    let provers = ProverSchema(&mut storage).load_provers().await?;
+    assert_eq!(provers.len(), 1);
+    assert_eq!(provers[0].name, PROVER_NAME);
+    assert_eq!(provers[0].version.as_deref(), Some("1.0.0"));
+    assert_eq!(provers[0].preferred_block_size, Some(6));
+    assert_eq!(provers[0].jobs_completed, 1);
+    assert_eq!(provers[0].jobs_failed, 1);
+    assert!(provers[0].current_job_id.is_none());
+
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.first_block, BlockNumber(201));
+    ProverSchema(&mut storage)
+        .record_prover_job_assigned(PROVER_NAME, job.job_id)
+        .await?;
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+
+    // Stopped prover is considered running again once it requests a job.
+    ProverSchema(&mut storage)
+        .record_prover_stop(PROVER_NAME)
+        .await?;
+    let provers = ProverSchema(&mut storage).load_provers().await?;
+    assert_eq!(provers[0].jobs_completed, 2);
+    assert!(provers[0].stopped_at.is_some());
+
+    ProverSchema(&mut storage)
+        .record_prover_request(
+            PROVER_NAME,
+            &ProverInputRequestAuxData {
+                version: Some("1.0.1".to_string()),
+                preferred_block_size: None,
+                prefer_aggregated_proof: Some(true),
+                capabilities: None,
+            },
+        )
+        .await?;
+    let provers = ProverSchema(&mut storage).load_provers().await?;
+    assert!(provers[0].stopped_at.is_none());
+    assert_eq!(provers[0].version.as_deref(), Some("1.0.1"));
+    assert_eq!(provers[0].prefer_aggregated_proof, Some(true));
+
+    Ok(())
+}
+
+/// Checks that the jobs with invalid proofs are returned to the queue until the maximum amount
+/// of attempts is reached, and the invalid proofs are attributed to the prover.
+async fn invalid_proofs(mut storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
+    const PROVER_NAME: &str = "invalid_proofs_prover";
+    const MAX_ATTEMPTS: u32 = 2;
+
+    ProverSchema(&mut storage)
+        .record_prover_request(PROVER_NAME, &Default::default())
+        .await?;
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(250),
+            BlockNumber(250),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+            Some(10),
+        )
+        .await?;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let job = get_idle_job_from_queue(&mut storage).await?;
+        assert_eq!(job.first_block, BlockNumber(250));
+        ProverSchema(&mut storage)
+            .record_prover_job_assigned(PROVER_NAME, job.job_id)
+            .await?;
+
+        let loaded_job = ProverSchema(&mut storage)
+            .get_prover_job_by_id(job.job_id)
+            .await?
+            .expect("Job is not stored");
+        assert_eq!(loaded_job.attempts, attempt as i32);
+
+        let failed = ProverSchema(&mut storage)
+            .record_invalid_proof(job.job_id, MAX_ATTEMPTS)
+            .await?;
+        assert_eq!(failed, attempt == MAX_ATTEMPTS);
+    }
+
+    // Failed job is not given anymore.
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue(&Default::default())
+        .await?;
+    assert!(job.is_none());
+
+    let provers = ProverSchema(&mut storage).load_provers().await?;
+    let prover = provers
+        .iter()
+        .find(|prover| prover.name == PROVER_NAME)
+        .expect("Prover is not registered");
+    assert_eq!(prover.invalid_proofs, MAX_ATTEMPTS as i64);
+    assert_eq!(prover.jobs_failed, MAX_ATTEMPTS as i64);
+    assert!(prover.current_job_id.is_none());
+
+    Ok(())
+}
+
+/// Checks that the jobs not satisfying the filter are skipped in favor of the next compatible ones.
+async fn job_filter(mut storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
+    for (block, block_size) in &[(300, 32), (301, 10)] {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(*block),
+                BlockNumber(*block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+                Some(*block_size),
+            )
+            .await?;
+    }
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(300),
+            BlockNumber(301),
+            Default::default(),
+            0,
+            ProverJobType::AggregatedProof,
+            None,
+        )
+        .await?;
+
+    // Neither the aggregated proof for two blocks nor the block of size 32 can be proven.
+    let filter = ProverJobFilter {
+        block_sizes: Some(vec![10]),
+        max_aggregated_blocks: Some(1),
+    };
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue(&filter)
+        .await?
+        .expect("Compatible job should be given");
+    assert_eq!(
+        (job.first_block, job.last_block),
+        (BlockNumber(301), BlockNumber(301))
+    );
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue(&filter)
+        .await?;
+    assert!(job.is_none());
+
+    // Prover capable of aggregating the proofs only.
+    let filter = ProverJobFilter {
+        block_sizes: Some(Vec::new()),
+        max_aggregated_blocks: Some(4),
+    };
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue(&filter)
+        .await?
+        .expect("Compatible job should be given");
+    assert_eq!(
+        (job.first_block, job.last_block),
+        (BlockNumber(300), BlockNumber(301))
+    );
+    ProverSchema(&mut storage)
+        .store_aggregated_proof(
+            job.job_id,
+            job.first_block,
+            job.last_block,
+            &get_sample_aggregated_proof(),
+        )
+        .await?;
+
+    // Prover without restrictions gets the remaining job.
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.first_block, BlockNumber(300));
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+
+    Ok(())
+}
+
 /// Checks that the single and aggregated proof can be stored and loaded.
 async fn test_store_proof(mut storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
     // Attempt to load the proof that was not stored should result in None.
@@ -72,6 +406,7 @@ async fn test_store_proof(mut storage: &mut StorageProcessor<'_>) -> QueryResult
             job_data.clone(),
             0,
             ProverJobType::SingleProof,
+            Some(10),
         )
         .await;
     let stored_aggregated_job = ProverSchema(&mut storage)
@@ -81,6 +416,7 @@ async fn test_store_proof(mut storage: &mut StorageProcessor<'_>) -> QueryResult
             job_data,
             1,
             ProverJobType::AggregatedProof,
+            None,
         )
         .await;
 
@@ -136,6 +472,7 @@ async fn pending_jobs_count(mut storage: &mut StorageProcessor<'_>) -> QueryResu
             Default::default(),
             1,
             ProverJobType::SingleProof,
+            Some(10),
         )
         .await?;
     ProverSchema(&mut storage)
@@ -145,6 +482,7 @@ async fn pending_jobs_count(mut storage: &mut StorageProcessor<'_>) -> QueryResu
             Default::default(),
             1,
             ProverJobType::SingleProof,
+            Some(10),
         )
         .await?;
     ProverSchema(&mut storage)
@@ -154,6 +492,7 @@ async fn pending_jobs_count(mut storage: &mut StorageProcessor<'_>) -> QueryResu
             Default::default(),
             0,
             ProverJobType::AggregatedProof,
+            None,
         )
         .await?;
 
@@ -203,6 +542,34 @@ async fn pending_jobs_count(mut storage: &mut StorageProcessor<'_>) -> QueryResu
     let jobs_count = ProverSchema(&mut storage).pending_jobs_count().await?;
     assert_eq!(jobs_count, 2);
 
+    // Pending jobs are grouped by the job type and block size.
+    let pending_jobs = ProverSchema(&mut storage).load_pending_jobs().await?;
+    assert_eq!(pending_jobs.len(), 2);
+    let single_proof_jobs = pending_jobs
+        .iter()
+        .find(|jobs| jobs.job_type == ProverJobType::SingleProof.to_string())
+        .expect("No pending single proof jobs");
+    assert_eq!(single_proof_jobs.block_size, Some(10));
+    assert_eq!(single_proof_jobs.jobs_count, 1);
+    let aggregated_proof_jobs = pending_jobs
+        .iter()
+        .find(|jobs| jobs.job_type == ProverJobType::AggregatedProof.to_string())
+        .expect("No pending aggregated proof jobs");
+    assert_eq!(aggregated_proof_jobs.block_size, None);
+    assert_eq!(aggregated_proof_jobs.jobs_count, 1);
+
+    // Proof times are known for the jobs completed by the registered provers.
+    let proof_times = ProverSchema(&mut storage)
+        .load_average_proof_times(Utc::now() - chrono::Duration::hours(1))
+        .await?;
+    assert!(proof_times.iter().any(|time| {
+        time.job_type == ProverJobType::SingleProof.to_string() && time.block_size == Some(10)
+    }));
+    let proof_times = ProverSchema(&mut storage)
+        .load_average_proof_times(Utc::now() + chrono::Duration::hours(1))
+        .await?;
+    assert!(proof_times.is_empty());
+
     Ok(())
 }
 
@@ -263,3 +630,95 @@ async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks that the witnesses are kept in the artifact store if one is configured,
+/// and that the witnesses stored in the database can be moved to it.
+#[db_test]
+async fn test_artifact_store(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const BLOCK_SIZE: usize = 100;
+    let artifacts_dir = tempfile::tempdir()?;
+    let artifact_store: Arc<dyn ArtifactStore> =
+        Arc::new(FilesystemArtifactStore::new(artifacts_dir.path()));
+
+    // Artifacts are content-addressed.
+    let reference = artifact_store.put(b"artifact".to_vec()).await?;
+    assert_eq!(artifact_store.put(b"artifact".to_vec()).await?, reference);
+    assert_eq!(artifact_store.get(&reference).await?, b"artifact".to_vec());
+    assert!(artifact_store.get("../artifact").await.is_err());
+
+    for block_number in 1..=2 {
+        storage
+            .chain()
+            .block_schema()
+            .save_block(gen_sample_block(
+                BlockNumber(block_number),
+                BLOCK_SIZE,
+                Default::default(),
+            ))
+            .await?;
+    }
+    let witness = |block_number: u32| serde_json::to_value(format!("witness {}", block_number));
+
+    // The first witness is stored in the database, the second one in the artifact store.
+    let mut storage = storage.with_artifact_store(None);
+    storage
+        .prover_schema()
+        .store_witness(BlockNumber(1), witness(1)?)
+        .await?;
+    let mut storage = storage.with_artifact_store(Some(artifact_store.clone()));
+    storage
+        .prover_schema()
+        .store_witness(BlockNumber(2), witness(2)?)
+        .await?;
+    for block_number in 1..=2 {
+        let loaded = storage
+            .prover_schema()
+            .get_witness(BlockNumber(block_number))
+            .await?;
+        assert_eq!(loaded, Some(witness(block_number)?));
+    }
+
+    // The witness kept in the artifact store cannot be loaded without it,
+    // but is still known to exist.
+    let mut storage = storage.with_artifact_store(None);
+    for block_number in 1..=2 {
+        assert!(
+            storage
+                .prover_schema()
+                .witness_exists(BlockNumber(block_number))
+                .await?
+        );
+    }
+    assert!(
+        !storage
+            .prover_schema()
+            .witness_exists(BlockNumber(3))
+            .await?
+    );
+    assert!(storage
+        .prover_schema()
+        .get_witness(BlockNumber(2))
+        .await
+        .is_err());
+    assert!(storage
+        .prover_schema()
+        .move_artifacts_to_store(10)
+        .await
+        .is_err());
+
+    // Move the first witness to the artifact store.
+    let mut storage = storage.with_artifact_store(Some(artifact_store));
+    let moved = storage.prover_schema().move_artifacts_to_store(10).await?;
+    assert_eq!(moved, 1);
+    let moved = storage.prover_schema().move_artifacts_to_store(10).await?;
+    assert_eq!(moved, 0);
+
+    let mut storage = storage.with_artifact_store(None);
+    assert!(storage
+        .prover_schema()
+        .get_witness(BlockNumber(1))
+        .await
+        .is_err());
+
+    Ok(())
+}
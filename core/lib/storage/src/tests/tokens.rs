@@ -1,7 +1,10 @@
 // External imports
 use num::{rational::Ratio, BigUint};
 // Workspace imports
-use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{
+    tokens::{FeeTokenEligibility, FeeTokenSettings, TokenMarketVolume},
+    Fee, OutputFeeType, Token, TokenId, TokenLike, TokenPrice,
+};
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
 use crate::tests::db_test;
@@ -193,3 +196,244 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks the store/load/remove routine for the fee token overrides and their history.
+#[db_test]
+async fn test_fee_token_overrides(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    // No overrides exist yet.
+    assert!(storage
+        .tokens_schema()
+        .get_fee_token_override(TOKEN_ID)
+        .await?
+        .is_none());
+    assert!(storage
+        .tokens_schema()
+        .load_fee_token_overrides()
+        .await?
+        .is_empty());
+
+    // Deny the token.
+    let denied = FeeTokenSettings {
+        eligibility: Some(FeeTokenEligibility::Denied),
+        ..Default::default()
+    };
+    storage
+        .tokens_schema()
+        .store_fee_token_override(TOKEN_ID, denied.clone(), "alice")
+        .await?;
+
+    // Then allow it with a fixed price, the previous override should be replaced.
+    let allowed = FeeTokenSettings {
+        eligibility: Some(FeeTokenEligibility::Allowed),
+        subsidized: Some(false),
+        fixed_usd_price: Some(Ratio::new(BigUint::from(1u32), BigUint::from(4u32))),
+    };
+    let stored = storage
+        .tokens_schema()
+        .store_fee_token_override(TOKEN_ID, allowed.clone(), "bob")
+        .await?;
+    assert_eq!(stored.settings, allowed);
+    assert_eq!(stored.updated_by, "bob");
+
+    let loaded = storage
+        .tokens_schema()
+        .get_fee_token_override(TOKEN_ID)
+        .await?
+        .expect("couldn't load fee token override");
+    assert_eq!(loaded, stored);
+    assert_eq!(
+        storage.tokens_schema().load_fee_token_overrides().await?,
+        vec![stored]
+    );
+
+    // Remove the override, removing it twice should have no effect.
+    assert!(
+        storage
+            .tokens_schema()
+            .remove_fee_token_override(TOKEN_ID, "carol")
+            .await?
+    );
+    assert!(
+        !storage
+            .tokens_schema()
+            .remove_fee_token_override(TOKEN_ID, "carol")
+            .await?
+    );
+    assert!(storage
+        .tokens_schema()
+        .get_fee_token_override(TOKEN_ID)
+        .await?
+        .is_none());
+
+    // Every change should be recorded in the history, latest first.
+    let history = storage
+        .tokens_schema()
+        .load_fee_token_override_history(TOKEN_ID)
+        .await?;
+    let history: Vec<_> = history
+        .into_iter()
+        .map(|change| (change.settings, change.changed_by))
+        .collect();
+    assert_eq!(
+        history,
+        vec![
+            (None, "carol".to_string()),
+            (Some(allowed), "bob".to_string()),
+            (Some(denied), "alice".to_string()),
+        ]
+    );
+
+    Ok(())
+}
+
+/// Checks the store/load/remove routine for the ticker price and fee history.
+#[db_test]
+async fn test_ticker_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    let now = chrono::Utc::now();
+    let hours_ago = |hours: i64| now - chrono::Duration::hours(hours);
+
+    // Every price update should be kept in the history.
+    for (hours, price) in vec![(3, 100u32), (2, 110), (1, 105)] {
+        let price = TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(price)),
+            last_updated: hours_ago(hours),
+        };
+        storage
+            .tokens_schema()
+            .update_historical_ticker_price(TOKEN_ID, price)
+            .await?;
+    }
+    let history = storage
+        .tokens_schema()
+        .load_ticker_price_history(TOKEN_ID, Some(hours_ago(4)), None, 10)
+        .await?;
+    let prices: Vec<_> = history
+        .into_iter()
+        .map(|price| price.usd_price.to_integer())
+        .collect();
+    assert_eq!(
+        prices,
+        vec![
+            BigUint::from(105u32),
+            BigUint::from(110u32),
+            BigUint::from(100u32)
+        ]
+    );
+
+    // Time range and limit are respected.
+    let history = storage
+        .tokens_schema()
+        .load_ticker_price_history(TOKEN_ID, Some(hours_ago(4)), Some(hours_ago(2)), 1)
+        .await?;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].usd_price.to_integer(), BigUint::from(110u32));
+
+    // Store the fees sampled in different time.
+    let fee = |total_fee: u32| Fee {
+        fee_type: OutputFeeType::Withdraw,
+        gas_tx_amount: BigUint::from(10u32),
+        gas_price_wei: BigUint::from(20u32),
+        gas_fee: BigUint::from(total_fee - 1),
+        zkp_fee: BigUint::from(1u32),
+        total_fee: BigUint::from(total_fee),
+    };
+    storage
+        .tokens_schema()
+        .store_fee_history(TOKEN_ID, &[fee(100)], hours_ago(3))
+        .await?;
+    storage
+        .tokens_schema()
+        .store_fee_history(TOKEN_ID, &[fee(200)], hours_ago(1))
+        .await?;
+
+    let history = storage
+        .tokens_schema()
+        .load_fee_history(TOKEN_ID, Some(hours_ago(4)), None, 10)
+        .await?;
+    let fees: Vec<_> = history
+        .iter()
+        .map(|entry| (entry.fee.fee_type, entry.fee.total_fee.clone()))
+        .collect();
+    assert_eq!(
+        fees,
+        vec![
+            (OutputFeeType::Withdraw, BigUint::from(200u32)),
+            (OutputFeeType::Withdraw, BigUint::from(100u32))
+        ]
+    );
+
+    // Outdated entries are removed.
+    storage
+        .tokens_schema()
+        .remove_outdated_ticker_history(hours_ago(2))
+        .await?;
+    let prices = storage
+        .tokens_schema()
+        .load_ticker_price_history(TOKEN_ID, Some(hours_ago(4)), None, 10)
+        .await?;
+    assert_eq!(prices.len(), 1);
+    let fees = storage
+        .tokens_schema()
+        .load_fee_history(TOKEN_ID, Some(hours_ago(4)), None, 10)
+        .await?;
+    assert_eq!(fees.len(), 1);
+    assert_eq!(fees[0].fee.total_fee, BigUint::from(200u32));
+
+    Ok(())
+}
+
+/// Checks that the fee token override with an unknown eligibility results in an error
+/// instead of a panic.
+#[db_test]
+async fn test_fee_token_override_unknown_eligibility(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    sqlx::query(
+        "INSERT INTO fee_token_overrides ( token_id, eligibility, updated_by, updated_at )
+        VALUES ( $1, 'Unknown', 'alice', now() )",
+    )
+    .bind(i32::from(*TOKEN_ID))
+    .execute(storage.conn())
+    .await?;
+
+    assert!(storage
+        .tokens_schema()
+        .get_fee_token_override(TOKEN_ID)
+        .await
+        .is_err());
+    assert!(storage
+        .tokens_schema()
+        .load_fee_token_overrides()
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+/// Checks that the fee history entry with an unknown fee type results in an error
+/// instead of a panic.
+#[db_test]
+async fn test_fee_history_unknown_fee_type(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    sqlx::query(
+        "INSERT INTO ticker_fee_history ( token_id, fee_type, gas_tx_amount, gas_price_wei, gas_fee, zkp_fee, total_fee, sampled_at )
+        VALUES ( $1, '\"Unknown\"', 1, 1, 1, 1, 2, now() )",
+    )
+    .bind(i32::from(*TOKEN_ID))
+    .execute(storage.conn())
+    .await?;
+
+    assert!(storage
+        .tokens_schema()
+        .load_fee_history(TOKEN_ID, None, None, 10)
+        .await
+        .is_err());
+
+    Ok(())
+}
@@ -1,13 +1,22 @@
 // Built-in deps
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 // Workspace imports
-use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{
+    tokens::{FeeTokenOverride, FeeTokenOverrideChange, FeeTokenSettings},
+    Fee, FeeHistoryEntry, Token, TokenId, TokenLike, TokenPrice,
+};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken};
+use self::records::{
+    biguint_to_stored_decimal, fee_token_eligibility_to_stored_string,
+    fixed_usd_price_to_stored_decimal, DBMarketVolume, DbFeeHistoryEntry, DbFeeTokenOverride,
+    DbFeeTokenOverrideChange, DbTickerPrice, DbToken,
+};
 use crate::tokens::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
@@ -242,7 +251,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_price.map(|p| p.into()))
     }
 
-    /// Updates price in USD for the given token.
+    /// Updates price in USD for the given token and appends it to the price history.
     ///
     /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
     /// so the number might get rounded.
@@ -253,6 +262,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
     ) -> QueryResult<()> {
         let start = Instant::now();
         let usd_price_rounded = ratio_to_big_decimal(&price.usd_price, STORED_USD_PRICE_PRECISION);
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             r#"
             INSERT INTO ticker_price ( token_id, usd_price, last_updated )
@@ -265,10 +275,311 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             usd_price_rounded.clone(),
             price.last_updated
         )
-        .fetch_optional(self.0.conn())
+        .fetch_optional(transaction.conn())
         .await?;
 
+        sqlx::query!(
+            r#"
+            INSERT INTO ticker_price_history ( token_id, usd_price, last_updated )
+            VALUES ( $1, $2, $3 )
+            "#,
+            i32::from(*token_id),
+            usd_price_rounded,
+            price.last_updated
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
         metrics::histogram!("sql.token.update_historical_ticker_price", start.elapsed());
         Ok(())
     }
+
+    /// Loads the prices of the given token received within the time range, latest prices first.
+    pub async fn load_ticker_price_history(
+        &mut self,
+        token_id: TokenId,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: u32,
+    ) -> QueryResult<Vec<TokenPrice>> {
+        let start = Instant::now();
+        let prices = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT token_id, usd_price, last_updated FROM ticker_price_history
+            WHERE token_id = $1
+                AND ($2::timestamptz IS NULL OR last_updated >= $2)
+                AND ($3::timestamptz IS NULL OR last_updated <= $3)
+            ORDER BY last_updated DESC, id DESC
+            LIMIT $4
+            "#,
+            i32::from(*token_id),
+            from,
+            to,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_ticker_price_history", start.elapsed());
+        Ok(prices.into_iter().map(|p| p.into()).collect())
+    }
+
+    /// Stores the fees computed by the fee ticker for the given token.
+    pub async fn store_fee_history(
+        &mut self,
+        token_id: TokenId,
+        fees: &[Fee],
+        sampled_at: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        for fee in fees {
+            sqlx::query!(
+                r#"
+                INSERT INTO ticker_fee_history (
+                    token_id, fee_type, gas_tx_amount, gas_price_wei, gas_fee, zkp_fee, total_fee, sampled_at
+                )
+                VALUES ( $1, $2, $3, $4, $5, $6, $7, $8 )
+                "#,
+                i32::from(*token_id),
+                serde_json::to_value(&fee.fee_type).expect("Cannot serialize fee type"),
+                biguint_to_stored_decimal(&fee.gas_tx_amount),
+                biguint_to_stored_decimal(&fee.gas_price_wei),
+                biguint_to_stored_decimal(&fee.gas_fee),
+                biguint_to_stored_decimal(&fee.zkp_fee),
+                biguint_to_stored_decimal(&fee.total_fee),
+                sampled_at
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.store_fee_history", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the fees in the given token computed within the time range, latest fees first.
+    pub async fn load_fee_history(
+        &mut self,
+        token_id: TokenId,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: u32,
+    ) -> QueryResult<Vec<FeeHistoryEntry>> {
+        let start = Instant::now();
+        let fees = sqlx::query_as!(
+            DbFeeHistoryEntry,
+            r#"
+            SELECT * FROM ticker_fee_history
+            WHERE token_id = $1
+                AND ($2::timestamptz IS NULL OR sampled_at >= $2)
+                AND ($3::timestamptz IS NULL OR sampled_at <= $3)
+            ORDER BY sampled_at DESC, id DESC
+            LIMIT $4
+            "#,
+            i32::from(*token_id),
+            from,
+            to,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_fee_history", start.elapsed());
+        fees.into_iter().map(FeeHistoryEntry::try_from).collect()
+    }
+
+    /// Removes the price and fee history entries older than the given timestamp.
+    pub async fn remove_outdated_ticker_history(
+        &mut self,
+        older_than: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "DELETE FROM ticker_price_history WHERE last_updated < $1",
+            older_than
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "DELETE FROM ticker_fee_history WHERE sampled_at < $1",
+            older_than
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.remove_outdated_ticker_history", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads fee settings overrides for all the tokens that have them.
+    pub async fn load_fee_token_overrides(&mut self) -> QueryResult<Vec<FeeTokenOverride>> {
+        let start = Instant::now();
+        let overrides = sqlx::query_as!(
+            DbFeeTokenOverride,
+            r#"
+            SELECT * FROM fee_token_overrides
+            ORDER BY token_id ASC
+            "#,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_fee_token_overrides", start.elapsed());
+        overrides
+            .into_iter()
+            .map(FeeTokenOverride::try_from)
+            .collect()
+    }
+
+    /// Given token id, returns its fee settings override if there is one.
+    pub async fn get_fee_token_override(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<FeeTokenOverride>> {
+        let start = Instant::now();
+        let db_override = sqlx::query_as!(
+            DbFeeTokenOverride,
+            r#"
+            SELECT * FROM fee_token_overrides
+            WHERE token_id = $1
+            LIMIT 1
+            "#,
+            i32::from(*token_id)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_fee_token_override", start.elapsed());
+        db_override.map(FeeTokenOverride::try_from).transpose()
+    }
+
+    /// Sets fee settings override for the given token and records the change
+    /// in the overrides history.
+    pub async fn store_fee_token_override(
+        &mut self,
+        token_id: TokenId,
+        settings: FeeTokenSettings,
+        updated_by: &str,
+    ) -> QueryResult<FeeTokenOverride> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let eligibility = settings
+            .eligibility
+            .map(fee_token_eligibility_to_stored_string);
+        let fixed_usd_price = fixed_usd_price_to_stored_decimal(&settings);
+
+        let db_override = sqlx::query_as!(
+            DbFeeTokenOverride,
+            r#"
+            INSERT INTO fee_token_overrides ( token_id, eligibility, subsidized, fixed_usd_price, updated_by, updated_at )
+            VALUES ( $1, $2, $3, $4, $5, now() )
+            ON CONFLICT (token_id)
+            DO
+              UPDATE SET eligibility = $2, subsidized = $3, fixed_usd_price = $4, updated_by = $5, updated_at = now()
+            RETURNING *
+            "#,
+            i32::from(*token_id),
+            eligibility,
+            settings.subsidized,
+            fixed_usd_price,
+            updated_by,
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_token_overrides_history ( token_id, removed, eligibility, subsidized, fixed_usd_price, changed_by, changed_at )
+            VALUES ( $1, false, $2, $3, $4, $5, $6 )
+            "#,
+            db_override.token_id,
+            db_override.eligibility,
+            db_override.subsidized,
+            db_override.fixed_usd_price,
+            db_override.updated_by,
+            db_override.updated_at,
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.store_fee_token_override", start.elapsed());
+        FeeTokenOverride::try_from(db_override)
+    }
+
+    /// Removes fee settings override for the given token and records the change
+    /// in the overrides history.
+    ///
+    /// Returns `false` if there was no override for the token.
+    pub async fn remove_fee_token_override(
+        &mut self,
+        token_id: TokenId,
+        removed_by: &str,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM fee_token_overrides
+            WHERE token_id = $1
+            "#,
+            i32::from(*token_id)
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        if removed {
+            sqlx::query!(
+                r#"
+                INSERT INTO fee_token_overrides_history ( token_id, removed, changed_by, changed_at )
+                VALUES ( $1, true, $2, now() )
+                "#,
+                i32::from(*token_id),
+                removed_by,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.remove_fee_token_override", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Loads the history of fee settings changes for the given token, latest changes first.
+    pub async fn load_fee_token_override_history(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Vec<FeeTokenOverrideChange>> {
+        let start = Instant::now();
+        let changes = sqlx::query_as!(
+            DbFeeTokenOverrideChange,
+            r#"
+            SELECT * FROM fee_token_overrides_history
+            WHERE token_id = $1
+            ORDER BY id DESC
+            "#,
+            i32::from(*token_id)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_fee_token_override_history", start.elapsed());
+        changes
+            .into_iter()
+            .map(FeeTokenOverrideChange::try_from)
+            .collect()
+    }
 }
@@ -1,13 +1,22 @@
+// Built-in deps
+use std::convert::TryFrom;
 // External imports
+use anyhow::format_err;
+use num::{bigint::ToBigInt, BigInt, BigUint};
 use serde::{Deserialize, Serialize};
 use sqlx::{types::BigDecimal, FromRow};
 // Workspace imports
 // Local imports
 use crate::tokens::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
-use zksync_types::tokens::{TokenMarketVolume, TokenPrice};
-use zksync_types::{Token, TokenId};
-use zksync_utils::big_decimal_to_ratio;
+use zksync_types::tokens::{
+    FeeTokenEligibility, FeeTokenOverride, FeeTokenOverrideChange, FeeTokenSettings,
+    TokenMarketVolume, TokenPrice,
+};
+use zksync_types::{Fee, FeeHistoryEntry, OutputFeeType, Token, TokenId};
+use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
+
+use super::STORED_USD_PRICE_PRECISION;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
 pub struct DbToken {
@@ -55,6 +64,52 @@ impl Into<TokenPrice> for DbTickerPrice {
     }
 }
 
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFeeHistoryEntry {
+    pub id: i64,
+    pub token_id: i32,
+    pub fee_type: serde_json::Value,
+    pub gas_tx_amount: BigDecimal,
+    pub gas_price_wei: BigDecimal,
+    pub gas_fee: BigDecimal,
+    pub zkp_fee: BigDecimal,
+    pub total_fee: BigDecimal,
+    pub sampled_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbFeeHistoryEntry> for FeeHistoryEntry {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbFeeHistoryEntry) -> Result<Self, Self::Error> {
+        let fee_type: OutputFeeType = serde_json::from_value(value.fee_type.clone())
+            .map_err(|_| format_err!("Invalid fee type in the database: {}", value.fee_type))?;
+
+        Ok(Self {
+            token_id: TokenId(value.token_id as u16),
+            fee: Fee {
+                fee_type,
+                gas_tx_amount: stored_decimal_to_biguint(&value.gas_tx_amount),
+                gas_price_wei: stored_decimal_to_biguint(&value.gas_price_wei),
+                gas_fee: stored_decimal_to_biguint(&value.gas_fee),
+                zkp_fee: stored_decimal_to_biguint(&value.zkp_fee),
+                total_fee: stored_decimal_to_biguint(&value.total_fee),
+            },
+            sampled_at: value.sampled_at,
+        })
+    }
+}
+
+pub(crate) fn biguint_to_stored_decimal(value: &BigUint) -> BigDecimal {
+    BigDecimal::from(BigInt::from(value.clone()))
+}
+
+fn stored_decimal_to_biguint(value: &BigDecimal) -> BigUint {
+    value
+        .to_bigint()
+        .and_then(|value| value.to_biguint())
+        .expect("Fee amount could not be negative")
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct DBMarketVolume {
     pub token_id: i32,
@@ -71,3 +126,108 @@ impl Into<TokenMarketVolume> for DBMarketVolume {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFeeTokenOverride {
+    pub token_id: i32,
+    pub eligibility: Option<String>,
+    pub subsidized: Option<bool>,
+    pub fixed_usd_price: Option<BigDecimal>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbFeeTokenOverride> for FeeTokenOverride {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbFeeTokenOverride) -> Result<Self, Self::Error> {
+        Ok(Self {
+            token_id: TokenId(value.token_id as u16),
+            settings: stored_fee_token_settings(
+                value.eligibility,
+                value.subsidized,
+                value.fixed_usd_price,
+            )?,
+            updated_by: value.updated_by,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFeeTokenOverrideChange {
+    pub id: i64,
+    pub token_id: i32,
+    pub removed: bool,
+    pub eligibility: Option<String>,
+    pub subsidized: Option<bool>,
+    pub fixed_usd_price: Option<BigDecimal>,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbFeeTokenOverrideChange> for FeeTokenOverrideChange {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbFeeTokenOverrideChange) -> Result<Self, Self::Error> {
+        let settings = if value.removed {
+            None
+        } else {
+            Some(stored_fee_token_settings(
+                value.eligibility,
+                value.subsidized,
+                value.fixed_usd_price,
+            )?)
+        };
+
+        Ok(Self {
+            id: value.id,
+            token_id: TokenId(value.token_id as u16),
+            settings,
+            changed_by: value.changed_by,
+            changed_at: value.changed_at,
+        })
+    }
+}
+
+pub(crate) fn fee_token_eligibility_to_stored_string(
+    eligibility: FeeTokenEligibility,
+) -> &'static str {
+    match eligibility {
+        FeeTokenEligibility::Allowed => "Allowed",
+        FeeTokenEligibility::Denied => "Denied",
+    }
+}
+
+pub(crate) fn fixed_usd_price_to_stored_decimal(settings: &FeeTokenSettings) -> Option<BigDecimal> {
+    settings
+        .fixed_usd_price
+        .as_ref()
+        .map(|price| ratio_to_big_decimal(price, STORED_USD_PRICE_PRECISION))
+}
+
+fn stored_fee_token_settings(
+    eligibility: Option<String>,
+    subsidized: Option<bool>,
+    fixed_usd_price: Option<BigDecimal>,
+) -> anyhow::Result<FeeTokenSettings> {
+    let eligibility = eligibility
+        .map(|eligibility| match eligibility.as_str() {
+            "Allowed" => Ok(FeeTokenEligibility::Allowed),
+            "Denied" => Ok(FeeTokenEligibility::Denied),
+            other => Err(format_err!(
+                "Unknown fee token eligibility stored in the database: {}",
+                other
+            )),
+        })
+        .transpose()?;
+    let fixed_usd_price = fixed_usd_price
+        .map(|price| big_decimal_to_ratio(&price))
+        .transpose()?;
+
+    Ok(FeeTokenSettings {
+        eligibility,
+        subsidized,
+        fixed_usd_price,
+    })
+}
@@ -2,11 +2,12 @@
 // Built-in deps
 use std::{convert::TryFrom, fmt, str::FromStr};
 // External uses
+use chrono::{DateTime, Utc};
 use ethabi::{decode, ParamType};
 use serde::{Deserialize, Serialize};
 // Local uses
 use crate::aggregated_operations::{AggregatedActionType, AggregatedOperation};
-use zksync_basic_types::{Log, H256, U256};
+use zksync_basic_types::{Address, Log, H256, U256};
 
 /// Numerical identifier of the Ethereum operation.
 pub type EthOpId = i64;
@@ -56,12 +57,19 @@ pub struct ETHOperation {
     pub op_type: AggregatedActionType,
     /// Optional ZKSync operation associated with Ethereum operation.
     pub op: Option<(i64, AggregatedOperation)>,
+    /// Operator account sending the transactions. `None` stands for the main
+    /// operator account, otherwise the operation belongs to the nonce lane of
+    /// one of the additional operator accounts.
+    pub sender: Option<Address>,
     /// Used nonce (fixed for all the sent transactions).
     pub nonce: U256,
     /// Deadline block of the last sent transaction.
     pub last_deadline_block: u64,
     /// Gas price used in the last sent transaction.
     pub last_used_gas_price: U256,
+    /// Priority fee used in the last sent transaction. Set only for EIP-1559
+    /// transactions, in which case `last_used_gas_price` holds the max fee per gas.
+    pub last_used_priority_fee: Option<U256>,
     /// Hashes of all the sent transactions.
     pub used_tx_hashes: Vec<H256>,
     /// Tx payload (not signed).
@@ -99,12 +107,92 @@ impl PartialEq for ETHOperation {
         (self.id == other.id)
             && (self.last_deadline_block == other.last_deadline_block)
             && (self.last_used_gas_price == other.last_used_gas_price)
+            && (self.last_used_priority_fee == other.last_used_priority_fee)
             && (self.used_tx_hashes == other.used_tx_hashes)
             && (self.confirmed == other.confirmed)
             && (self.final_hash == other.final_hash)
     }
 }
 
+/// Type of the admin action with the Ethereum sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ETHSenderAdminActionType {
+    /// Stop sending the transactions, including the replacements of the stuck ones.
+    Pause,
+    /// Resume sending the transactions.
+    Resume,
+    /// Replace the pending transaction of the operation with the increased gas price
+    /// without waiting for the transaction to get stuck.
+    BumpGasPrice,
+    /// Broadcast the last signed transaction of the operation once again.
+    Resend,
+}
+
+impl ETHSenderAdminActionType {
+    /// Returns `true` if the action is applied to the particular Ethereum operation.
+    pub fn is_operation_action(self) -> bool {
+        matches!(self, Self::BumpGasPrice | Self::Resend)
+    }
+}
+
+impl fmt::Display for ETHSenderAdminActionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let action = match self {
+            Self::Pause => "Pause",
+            Self::Resume => "Resume",
+            Self::BumpGasPrice => "BumpGasPrice",
+            Self::Resend => "Resend",
+        };
+        write!(f, "{}", action)
+    }
+}
+
+impl FromStr for ETHSenderAdminActionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pause" => Ok(Self::Pause),
+            "Resume" => Ok(Self::Resume),
+            "BumpGasPrice" => Ok(Self::BumpGasPrice),
+            "Resend" => Ok(Self::Resend),
+            _ => Err("Incorrect Ethereum sender admin action type".to_owned()),
+        }
+    }
+}
+
+/// Admin action with the Ethereum sender requested via the admin API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ETHSenderAdminAction {
+    pub id: i64,
+    pub action: ETHSenderAdminActionType,
+    /// Ethereum operation the action is applied to, set only for the operation actions.
+    pub eth_op_id: Option<EthOpId>,
+    /// Gas price requested for the `BumpGasPrice` action. If not set, the price is chosen
+    /// the same way as for the stuck transactions.
+    pub gas_price: Option<U256>,
+    /// Subject of the auth token used to request the action.
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    /// Moment the action was performed, `None` if the action is pending.
+    pub processed_at: Option<DateTime<Utc>>,
+    /// Outcome of the performed action.
+    pub result: Option<String>,
+}
+
+/// Runtime state of the Ethereum sender controlled via the admin API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ETHSenderState {
+    /// Whether sending the transactions is paused.
+    pub paused: bool,
+    /// The last pause or resume action, if any.
+    pub last_change: Option<ETHSenderAdminAction>,
+    /// Admin actions waiting to be performed.
+    pub pending_actions: Vec<ETHSenderAdminAction>,
+}
+
 /// Structure representing the result of the insertion of the Ethereum
 /// operation into the database.
 /// Contains the assigned nonce and ID for the operation.
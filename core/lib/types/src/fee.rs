@@ -1,10 +1,12 @@
+use chrono::{DateTime, Utc};
 use num::rational::Ratio;
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 
 use crate::helpers::{closest_packable_fee_amount, pack_fee_amount, unpack_fee_amount};
 use crate::tokens::ChangePubKeyFeeTypeArg;
-use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str};
+use crate::TokenId;
+use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal};
 
 /// Type of the fee calculation pattern.
 /// Unlike the `TxFeeTypes`, this enum represents the fee
@@ -40,13 +42,55 @@ pub struct Fee {
 pub struct BatchFee {
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Fees of the batch transactions if they were sent separately, in the same token.
+    #[serde(default)]
+    pub transactions: Vec<Fee>,
+    /// Ways to pay the batch fee in the tokens of the batch transactions, the cheapest one goes first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<BatchFeeOption>,
+}
+
+/// Fee for the whole batch paid in the token of one of its transactions.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFeeOption {
+    pub token: TokenId,
+    /// Indices of the batch transactions which can carry the fee in this token.
+    pub payers: Vec<usize>,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub total_fee_usd: Ratio<BigUint>,
+}
+
+/// Fee computed by the fee ticker at some point in the past.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryEntry {
+    pub token_id: TokenId,
+    #[serde(flatten)]
+    pub fee: Fee,
+    pub sampled_at: DateTime<Utc>,
 }
 
 impl BatchFee {
-    pub fn new(zkp_fee: &Ratio<BigUint>, gas_fee: &Ratio<BigUint>) -> BatchFee {
+    pub fn new(
+        zkp_fee: &Ratio<BigUint>,
+        gas_fee: &Ratio<BigUint>,
+        transactions: Vec<Fee>,
+    ) -> BatchFee {
         let (_, _, mut total_fee) = total_fee(zkp_fee, gas_fee);
         total_fee = closest_packable_fee_amount(&total_fee);
-        BatchFee { total_fee }
+        BatchFee {
+            total_fee,
+            transactions,
+            options: Vec::new(),
+        }
+    }
+
+    /// Returns the cheapest way to pay the batch fee, if the payment options were requested.
+    pub fn cheapest_option(&self) -> Option<&BatchFeeOption> {
+        self.options.first()
     }
 }
 
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use zksync_basic_types::BlockNumber;
 
 #[derive(Debug, Clone)]
@@ -5,6 +6,8 @@ pub enum ProverJobStatus {
     Idle = 0,
     InProgress = 1,
     Done = 2,
+    /// Job was not completed after the maximum amount of attempts and is not given to the provers anymore.
+    Failed = 3,
 }
 
 impl ProverJobStatus {
@@ -13,6 +16,7 @@ impl ProverJobStatus {
             ProverJobStatus::Idle => 0,
             ProverJobStatus::InProgress => 1,
             ProverJobStatus::Done => 2,
+            ProverJobStatus::Failed => 3,
         }
     }
 
@@ -21,12 +25,17 @@ impl ProverJobStatus {
             0 => Self::Idle,
             1 => Self::InProgress,
             2 => Self::Done,
+            3 => Self::Failed,
             _ => anyhow::bail!("Incorrect ProverJobStatus number: {}", num),
         })
     }
 }
 
+/// Priority of the single block proofs. Jobs with the lower priority value are given
+/// to the provers first, and the jobs with the same priority are given in the order of blocks.
 pub const SINGLE_PROOF_JOB_PRIORITY: i32 = 1;
+/// Priority of the aggregated proofs. These jobs are the last step before the proof can be
+/// published and the blocks executed, so they go first.
 pub const AGGREGATED_PROOF_JOB_PRIORITY: i32 = 0;
 
 #[derive(Debug, Clone)]
@@ -35,6 +44,9 @@ pub struct ProverJob {
     pub first_block: BlockNumber,
     pub last_block: BlockNumber,
     pub job_data: serde_json::Value,
+    /// Amount of times the job was given to the provers, including the current one.
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
 }
 
 impl ProverJob {
@@ -43,16 +55,30 @@ impl ProverJob {
         first_block: BlockNumber,
         last_block: BlockNumber,
         job_data: serde_json::Value,
+        attempts: i32,
+        created_at: DateTime<Utc>,
     ) -> Self {
         Self {
             job_id,
             first_block,
             last_block,
             job_data,
+            attempts,
+            created_at,
         }
     }
 }
 
+/// Restrictions on the jobs which can be given to the prover.
+/// The jobs not satisfying them are skipped in favor of the next compatible ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProverJobFilter {
+    /// Block chunk sizes of the single block proofs the prover can create, `None` if any.
+    pub block_sizes: Option<Vec<usize>>,
+    /// Maximum amount of blocks in the aggregated proof the prover can create, `None` if any.
+    pub max_aggregated_blocks: Option<usize>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ProverJobType {
     SingleProof,
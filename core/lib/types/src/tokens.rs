@@ -4,7 +4,7 @@ use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
 use std::{fmt, fs::read_to_string, path::PathBuf, str::FromStr};
 use zksync_utils::parse_env;
-use zksync_utils::UnsignedRatioSerializeAsDecimal;
+use zksync_utils::{OptionUnsignedRatioSerializeAsDecimal, UnsignedRatioSerializeAsDecimal};
 
 // Order of the fields is important (from more specific types to less specific types)
 /// Set of values that can be interpreted as a token descriptor.
@@ -144,6 +144,56 @@ pub struct TokenMarketVolume {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Manual decision about whether the token can be used to pay fees,
+/// which takes precedence over the liquidity check.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FeeTokenEligibility {
+    /// Token is accepted for paying fees regardless of its market volume.
+    Allowed,
+    /// Token is never accepted for paying fees.
+    Denied,
+}
+
+/// Per-token fee settings configured by the operator.
+///
+/// Each field is optional, `None` means that the default behavior is used.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTokenSettings {
+    /// Overrides the decision of the fee token validator.
+    pub eligibility: Option<FeeTokenEligibility>,
+    /// Overrides whether the fees paid in this token are subsidized.
+    pub subsidized: Option<bool>,
+    /// Fixed USD price of the token (e.g. for stablecoins), used instead of the price API.
+    #[serde(default, with = "OptionUnsignedRatioSerializeAsDecimal")]
+    pub fixed_usd_price: Option<Ratio<BigUint>>,
+}
+
+/// Fee settings stored for a certain token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTokenOverride {
+    pub token_id: TokenId,
+    #[serde(flatten)]
+    pub settings: FeeTokenSettings,
+    /// Subject of the admin token used to perform the last change.
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Audit log entry describing a single change of the token fee settings.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTokenOverrideChange {
+    pub id: i64,
+    pub token_id: TokenId,
+    /// New settings of the token, `None` if the override was removed.
+    pub settings: Option<FeeTokenSettings>,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
 pub enum ChangePubKeyFeeType {
     Onchain,
@@ -232,4 +282,31 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn fee_token_settings_deserialize() {
+        let deserialized: FeeTokenSettings =
+            serde_json::from_str(r#"{ "eligibility": "allowed" }"#).unwrap();
+        assert_eq!(
+            deserialized,
+            FeeTokenSettings {
+                eligibility: Some(FeeTokenEligibility::Allowed),
+                ..Default::default()
+            }
+        );
+
+        let deserialized: FeeTokenSettings =
+            serde_json::from_str(r#"{ "subsidized": false, "fixedUsdPrice": "1.5" }"#).unwrap();
+        assert_eq!(
+            deserialized,
+            FeeTokenSettings {
+                subsidized: Some(false),
+                fixed_usd_price: Some(Ratio::new(BigUint::from(3u32), BigUint::from(2u32))),
+                ..Default::default()
+            }
+        );
+
+        let deserialized: FeeTokenSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(deserialized, FeeTokenSettings::default());
+    }
 }
@@ -39,6 +39,31 @@ impl UnsignedRatioSerializeAsDecimal {
     }
 }
 
+/// Used to annotate `Option<Ratio<BigUint>>` fields that you want to serialize like decimals.
+#[derive(Clone, Debug)]
+pub struct OptionUnsignedRatioSerializeAsDecimal;
+impl OptionUnsignedRatioSerializeAsDecimal {
+    pub fn serialize<S>(value: &Option<Ratio<BigUint>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let decimal_value = value.as_ref().map(|value| ratio_to_big_decimal(value, 18));
+
+        Option::serialize(&decimal_value, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Ratio<BigUint>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let optional_big_decimal: Option<BigDecimal> = Option::deserialize(deserializer)?;
+
+        optional_big_decimal
+            .map(|value| big_decimal_to_ratio(&value).map_err(de::Error::custom))
+            .transpose()
+    }
+}
+
 /// Used to serialize BigUint as radix 10 string.
 #[derive(Clone, Debug)]
 pub struct BigUintSerdeAsRadix10Str;
@@ -212,6 +237,29 @@ mod test {
         assert_eq!(expected.0, ratio.0);
     }
 
+    /// Tests that optional `Ratio` serializer works correctly.
+    #[test]
+    fn test_optional_ratio_serialize_as_decimal() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct OptionRatioSerdeWrapper(
+            #[serde(with = "OptionUnsignedRatioSerializeAsDecimal")] pub Option<Ratio<BigUint>>,
+        );
+
+        for expected in vec![
+            OptionRatioSerdeWrapper(Some(Ratio::new(
+                BigUint::from(101u64),
+                BigUint::from(100u64),
+            ))),
+            OptionRatioSerdeWrapper(None),
+        ] {
+            let value = serde_json::to_value(expected.clone())
+                .expect("cannot serialize Option<Ratio> as Decimal");
+            let ratio: OptionRatioSerdeWrapper = serde_json::from_value(value)
+                .expect("cannot deserialize Option<Ratio> from Decimal");
+            assert_eq!(expected.0, ratio.0);
+        }
+    }
+
     /// Tests that `BigUint` serializer works correctly.
     #[test]
     fn test_serde_big_uint_wrapper() {
@@ -32,6 +32,12 @@ export async function migrate() {
     await utils.exec('cd core/lib/storage && diesel migration run');
 }
 
+export async function migrateArtifacts() {
+    await utils.confirmAction();
+    console.log('Moving witnesses and proofs to the artifact store...');
+    await utils.spawn('cargo run --bin migrate_artifacts --release');
+}
+
 export async function setup() {
     await basicSetup();
     await utils.spawn('cargo sqlx prepare --check || cargo sqlx prepare');
@@ -76,6 +82,10 @@ export const command = new Command('db')
 
 command.command('drop').description('drop the database').action(drop);
 command.command('migrate').description('run migrations').action(migrate);
+command
+    .command('migrate-artifacts')
+    .description('move witnesses and proofs from the database to the artifact store')
+    .action(migrateArtifacts);
 command
     .command('basic-setup')
     .description('initialize the database and perform migrations (without sqlx call)')
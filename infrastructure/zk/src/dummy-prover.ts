@@ -30,6 +30,10 @@ export async function status() {
 async function setStatus(value: boolean, redeploy: boolean) {
     env.modify('CONTRACTS_TEST_DUMMY_VERIFIER', `CONTRACTS_TEST_DUMMY_VERIFIER="${value}"`);
     env.modify_contracts_toml('CONTRACTS_TEST_DUMMY_VERIFIER', `CONTRACTS_TEST_DUMMY_VERIFIER="${value}"`);
+    // Proofs created by the dummy prover can't pass the server-side verification.
+    // The flag is persisted in the toml config as well, so that it's not restored by the config compilation.
+    env.modify('PROVER_CORE_VERIFY_PROOFS', `PROVER_CORE_VERIFY_PROOFS="${!value}"`);
+    env.modify_prover_core_toml('PROVER_CORE_VERIFY_PROOFS', !value);
     await status();
     if (redeploy) {
         console.log('Redeploying the contract...');
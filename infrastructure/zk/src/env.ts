@@ -137,6 +137,25 @@ export function modify_contracts_toml(variable: string, assignedVariable: string
     fs.writeFileSync(toml_file, toml.stringify(toml_res));
 }
 
+// replaces a variable of the `prover.core` section in the prover.toml config file
+// takes variable name, e.g. PROVER_CORE_VARIABLE
+// and the new value, e.g. true
+export function modify_prover_core_toml(variable: string, value: boolean) {
+    const toml_file = `${process.env.ENV_DIR}/prover.toml`;
+
+    if (!fs.existsSync(toml_file)) {
+        console.log(`prover.toml config file was not found, skipping update...`);
+        return;
+    }
+
+    const source = fs.readFileSync(toml_file).toString();
+    const toml_res = toml.parse(source);
+    const trimmed_variable = variable.replace('PROVER_CORE_', '').toLowerCase();
+    // @ts-ignore
+    toml_res['prover']['core'][trimmed_variable] = value;
+    fs.writeFileSync(toml_file, toml.stringify(toml_res));
+}
+
 export const command = new Command('env')
     .arguments('[env_name]')
     .description('get or set zksync environment')
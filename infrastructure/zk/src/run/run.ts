@@ -99,6 +99,10 @@ export async function exitProof(...args: string[]) {
     await utils.spawn(`cargo run --example generate_exit_proof --release -- ${args.join(' ')}`);
 }
 
+export async function exportBlockArtifact(block: string, output: string) {
+    await utils.spawn(`cargo run --bin block_artifact --release -- export --block ${block} --output ${output}`);
+}
+
 export async function catLogs(exitCode?: number) {
     utils.allowFailSync(() => {
         console.log('\nSERVER LOGS:\n', fs.readFileSync('server.log').toString());
@@ -211,17 +215,33 @@ command
 command
     .command('exit-proof')
     .option('--account <id>')
+    .option('--address <address>')
     .option('--token <id>')
+    .option('--snapshot <path>', 'restore the state from the snapshot file instead of the database')
+    .option('--export-snapshot <path>', 'export the state snapshot restored from the database')
     .option('--help')
-    .description('generate exit proof')
+    .description('generate exit proof and the exodus transaction calldata')
     .action(async (cmd: Command) => {
-        if (!cmd.account || !cmd.token) {
+        const args = [];
+        if (cmd.account) args.push('--account_id', cmd.account);
+        if (cmd.address) args.push('--address', cmd.address);
+        if (cmd.token) args.push('--token', cmd.token);
+        if (cmd.snapshot) args.push('--snapshot', cmd.snapshot);
+        if (cmd.exportSnapshot) args.push('--export_snapshot', cmd.exportSnapshot);
+
+        const proofRequested = (cmd.account || cmd.address) && cmd.token;
+        if (!proofRequested && !cmd.exportSnapshot) {
             await exitProof('--help');
         } else {
-            await exitProof('--account_id', cmd.account, '--token', cmd.token);
+            await exitProof(...args);
         }
     });
 
+command
+    .command('export-block-artifact <block> <output>')
+    .description('export block witness and proof to be proven or verified offline with the `block_artifact` tool')
+    .action(exportBlockArtifact);
+
 command
     .command('loadtest [options...]')
     .description('run the loadtest')
@@ -31,6 +31,11 @@ export async function gen(command: 'contract' | 'all' | 'circuit-size') {
     fs.copyFileSync(`${outputDir}/KeysWithPlonkVerifier.sol`, 'contracts/contracts/KeysWithPlonkVerifier.sol');
 }
 
+export async function opCircuitSize(output?: string) {
+    const outputArg = output ? ` --output ${output}` : '';
+    await utils.spawn(`cargo run --bin key_generator --release -- op-circuit-size${outputArg}`);
+}
+
 export async function unpack() {
     const keysTarball = verfiyKeysTarball();
     if (!fs.existsSync(`keys/packed/${keysTarball}`)) {
@@ -49,6 +54,10 @@ export async function pack() {
 
 export const command = new Command('verify-keys').description('manage verification keys');
 
+command
+    .command('op-circuit-size [output]')
+    .description('report circuit size of the blocks with a single operation of each type as JSON')
+    .action(opCircuitSize);
 command.command('pack').description('reverse of unpack').action(pack);
 command.command('unpack').description('unpacks verification keys for your current circuit parameters').action(unpack);
 
@@ -64,6 +64,26 @@ pub trait Provider {
         token: impl Into<TokenLike> + Send + 'async_trait,
     ) -> ResponseResult<BigUint>;
 
+    /// Obtains the fee for the transactions batch along with the fees of the separate transactions.
+    /// `tx_tokens` are the tokens of the batch transactions: the result contains the options to pay
+    /// the batch fee by any of the batch members in its token, the cheapest one first.
+    ///
+    /// By default, only the total fee obtained with `get_txs_batch_fee` is returned.
+    async fn get_txs_batch_fee_details(
+        &self,
+        tx_types: Vec<TxFeeTypes>,
+        addresses: Vec<Address>,
+        _tx_tokens: Vec<TokenLike>,
+        token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<BatchFee> {
+        let total_fee = self.get_txs_batch_fee(tx_types, addresses, token).await?;
+        Ok(BatchFee {
+            total_fee,
+            transactions: Vec::new(),
+            options: Vec::new(),
+        })
+    }
+
     /// Requests and returns information about an Ethereum operation given its `serial_id`.
     async fn ethop_info(&self, serial_id: u32) -> ResponseResult<EthOpInfo>;
 
@@ -139,12 +159,25 @@ impl Provider for RpcProvider {
         addresses: Vec<Address>,
         token: impl Into<TokenLike> + Send + 'async_trait,
     ) -> ResponseResult<BigUint> {
-        let msg = JsonRpcRequest::get_txs_batch_fee_in_wei(tx_types, addresses, token.into());
+        let msg =
+            JsonRpcRequest::get_txs_batch_fee_in_wei(tx_types, addresses, token.into(), Vec::new());
 
         let batch_fee: BatchFee = self.send_and_deserialize(&msg).await?;
         Ok(batch_fee.total_fee)
     }
 
+    async fn get_txs_batch_fee_details(
+        &self,
+        tx_types: Vec<TxFeeTypes>,
+        addresses: Vec<Address>,
+        tx_tokens: Vec<TokenLike>,
+        token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<BatchFee> {
+        let msg =
+            JsonRpcRequest::get_txs_batch_fee_in_wei(tx_types, addresses, token.into(), tx_tokens);
+        self.send_and_deserialize(&msg).await
+    }
+
     async fn ethop_info(&self, serial_id: u32) -> ResponseResult<EthOpInfo> {
         let msg = JsonRpcRequest::ethop_info(serial_id);
         self.send_and_deserialize(&msg).await
@@ -420,8 +453,12 @@ mod messages {
             tx_types: Vec<TxFeeTypes>,
             addresses: Vec<Address>,
             token_like: TokenLike,
+            tx_tokens: Vec<TokenLike>,
         ) -> Self {
-            let params = json_values![tx_types, addresses, token_like];
+            let mut params = json_values![tx_types, addresses, token_like];
+            if !tx_tokens.is_empty() {
+                params.push(to_json_value(tx_tokens));
+            }
             Self::create("get_txs_batch_fee_in_wei", params)
         }
     }
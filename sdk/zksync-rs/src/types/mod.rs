@@ -1,8 +1,10 @@
-use num::BigUint;
+use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use zksync_types::{AccountId, Address, Nonce, PubKeyHash, Token};
-use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
+use zksync_types::{AccountId, Address, Nonce, PubKeyHash, Token, TokenId};
+use zksync_utils::{
+    BigUintSerdeAsRadix10Str, BigUintSerdeWrapper, UnsignedRatioSerializeAsDecimal,
+};
 
 pub type Tokens = HashMap<String, Token>;
 
@@ -167,7 +169,25 @@ pub struct Fee {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct BatchFee {
+pub struct BatchFee {
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Fees of the batch transactions if they were sent separately.
+    #[serde(default)]
+    pub transactions: Vec<Fee>,
+    /// Ways to pay the batch fee in the tokens of the batch transactions, the cheapest one goes first.
+    #[serde(default)]
+    pub options: Vec<BatchFeeOption>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFeeOption {
+    pub token: TokenId,
+    /// Indices of the batch transactions which can carry the fee in this token.
+    pub payers: Vec<usize>,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub total_fee_usd: Ratio<BigUint>,
 }